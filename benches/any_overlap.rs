@@ -0,0 +1,25 @@
+//! `any_overlap` against `count_overlaps` on a dense fragment workload:
+//! short fragments over heavily stacked features, where most fragments have
+//! many hits.
+//!
+//! Run with `cargo bench --bench any_overlap`.
+
+mod common;
+
+use ruranges::overlaps::{any_overlap, count_overlaps};
+
+use common::{random_intervals, time};
+
+const CHROM_LEN: i64 = 10_000_000;
+const N_CHRS: u32 = 2;
+
+fn main() {
+    let (chrs, starts, ends) = random_intervals(1_000_000, N_CHRS, CHROM_LEN, 50, 500, 7);
+    for n2 in [100_000, 500_000] {
+        let (chrs2, starts2, ends2) = random_intervals(n2, N_CHRS, CHROM_LEN, 200, 2_000, 11);
+        println!("1000000 fragments against {n2} features");
+        let any = time("any_overlap", 5, || any_overlap(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0));
+        let count = time("count_overlaps", 5, || count_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0));
+        println!("{:<40} {:>11.1}x", "speedup", count.as_secs_f64() / any.as_secs_f64());
+    }
+}
@@ -0,0 +1,50 @@
+//! Synthetic interval sets shared by the benchmarks.
+
+use std::time::{Duration, Instant};
+
+/// Unsorted intervals on `n_chrs` chromosomes of `chrom_len` bases,
+/// with lengths in `min_len..max_len`, from a fixed-seed generator so every
+/// run times the same data.
+pub fn random_intervals(
+    n: usize,
+    n_chrs: u32,
+    chrom_len: i64,
+    min_len: i64,
+    max_len: i64,
+    seed: u64,
+) -> (Vec<u32>, Vec<i64>, Vec<i64>) {
+    let mut state = seed;
+    let mut next = move || {
+        // xorshift64*
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    };
+
+    let mut chrs = Vec::with_capacity(n);
+    let mut starts = Vec::with_capacity(n);
+    let mut ends = Vec::with_capacity(n);
+    for _ in 0..n {
+        let len = min_len + (next() % (max_len - min_len) as u64) as i64;
+        let start = (next() % (chrom_len - len) as u64) as i64;
+        chrs.push((next() % n_chrs as u64) as u32);
+        starts.push(start);
+        ends.push(start + len);
+    }
+    (chrs, starts, ends)
+}
+
+/// Best wall time of `runs` calls of `f`, printed under `name`.
+pub fn time<R>(name: &str, runs: usize, mut f: impl FnMut() -> R) -> Duration {
+    let best = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default();
+    println!("{name:<40} {best:>12.2?}");
+    best
+}
@@ -0,0 +1,46 @@
+//! `coverage_rle` against a dense per-base depth array on sparse data:
+//! peaks covering a small fraction of a few large chromosomes.
+//!
+//! Run with `cargo bench --bench coverage_rle`.
+
+mod common;
+
+use ruranges::coverage::coverage_rle;
+
+use common::{random_intervals, time};
+
+const CHROM_LEN: i64 = 50_000_000;
+const N_CHRS: u32 = 4;
+
+/// Per-base depth of every chromosome via a difference array, summed so
+/// the work is not optimised away.
+fn dense_coverage(chrs: &[u32], starts: &[i64], ends: &[i64]) -> u64 {
+    let mut total = 0;
+    for chr in 0..N_CHRS {
+        let mut diff = vec![0i32; CHROM_LEN as usize + 1];
+        for i in (0..chrs.len()).filter(|&i| chrs[i] == chr) {
+            diff[starts[i] as usize] += 1;
+            diff[ends[i] as usize] -= 1;
+        }
+        let mut depth = 0i32;
+        let depths: Vec<u32> = diff[..CHROM_LEN as usize]
+            .iter()
+            .map(|&d| {
+                depth += d;
+                depth as u32
+            })
+            .collect();
+        total += depths.iter().map(|&d| d as u64).sum::<u64>();
+    }
+    total
+}
+
+fn main() {
+    for n in [10_000, 100_000] {
+        let (chrs, starts, ends) = random_intervals(n, N_CHRS, CHROM_LEN, 200, 2_000, 42);
+        println!("{n} intervals on {N_CHRS} x {CHROM_LEN} bp");
+        let rle = time("coverage_rle", 5, || coverage_rle(&chrs, &starts, &ends));
+        let dense = time("dense depth array", 5, || dense_coverage(&chrs, &starts, &ends));
+        println!("{:<40} {:>11.1}x", "speedup", dense.as_secs_f64() / rle.as_secs_f64());
+    }
+}
@@ -1,5 +1,6 @@
 import importlib
-from typing import Any, Callable, Literal, Sequence, TypeVar
+import warnings
+from typing import Any, Callable, Iterator, Literal, NamedTuple, Sequence, TypeVar
 import numpy as np
 from numpy.typing import NDArray
 
@@ -14,6 +15,41 @@ GroupIdInt = TypeVar("GroupIdInt", np.int8, np.int16, np.int32)
 RangeInt = TypeVar("RangeInt", bound=np.integer)
 
 
+# Named results.  They are plain tuples underneath, so positional unpacking,
+# indexing, isinstance(..., tuple) and pickling keep working.
+class OverlapsResult(NamedTuple):
+    """``(query_idx, subject_idx)`` pairs from :func:`overlaps`."""
+
+    query_idx: NDArray[np.uint32]
+    subject_idx: NDArray[np.uint32]
+
+    def to_dict(self) -> dict[str, NDArray]:
+        return self._asdict()
+
+
+class NearestResult(NamedTuple):
+    """``(query_idx, subject_idx, distance)`` rows from :func:`nearest`."""
+
+    query_idx: NDArray[np.uint32]
+    subject_idx: NDArray[np.uint32]
+    distance: NDArray[RangeInt]
+
+    def to_dict(self) -> dict[str, NDArray]:
+        return self._asdict()
+
+
+class NearestTiesResult(NamedTuple):
+    """:class:`NearestResult` plus ``n_ties``, from ``nearest(return_ties=True)``."""
+
+    query_idx: NDArray[np.uint32]
+    subject_idx: NDArray[np.uint32]
+    distance: NDArray[RangeInt]
+    n_ties: NDArray[np.uint32]
+
+    def to_dict(self) -> dict[str, NDArray]:
+        return self._asdict()
+
+
 # dtype-suffix map shared by every operation
 # (group_dtype, range_dtype)  →  (suffix, group_target_dtype, range_target_dtype)
 _SUFFIX_TABLE = {
@@ -45,30 +81,84 @@ _SUFFIX_TABLE = {
     (np.dtype(np.uint64), np.dtype(np.int16)): ("u64_i64", np.uint64, np.int64),
     (np.dtype(np.uint64), np.dtype(np.int32)): ("u64_i64", np.uint64, np.int64),
     (np.dtype(np.uint64), np.dtype(np.int64)): ("u64_i64", np.uint64, np.int64),
+    # ─── float positions (overlaps, merge and cluster only) ──────────
+    (np.dtype(np.uint8), np.dtype(np.float32)): ("u32_f32", np.uint32, np.float32),
+    (np.dtype(np.uint16), np.dtype(np.float32)): ("u32_f32", np.uint32, np.float32),
+    (np.dtype(np.uint32), np.dtype(np.float32)): ("u32_f32", np.uint32, np.float32),
+    (np.dtype(np.uint8), np.dtype(np.float64)): ("u32_f64", np.uint32, np.float64),
+    (np.dtype(np.uint16), np.dtype(np.float64)): ("u32_f64", np.uint32, np.float64),
+    (np.dtype(np.uint32), np.dtype(np.float64)): ("u32_f64", np.uint32, np.float64),
+    (np.dtype(np.uint64), np.dtype(np.float32)): ("u64_f64", np.uint64, np.float64),
+    (np.dtype(np.uint64), np.dtype(np.float64)): ("u64_f64", np.uint64, np.float64),
 }
 
 RETURN_SIGNATURES: dict[str, tuple[str, ...]] = {
     "chromsweep_numpy": ("grp", "grp"),
+    "chromsweep_within_group_numpy": ("idx", "idx"),
+    "contained_numpy": ("idx", "idx"),
+    "annotate_numpy": ("label",),
     "sweepline_numpy": ("grp", "grp"),
-    "nearest_numpy": ("grp", "grp", "pos"),
+    "overlap_matrix_numpy": ("idx", "idx"),
+    "point_overlaps_numpy": ("idx", "idx"),
+    "pairs_within_numpy": ("idx", "idx", "pos"),
+    "dump_events_numpy": ("grp", "pos", "flag", "flag", "idx"),
+    "nearest_numpy": ("grp", "grp", "pos", "count"),
     "subtract_numpy": ("grp", "pos", "pos"),
+    "subtract_min_fraction_numpy": ("grp", "pos", "pos"),
+    "clip_to_numpy": ("idx", "pos", "pos"),
     "complement_overlaps_numpy": ("grp",),
+    "no_overlap_mask_numpy": ("mask",),
+    "complement_set2_numpy": ("idx", "pos", "pos"),
     "count_overlaps_numpy": ("count",),
+    "any_overlap_numpy": ("mask",),
+    "evaluate_overlap_numpy": ("score", "score", "score"),
+    "jaccard_numpy": ("score", "stats"),
+    "coverage_normalize_rpm_numpy": ("score",),
+    "coverage_normalize_rpkm_numpy": ("score",),
+    "anchor_profile_numpy": ("count",),
+    "coverage_rle_numpy": ("grp", "pos", "pos", "count"),
+    "count_overlaps_both_numpy": ("count", "count"),
+    "overlap_matrix_dense_numpy": ("count",),
     "sort_groups_numpy": ("idx",),
     "sort_intervals_numpy": ("idx",),
+    "sort_intervals_apply_numpy": ("grp", "pos", "pos", "idx"),
+    "chrom_boundaries_numpy": ("idx", "idx"),
     "cluster_numpy": ("idx", "count"),
+    "cluster_min_coverage_numpy": ("idx", "idx"),
+    "cluster_transitions_numpy": ("pos", "idx"),
+    "cluster_stats_numpy": ("idx", "count", "score", "score", "pos", "pos"),
     "max_disjoint_numpy": ("idx",),
     "merge_numpy": ("grp", "pos", "pos", "count"),
+    "merge_values_numpy": ("grp", "pos", "pos", "count", "score", "score", "score"),
+    "span_numpy": ("grp", "pos", "pos", "count"),
+    "merge_ids_numpy": ("idx",),
     "window_numpy": ("grp", "pos", "pos"),
-    "tile_numpy": ("grp", "pos", "pos", "fraction"),
+    "tile_numpy": ("grp", "pos", "pos", "fraction", "fraction"),
+    "tile_grouped_numpy": ("grp", "pos", "pos", "fraction", "fraction", "pos"),
+    "tile_n_numpy": ("grp", "pos", "pos", "ordinal"),
+    "tile_aggregate_count_numpy": ("grp", "pos", "pos", "count"),
+    "tile_scores_numpy": ("grp", "pos", "pos", "score"),
     "complement_numpy": ("grp", "pos", "pos", "index"),
+    "gaps_numpy": ("idx", "pos", "pos"),
     "boundary_numpy": ("index", "pos", "pos", "count"),
     "spliced_subsequence_numpy": ("index", "pos", "pos", "_strand"),
     "spliced_subsequence_multi_numpy": ("index", "pos", "pos", "_strand"),
     "split_numpy": ("index", "pos", "pos"),
+    "split_max_length_numpy": ("index", "pos", "pos"),
+    "partition_into_layers_numpy": ("layer",),
     "extend_numpy": ("pos", "pos"),
+    "extend_rows_numpy": ("pos", "pos"),
+    "spliced_length_numpy": ("pos",),
+    "midpoints_numpy": ("pos",),
+    "midpoint_intervals_numpy": ("pos", "pos"),
+    "extend_5prime_numpy": ("pos", "pos"),
+    "extend_3prime_numpy": ("pos", "pos"),
     "genome_bounds_numpy": ("index", "pos", "pos"),
+    "permute_starts_numpy": ("pos", "pos"),
+    "genome_bounds_flags_numpy": ("index", "pos", "pos", "flag"),
+    "outside_bounds_circular_numpy": ("index", "pos", "pos"),
     "group_cumsum_numpy": ("index", "pos", "pos"),
+    "group_cumsum_totals_numpy": ("index", "pos", "pos", "pos"),
     "map_to_global_numpy": ("index", "pos", "pos", "strand"),
 }
 
@@ -81,20 +171,29 @@ def overlaps(
     ends2: NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
     groups2: NDArray[GroupIdInt] | None = None,
-    multiple: Literal["first", "all", "last", "contained"] = "all",
+    multiple: Literal["first", "all", "last"] = "all",
     contained: bool = False,
     sort_output: bool = True,
-    slack: int = 0,
-) -> tuple[GroupIdInt, GroupIdInt]:
+    slack: int | NDArray[RangeInt] = 0,
+    max_per_query: int | None = None,
+    sort_by: Literal["idx", "idx1", "idx2", "length_desc", "length_asc", "none"] | None = None,
+    min_overlap_bp: int | None = None,
+    strand_mode: Literal["any", "same", "opposite"] = "any",
+    strands: NDArray[np.bool_] | None = None,
+    strands2: NDArray[np.bool_] | None = None,
+    validate: bool = False,
+) -> OverlapsResult:
     """
     Compute overlapping intervals between two sets of ranges.
 
     The four mandatory arrays (starts, ends, starts2, ends2) must all have the same length.
     If one of groups or groups2 is provided, then both must be provided and have the same length
-    as the other arrays.
+    as the other arrays.  Both must use the same id for the same chromosome; categorical codes
+    from different frames can be aligned with :func:`remap_codes`.
 
-    The function returns a tuple (idx1, idx2) of numpy arrays, where each pair (idx1[i], idx2[i])
-    indicates an overlapping interval between the first and second set.
+    The function returns an OverlapsResult, a named tuple of the numpy arrays
+    ``query_idx`` and ``subject_idx`` (idx1, idx2), where each pair
+    (idx1[i], idx2[i]) indicates an overlapping interval between the first and second set.
 
     Examples
     --------
@@ -110,7 +209,7 @@ def overlaps(
     >>> ends2   = np.array([6, 25], dtype=RangeInt)
     >>> result = overlaps(starts=starts, ends=ends, starts2=starts2, ends2=ends2)
     >>> # In this hypothetical example only the first intervals overlap.
-    >>> result
+    >>> tuple(result)
     (array([0], dtype=uint32), array([0], dtype=uint32))
 
     With groups:
@@ -124,11 +223,44 @@ def overlaps(
     >>> result = overlaps(starts=starts, ends=ends, starts2=starts2, ends2=ends2,
     ...                   groups=groups, groups2=groups2)
     >>> # Here the algorithm checks overlaps only within the same group.
-    >>> result
+    >>> tuple(result)
     (array([0], dtype=uint32), array([0], dtype=uint32))
 
     Additional parameters such as `multiple`, `contained`, and `slack` control the overlap
-    behavior; see the documentation for details.
+    behavior; see the documentation for details.  *multiple* picks which hits
+    of each query are kept: ``"all"``, ``"first"`` or ``"last"``; anything
+    else raises ``ValueError``.  Containment is requested with *contained*,
+    not through *multiple*.  *slack* may also be an array
+    with one tolerance per row of *(starts, ends)*.
+
+    Coordinates may also be ``float32``/``float64`` (with groups given), for
+    non-genomic data such as normalized positions.
+
+    *max_per_query* keeps at most that many matches per query interval,
+    after *multiple* is applied.  The kept matches are those with the lowest
+    subject indices; *sort_output* only orders the result by query index and
+    does not change which are kept.
+
+    With *contained* only query intervals lying inside a subject interval
+    are reported; :func:`contained_overlaps` also offers the reverse
+    direction.
+
+    *min_overlap_bp* drops pairs sharing fewer than that many bases, like
+    bedtools' minimum overlap; touching intervals, or intervals only brought
+    together by *slack*, share no base.  It is applied before *multiple* and
+    *max_per_query*.
+
+    *sort_by* overrides *sort_output*: ``"idx1"`` orders the pairs by query
+    index, ``"idx2"`` by subject index (ties by query index), e.g. for a
+    merge-join against the subject table, and ``"none"`` keeps sweep order.
+    ``"length_desc"`` and ``"length_asc"`` order by the number of bases shared
+    (ties by query index), e.g. for greedily assigning reads to their best
+    annotation.  ``"idx"`` is an alias of ``"idx1"``.
+
+    *strand_mode* ``"same"`` keeps only pairs whose *strands* and *strands2*
+    flags are equal, ``"opposite"`` only pairs where they differ, e.g. for
+    stranded RNA-seq libraries.  Both flag arrays are then required.  Like
+    *min_overlap_bp* it is applied before *multiple* and *max_per_query*.
 
     Raises
     ------
@@ -136,7 +268,10 @@ def overlaps(
         If any of the length checks fail or if only one of groups/groups2 is provided.
     """
 
-    return _dispatch_binary(
+    if sort_by is None:
+        sort_by = "idx1" if sort_output else "none"
+    slack, slack_kw = _split_slack(slack, len(starts))
+    return OverlapsResult(*_dispatch_binary(
         "chromsweep_numpy",
         groups,
         starts,
@@ -147,9 +282,322 @@ def overlaps(
         slack,
         overlap_type=multiple,
         contained=contained,
-        sort_output=sort_output,
+        sort_by=sort_by,
+        **slack_kw,
+        max_per_query=max_per_query,
+        min_overlap_bp=min_overlap_bp,
+        strand_mode=strand_mode,
+        strands=None if strands is None else np.asarray(strands, dtype=np.bool_),
+        strands2=None if strands2 is None else np.asarray(strands2, dtype=np.bool_),
+        validate=validate,
+    ))
+
+
+def overlaps_polars(
+    df: "pl.DataFrame",
+    df2: "pl.DataFrame",
+    *,
+    chrom_col: str = "Chromosome",
+    start_col: str = "Start",
+    end_col: str = "End",
+    **kwargs: Any,
+) -> "pl.DataFrame":
+    """
+    :func:`overlaps` for two polars DataFrames, returning the pairs as a
+    DataFrame with ``uint32`` columns ``idx`` and ``idx2``.
+
+    Chromosomes may be strings or categoricals.  They are matched by name,
+    not by physical code, so frames whose categoricals use different
+    mappings still pair up correctly (see :func:`remap_codes`).  Start and end columns are
+    passed to the kernel without copying when they are contiguous and free
+    of nulls.  Remaining keyword arguments go to :func:`overlaps`.
+    """
+    import polars as pl
+
+    chroms = pl.concat([df[chrom_col].cast(pl.String), df2[chrom_col].cast(pl.String)])
+    codes = chroms.cast(pl.Categorical).to_physical().to_numpy()
+
+    idx1, idx2 = overlaps(
+        starts=df[start_col].to_numpy(),
+        ends=df[end_col].to_numpy(),
+        starts2=df2[start_col].to_numpy(),
+        ends2=df2[end_col].to_numpy(),
+        groups=codes[: df.height],
+        groups2=codes[df.height :],
+        **kwargs,
+    )
+    return pl.DataFrame({"idx": idx1, "idx2": idx2})
+
+
+def remap_codes(
+    codes: NDArray[np.integer],
+    categories: Sequence[str] | NDArray,
+    target_categories: Sequence[str] | NDArray,
+) -> NDArray[np.uint32]:
+    """
+    Re-express categorical *codes* (indices into *categories*) in the code
+    space of *target_categories*, so two frames can be compared by code.
+
+    Group ids only match between the two sides of a binary operation when
+    they were encoded from the same name↔code mapping.  Physical codes of
+    polars categoricals from different frames (or without a shared string
+    cache) generally are not, so remap one side first::
+
+        codes2 = remap_codes(codes2, cats2, cats)
+
+    Names missing from *target_categories* get fresh codes starting at
+    ``len(target_categories)``, so they never match anything on the other
+    side.
+    """
+    categories = np.asarray(categories, dtype=object)
+    target_categories = np.asarray(target_categories, dtype=object)
+    lookup = {name: code for code, name in enumerate(target_categories)}
+
+    mapping = np.empty(len(categories), dtype=np.uint32)
+    fresh = len(target_categories)
+    for code, name in enumerate(categories):
+        if name in lookup:
+            mapping[code] = lookup[name]
+        else:
+            mapping[code] = fresh
+            fresh += 1
+    return mapping[np.asarray(codes)]
+
+
+def overlaps_chunked(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+    chunk_size: int = 1_000_000,
+) -> Iterator[tuple[NDArray[np.uint32], NDArray[np.uint32]]]:
+    """
+    Yield the overlapping pairs ``(idx1, idx2)`` in chunks of about
+    *chunk_size* pairs, so very large intersections never have to be held
+    in memory at once.
+
+    The sweep resumes where the previous chunk stopped.  A chunk can be
+    somewhat larger than *chunk_size*, by the number of intervals active
+    where it ends.  Pairs are in sweep order; concatenated, the chunks hold
+    the same pairs as ``overlaps(..., sort_by="none")``.
+    """
+    length = check_array_lengths(starts, ends, groups)
+    length2 = check_array_lengths(starts2, ends2, groups2)
+    groups = validate_groups(length, groups)
+    groups2 = validate_groups(length2, groups2)
+
+    chunks_cls, grp_t, pos_t = _resolve_rust_fn(
+        "OverlapChunks",
+        np.result_type(groups, groups2),
+        np.result_type(starts, ends, starts2, ends2),
+    )
+    yield from chunks_cls(
+        _cast(groups, grp_t),
+        _cast(starts, pos_t),
+        _cast(ends, pos_t),
+        _cast(groups2, grp_t),
+        _cast(starts2, pos_t),
+        _cast(ends2, pos_t),
+        slack=slack,
+        chunk_size=chunk_size,
+    )
+
+
+def contained_overlaps(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    how: Literal["a_in_b", "b_in_a", "either"] = "a_in_b",
+    slack: int = 0,
+    validate: bool = False,
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
+    """
+    Pairs where one interval lies entirely inside the other.
+
+    *how* picks the direction: ``"a_in_b"`` reports query intervals
+    *(starts, ends)* inside a subject interval *(starts2, ends2)*, the same
+    as ``overlaps(contained=True)``; ``"b_in_a"`` subjects inside a query;
+    ``"either"`` both, listing pairs of identical intervals once.
+    Containment includes shared boundaries, so ``[10, 20)`` lies inside
+    ``[10, 20)``.  Query intervals are widened by *slack* on both sides
+    before testing.
+
+    Returns ``(idx, idx2)`` sorted by query index, then subject index.
+    """
+    return _dispatch_binary(
+        "contained_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack=slack,
+        how=how,
+        validate=validate,
+    )
+
+
+def annotate_overlaps(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    labels2: NDArray[np.uint32],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+    overlap_type: Literal["first", "last", "largest"] = "first",
+) -> NDArray[np.uint32]:
+    """
+    Label every query interval with one overlapping annotation.
+
+    This is the left-join counterpart of :func:`overlaps`: the result has
+    one entry per row of *(starts, ends)*, holding the *labels2* entry of an
+    overlapping interval in *(starts2, ends2)*, or
+    ``np.iinfo(np.uint32).max`` when nothing overlaps it.
+
+    *overlap_type* picks among several hits: ``"first"`` and ``"last"`` go
+    by genomic order of the annotations (start, then row), not row order;
+    ``"largest"`` takes the one sharing the most bases.
+    """
+    return _dispatch_binary(
+        "annotate_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        labels2=np.asarray(labels2, dtype=np.uint32),
+        slack=slack,
+        overlap_type=overlap_type,
+    )
+
+
+def intersection_size(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+) -> int:
+    """
+    Total number of base pairs shared between the two interval sets.
+
+    Set 2 is counted as its union: a base of set 1 covered by several
+    overlapping set-2 intervals contributes once.  Overlapping set-1
+    intervals each contribute their own shared bases.
+
+    Returns
+    -------
+    int
+        Sum of the shared lengths, e.g. the numerator of a Jaccard index.
+    """
+    length = check_array_lengths(starts, ends, groups)
+    length2 = check_array_lengths(starts2, ends2, groups2)
+    groups = validate_groups(length, groups)
+    groups2 = validate_groups(length2, groups2)
+
+    # The kernel sums in its position dtype, so always run it on int64.
+    grp_t = _common_integer_dtype(groups, groups2)
+    rust_fn, grp_t, pos_t = _resolve_rust_fn("intersection_size_numpy", grp_t, np.dtype(np.int64))
+    return int(
+        rust_fn(
+            _cast(groups, grp_t),
+            _cast(starts, pos_t),
+            _cast(ends, pos_t),
+            _cast(groups2, grp_t),
+            _cast(starts2, pos_t),
+            _cast(ends2, pos_t),
+        )
+    )
+
+
+def query_single(
+    *,
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    start: int,
+    end: int,
+    groups2: NDArray[GroupIdInt] | None = None,
+    group: int = 0,
+    slack: int = 0,
+) -> NDArray[np.uint32]:
+    """
+    Overlaps of the single interval *(group, start, end)* with a database
+    that is **already sorted** by group then start (e.g. with
+    :func:`sort_intervals_apply`).
+
+    Finds the group by binary search and checks only the intervals starting
+    before the query ends, instead of running a full sweep; meant for
+    one-off lookups such as annotating a single variant.
+
+    Returns
+    -------
+    NDArray[np.uint32]
+        Positions in the sorted database arrays, ascending.
+    """
+    length = check_array_lengths(starts2, ends2, groups2)
+    groups2 = validate_groups(length, groups2)
+    pos_dt = np.result_type(starts2, ends2, np.min_scalar_type(start), np.min_scalar_type(end))
+    rust_fn, grp_t, pos_t = _resolve_rust_fn("query_single_numpy", groups2.dtype, pos_dt)
+    return rust_fn(
+        _cast(groups2, grp_t),
+        _cast(starts2, pos_t),
+        _cast(ends2, pos_t),
+        group,
+        start,
+        end,
+        slack,
     )
 
+
+def overlaps_within_group(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    group_ids: NDArray,
+    group_ids2: NDArray,
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> OverlapsResult:
+    """
+    Like :func:`overlaps`, but only pairs whose rows carry the same metadata
+    id (e.g. gene or sample) are reported.
+
+    *group_ids* / *group_ids2* hold one id per row of the first / second set
+    and may be of any dtype; they are independent of the chromosome
+    *groups*.  Pairs are sorted by query then subject index.
+    """
+    group_ids, group_ids2 = _shared_group_codes(group_ids, group_ids2)
+    return OverlapsResult(*_dispatch_binary(
+        "chromsweep_within_group_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack,
+        group_ids=group_ids,
+        group_ids2=group_ids2,
+    ))
+
 def overlaps_simple(
     *,
     starts: NDArray[RangeInt],
@@ -158,7 +606,7 @@ def overlaps_simple(
     ends2: NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
     groups2: NDArray[GroupIdInt] | None = None,
-    multiple: Literal["first", "all", "last", "contained"] = "all",
+    multiple: Literal["first", "all", "last"] = "all",
     contained: bool = False,
     sort_output: bool = True,
     slack: int = 0,
@@ -250,143 +698,504 @@ def overlaps_simple(
         no_checks=_no_checks,
     )
 
-def map_to_global(
+def overlap_matrix(
     *,
-    # ─── query (local) table ─────────────────────────────────────────
-    starts:    NDArray[RangeInt],
-    ends:      NDArray[RangeInt],
-    groups:    NDArray[GroupIdInt],
-    strand:    NDArray[np.bool_],
-    # ─── exon (annotation) table ─────────────────────────────────────
-    starts2:   NDArray[RangeInt],
-    ends2:     NDArray[RangeInt],
-    groups2:   NDArray[GroupIdInt],
-    chr_code2: NDArray[GroupIdInt],
-    genome_start2: NDArray[RangeInt],
-    genome_end2:   NDArray[RangeInt],
-    strand2:   NDArray[np.bool_],
-) -> tuple[NDArray, NDArray, NDArray, NDArray]:
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
     """
-    Vectorised transcript-to-genome projection.
+    All-vs-all overlaps within one interval set as a sparse COO matrix.
 
-    All arrays must be 1-D and already sorted per transcript by
-    (local_start).  The *strand* arrays encode '+' as True and '−' as False.
+    Only the upper triangle (``row < col``) is returned, so self-overlaps and
+    mirrored pairs are excluded.  Build a symmetric ``scipy.sparse`` matrix
+    with ``coo_matrix((ones, (rows, cols)), shape=(n, n))`` plus its
+    transpose.
 
-    Returns
-    -------
-    keep_idx : uint32
-        Row numbers into the *query* table (duplicated if interval splits).
-    g_start / g_end : same dtype as *starts*
-        Genomic coordinates of the mapped pieces.
-    g_strand_bool : bool
-        True ⇒ '+', False ⇒ '−'.
+    Examples
+    --------
+    >>> import numpy as np
+    >>> starts = np.array([0, 5, 12], dtype=np.int32)
+    >>> ends   = np.array([10, 15, 20], dtype=np.int32)
+    >>> overlap_matrix(starts=starts, ends=ends, groups=np.zeros(3, dtype=np.uint32))
+    (array([0, 1], dtype=uint32), array([1, 2], dtype=uint32))
     """
-
-    return _dispatch_map_global_binary(
-        "map_to_global_numpy",
-        groups,  starts,  ends,  strand,
-        groups2, starts2, ends2, strand2,
-        chr_code2, genome_start2, genome_end2,
+    return _dispatch_unary(
+        "overlap_matrix_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        slack=slack,
     )
 
 
-def nearest(
+def point_overlaps(
     *,
+    positions: NDArray[RangeInt],
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
-    starts2: NDArray[RangeInt],
-    ends2: NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
     groups2: NDArray[GroupIdInt] | None = None,
-    slack: int = 0,
-    k: int = 1,
-    include_overlaps: bool = True,
-    direction: Literal["forward", "backward", "any"] = "any",
-) -> tuple[NDArray[GroupIdInt], NDArray[GroupIdInt], NDArray[RangeInt]]:
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
     """
-    Find the *k* nearest intervals from *(starts2, ends2)* for every interval
-    in *(starts, ends)*, optionally restricting the search to matching
-    `groups` / `groups2`.
+    Intervals containing each point.
 
-    Parameters
-    ----------
-    starts, ends, starts2, ends2
-        Coordinate arrays (all same length and dtype ``RangeInt``).
-    groups, groups2
-        Optional per-row group IDs (e.g. chromosome numbers).  If one is
-        provided, the other **must** be provided too, and both must be the
-        same length as their corresponding coordinate arrays.
-    slack
-        Maximum distance allowed between intervals (0 ⇒ no limit).
-    k
-        Number of nearest neighbours to report per query interval.
-    include_overlaps
-        If *False*, overlapping intervals are *excluded*; otherwise they count
-        with distance 0.
-    direction
-        • ``"forward"`` – only neighbours that start **after** the query ends
-        • ``"backward"`` – only neighbours that end **before** the query starts
-        • ``"any"`` (default) – both directions.
+    A point at ``p`` is the zero-length interval ``[p, p)`` and overlaps
+    every interval with ``start <= p < end``; an interval ending at ``p``
+    does not contain it.  The same rule applies wherever zero-length
+    intervals are passed to :func:`overlaps` or :func:`count_overlaps`.
 
     Returns
     -------
-    idx1, idx2, dist
-        *idx1* / *idx2* are ``uint32`` indices into the first / second
-        interval sets; *dist* is the coordinate-typed distance between each
-        pair.
+    point_idx, interval_idx
+        Matching pairs, sorted by point index.
 
-    Raises
-    ------
-    ValueError
-        If the input lengths don’t match or only one of ``groups`` /
-        ``groups2`` is supplied.
+    Examples
+    --------
+    >>> import numpy as np
+    >>> point_overlaps(
+    ...     positions=np.array([0, 10, 15], dtype=np.int32),
+    ...     starts=np.array([0, 10], dtype=np.int32),
+    ...     ends=np.array([10, 20], dtype=np.int32),
+    ... )
+    (array([0, 1, 2], dtype=uint32), array([0, 1, 1], dtype=uint32))
     """
     return _dispatch_binary(
-        "nearest_numpy",
+        "point_overlaps_numpy",
         groups,
+        positions,
+        positions,
+        groups2,
         starts,
         ends,
-        groups2,
-        starts2,
-        ends2,
-        slack,
-        k=k,
-        include_overlaps=include_overlaps,
-        direction=direction,
     )
 
 
-def subtract(
+def dump_events(
+    *,
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
     starts2: NDArray[RangeInt],
     ends2: NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
     groups2: NDArray[GroupIdInt] | None = None,
-) -> tuple[NDArray[GroupIdInt], NDArray[RangeInt], NDArray[RangeInt]]:
+    slack: int = 0,
+) -> tuple[
+    NDArray[GroupIdInt],
+    NDArray[RangeInt],
+    NDArray[np.bool_],
+    NDArray[np.bool_],
+    NDArray[np.uint32],
+]:
+    """
+    Debugging aid: the sorted event stream the overlap sweeps see.
+
+    Every interval contributes a start and an end event (set-1 events
+    widened by *slack*).  Events are ordered by group, then position; at
+    equal positions ends come before starts, and starts of zero-length
+    intervals come last.
+
+    Returns
+    -------
+    chrs, positions, is_start, first_set, idx
+        One entry per event; *idx* is the row within its own set.
+    """
     return _dispatch_binary(
-        "subtract_numpy",
+        "dump_events_numpy",
         groups,
         starts,
         ends,
         groups2,
         starts2,
         ends2,
+        slack,
     )
 
 
-def complement_overlaps(
+def self_overlaps(
     *,
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
-    starts2: NDArray[RangeInt],
-    ends2: NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
-    groups2: NDArray[GroupIdInt] | None = None,
     slack: int = 0,
-) -> NDArray[GroupIdInt]:
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
     """
-    Return the indices of intervals in *(starts, ends)* that **do not** overlap
+    Pairs of overlapping intervals within one set, e.g. to find redundant
+    annotations.
+
+    Returns index pairs ``(i, j)`` with ``i < j``, sorted by *i* then *j*;
+    self-pairs are never produced.  The set is swept once, without matching
+    it against a copy of itself.  This is the same result as
+    :func:`overlap_matrix`.
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> self_overlaps(starts=np.array([0, 5, 12], dtype=np.int32),
+    ...               ends=np.array([10, 15, 20], dtype=np.int32))
+    (array([0, 1], dtype=uint32), array([1, 2], dtype=uint32))
+    """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
+    return _dispatch_unary(
+        "overlap_matrix_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        slack=slack,
+    )
+
+
+def pairs_within(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    max_gap: int,
+    groups: NDArray[GroupIdInt] | None = None,
+    max_pairs: int | None = None,
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32], NDArray[RangeInt]]:
+    """
+    Pairs of intervals within one set at most *max_gap* apart, with their
+    signed gap, e.g. for co-localization analysis.
+
+    Returns ``(i, j, gap)`` with ``i < j``, sorted by *i* then *j*.  The gap
+    is ``max(start_i, start_j) - min(end_i, end_j)``: 0 for touching
+    intervals and minus the overlap length for overlapping ones, so every
+    overlapping pair is included.
+
+    *max_pairs* stops the sweep once that many pairs are found, to bound
+    the output on dense data.  The truncated result holds the pairs seen
+    first in start order, not the closest ones.
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> pairs_within(starts=np.array([0, 5, 15, 24], dtype=np.int32),
+    ...              ends=np.array([10, 15, 20, 30], dtype=np.int32),
+    ...              max_gap=4)
+    (array([0, 1, 2], dtype=uint32), array([1, 2, 3], dtype=uint32), array([-5,  0,  4], dtype=int32))
+    """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
+    return _dispatch_unary(
+        "pairs_within_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        max_gap=max_gap,
+        max_pairs=max_pairs,
+    )
+
+
+def map_to_global(
+    *,
+    # ─── query (local) table ─────────────────────────────────────────
+    starts:    NDArray[RangeInt],
+    ends:      NDArray[RangeInt],
+    groups:    NDArray[GroupIdInt],
+    strand:    NDArray[np.bool_],
+    # ─── exon (annotation) table ─────────────────────────────────────
+    starts2:   NDArray[RangeInt],
+    ends2:     NDArray[RangeInt],
+    groups2:   NDArray[GroupIdInt],
+    chr_code2: NDArray[GroupIdInt],
+    genome_start2: NDArray[RangeInt],
+    genome_end2:   NDArray[RangeInt],
+    strand2:   NDArray[np.bool_],
+) -> tuple[NDArray, NDArray, NDArray, NDArray]:
+    """
+    Vectorised transcript-to-genome projection.
+
+    All arrays must be 1-D and already sorted per transcript by
+    (local_start).  The *strand* arrays encode '+' as True and '−' as False.
+
+    Returns
+    -------
+    keep_idx : uint32
+        Row numbers into the *query* table (duplicated if interval splits).
+    g_start / g_end : same dtype as *starts*
+        Genomic coordinates of the mapped pieces.
+    g_strand_bool : bool
+        True ⇒ '+', False ⇒ '−'.
+    """
+
+    return _dispatch_map_global_binary(
+        "map_to_global_numpy",
+        groups,  starts,  ends,  strand,
+        groups2, starts2, ends2, strand2,
+        chr_code2, genome_start2, genome_end2,
+    )
+
+
+def _shared_group_codes(ids: Any, ids2: Any) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
+    """Re-code two id arrays to dense ``uint32`` codes from one shared
+    vocabulary, so ids of any dtype can be compared in Rust."""
+    ids = np.asarray(ids)
+    ids2 = np.asarray(ids2)
+    _, codes = np.unique(np.concatenate([ids, ids2]), return_inverse=True)
+    codes = codes.astype(np.uint32)
+    return codes[: len(ids)], codes[len(ids):]
+
+
+def nearest(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+    k: int = 1,
+    include_overlaps: bool = True,
+    direction: Literal["forward", "backward", "any"] = "any",
+    exclude_group_ids: NDArray | None = None,
+    exclude_group_ids2: NDArray | None = None,
+    return_ties: bool = False,
+    sort_by: Literal["idx", "distance"] = "idx",
+    min_distance: int = 0,
+    distance_metric: Literal["edge", "midpoint"] = "edge",
+    validate: bool = False,
+) -> NearestResult | NearestTiesResult:
+    """
+    Find the *k* nearest intervals from *(starts2, ends2)* for every interval
+    in *(starts, ends)*, optionally restricting the search to matching
+    `groups` / `groups2`.
+
+    Parameters
+    ----------
+    starts, ends, starts2, ends2
+        Coordinate arrays (all same length and dtype ``RangeInt``).
+    groups, groups2
+        Optional per-row group IDs (e.g. chromosome numbers).  If one is
+        provided, the other **must** be provided too, and both must be the
+        same length as their corresponding coordinate arrays.
+    slack
+        Maximum distance allowed between intervals (0 ⇒ no limit).
+    k
+        Number of nearest neighbours to report per query interval.
+    include_overlaps
+        If *False*, overlapping intervals are *excluded*; otherwise they count
+        with distance 0.
+    direction
+        • ``"forward"`` – only neighbours that start **after** the query ends
+        • ``"backward"`` – only neighbours that end **before** the query starts
+        • ``"any"`` (default) – both directions.
+    exclude_group_ids, exclude_group_ids2
+        Optional per-row ids for the first / second set.  A pair is never
+        reported when both rows carry the same id, e.g. to find the nearest
+        *other* gene when both sets are the same annotation.  If only
+        *exclude_group_ids* is given it is used for both sets.
+    return_ties
+        If *True*, also report ``n_ties``: for every row, how many subjects
+        share its query's minimal distance.  Values above 1 flag queries
+        whose nearest assignment is ambiguous.
+    sort_by
+        ``"idx"`` (default) groups the rows by query, each query's neighbours
+        nearest first.  ``"distance"`` orders all rows globally nearest first
+        (ties by query, then subject), e.g. to keep the 1000 closest pairs.
+    min_distance
+        Skip neighbours fewer than this many bases away before picking the
+        *k* nearest; a positive value also drops overlaps, even with
+        *include_overlaps*.  Useful to find features outside a promoter
+        window.
+    distance_metric
+        ``"edge"`` (default) measures the gap between the closest edges.
+        ``"midpoint"`` measures between interval midpoints (rounded down),
+        which can pick a different nearest subject when sizes differ a lot;
+        *direction* then compares midpoints and *include_overlaps=False*
+        still drops overlapping subjects.
+
+    Returns
+    -------
+    NearestResult or NearestTiesResult
+        Named tuple ``(query_idx, subject_idx, distance)``, i.e.
+        ``(idx1, idx2, dist)``, or ``(idx1, idx2, dist, n_ties)`` with
+        *return_ties*.  *idx1* / *idx2* are
+        ``uint32`` indices into the first / second
+        interval sets; *dist* is the coordinate-typed gap between each pair
+        in bases: 0 means the intervals overlap or are directly adjacent
+        (bookended), a positive value is the number of bases between them.
+
+    Raises
+    ------
+    ValueError
+        If the input lengths don’t match or only one of ``groups`` /
+        ``groups2`` is supplied.
+    """
+    if exclude_group_ids is not None:
+        exclude_group_ids, exclude_group_ids2 = _shared_group_codes(
+            exclude_group_ids,
+            exclude_group_ids if exclude_group_ids2 is None else exclude_group_ids2,
+        )
+
+    idx1, idx2, dist, n_ties = _dispatch_binary(
+        "nearest_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack,
+        k=k,
+        include_overlaps=include_overlaps,
+        direction=direction,
+        exclude_group_ids=exclude_group_ids,
+        exclude_group_ids2=exclude_group_ids2,
+        sort_by=sort_by,
+        min_distance=min_distance,
+        distance_metric=distance_metric,
+        validate=validate,
+    )
+    if return_ties:
+        return NearestTiesResult(idx1, idx2, dist, n_ties)
+    return NearestResult(idx1, idx2, dist)
+
+
+class NearestIndex:
+    """
+    Database for repeated :func:`nearest` queries.
+
+    The sorted start/end events of *(starts2, ends2)* are built once in
+    ``__init__`` and reused by every :meth:`query`, so running many query
+    batches against the same annotation does not re-sort it each time.
+    """
+
+    def __init__(
+        self,
+        *,
+        starts2: NDArray[RangeInt],
+        ends2: NDArray[RangeInt],
+        groups2: NDArray[GroupIdInt] | None = None,
+    ) -> None:
+        length = check_array_lengths(starts2, ends2, groups2)
+        groups2 = validate_groups(length, groups2)
+        pos_dt = np.result_type(starts2, ends2)
+
+        try:
+            suffix, grp_t, pos_t = _SUFFIX_TABLE[(groups2.dtype, pos_dt)]
+        except KeyError as exc:
+            raise TypeError(f"Unsupported dtype pair: {groups2.dtype}, {pos_dt}") from exc
+
+        self._grp_t = grp_t
+        self._pos_t = pos_t
+        self._index = _result_class(f"NearestIndex_{suffix}")(
+            groups2.astype(grp_t, copy=False),
+            starts2.astype(pos_t, copy=False),
+            ends2.astype(pos_t, copy=False),
+        )
+
+    def query(
+        self,
+        *,
+        starts: NDArray[RangeInt],
+        ends: NDArray[RangeInt],
+        groups: NDArray[GroupIdInt] | None = None,
+        slack: int = 0,
+        k: int = 1,
+        include_overlaps: bool = True,
+        direction: Literal["forward", "backward", "any"] = "any",
+    ) -> NearestResult:
+        """Nearest database intervals for each query; see :func:`nearest`."""
+        length = check_array_lengths(starts, ends, groups)
+        groups = validate_groups(length, groups)
+
+        idx1, idx2, dist = self._index.query(
+            groups.astype(self._grp_t, copy=False),
+            starts.astype(self._pos_t, copy=False),
+            ends.astype(self._pos_t, copy=False),
+            slack=slack,
+            k=k,
+            include_overlaps=include_overlaps,
+            direction=direction,
+        )
+        return NearestResult(idx1, idx2, dist.astype(starts.dtype, copy=False))
+
+
+def subtract(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    min_fraction: float = 0.0,
+    validate: bool = False,
+) -> tuple[NDArray[GroupIdInt], NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Remove the parts of *(starts, ends)* covered by *(starts2, ends2)*.
+
+    Fragments shorter than *min_fraction* of their original interval's
+    length are dropped, e.g. ``min_fraction=0.5`` discards what is left of
+    a 100 bp interval after 60 bp of it were subtracted.
+    """
+    if min_fraction:
+        return _dispatch_binary(
+            "subtract_min_fraction_numpy",
+            groups,
+            starts,
+            ends,
+            groups2,
+            starts2,
+            ends2,
+            min_fraction=min_fraction,
+            validate=validate,
+        )
+    return _dispatch_binary(
+        "subtract_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        validate=validate,
+    )
+
+
+def clip_to(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    validate: bool = False,
+) -> tuple[NDArray[np.uint32], NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Keep the parts of *(starts, ends)* covered by *(starts2, ends2)*.
+
+    The inverse of :func:`subtract`: overlapping or touching set-2 intervals
+    are merged, clipped to each set-1 interval and returned as
+    ``(idx, starts, ends)``, where *idx* is the row of the set-1 interval
+    each piece belongs to. Pieces are sorted by *idx*, then start.
+    """
+    return _dispatch_binary(
+        "clip_to_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        validate=validate,
+    )
+
+
+def complement_overlaps(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> NDArray[GroupIdInt]:
+    """
+    Return the indices of intervals in *(starts, ends)* that **do not** overlap
     *any* interval in *(starts2, ends2)*, subject to an optional `slack`.
 
     Parameters
@@ -431,7 +1240,8 @@ def complement_overlaps(
     )
 
 
-def count_overlaps(
+def no_overlap_mask(
+    *,
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
     starts2: NDArray[RangeInt],
@@ -439,31 +1249,29 @@ def count_overlaps(
     groups: NDArray[GroupIdInt] | None = None,
     groups2: NDArray[GroupIdInt] | None = None,
     slack: int = 0,
-) -> NDArray[GroupIdInt]:
+) -> NDArray[np.bool_]:
     """
-    For every interval in *(starts, ends)*, count how many intervals in
-    *(starts2, ends2)* overlap it (distance ≤ ``slack``).
-
-    Parameters
-    ----------
-    starts, ends, starts2, ends2
-        Coordinate arrays (all same dtype ``RangeInt``).
-    groups, groups2
-        Optional group IDs (chromosomes, contigs, …).  If one is given, the
-        other **must** be given too.  Counts are computed *within* matching
-        groups only.
-    slack
-        Two intervals are considered overlapping if their gap is ≤ `slack`
-        (0 ⇒ they must actually touch/intersect).
+    Boolean-mask variant of :func:`complement_overlaps`.
 
     Returns
     -------
-    counts : NDArray[GroupIdInt]
-        ``uint32`` array, length == ``len(starts)``, holding the per-row
-        overlap counts.
+    mask : NDArray[np.bool_]
+        Array of length ``len(starts)`` that is *True* for every row in the
+        first interval set with **no** overlap in the second set.
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> starts  = np.array([ 1, 10, 30], dtype=np.int32)
+    >>> ends    = np.array([ 5, 15, 35], dtype=np.int32)
+    >>> starts2 = np.array([ 3, 20],     dtype=np.int32)
+    >>> ends2   = np.array([ 6, 25],     dtype=np.int32)
+    >>> no_overlap_mask(starts=starts, ends=ends,
+    ...                 starts2=starts2, ends2=ends2)
+    array([False,  True,  True])
     """
     return _dispatch_binary(
-        "count_overlaps_numpy",
+        "no_overlap_mask_numpy",
         groups,
         starts,
         ends,
@@ -474,84 +1282,700 @@ def count_overlaps(
     )
 
 
-def sort_intervals(
+def set2_uncovered(
+    *,
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
-    sort_reverse_direction: NDArray[np.bool_] | None = None,
-) -> NDArray[GroupIdInt]:
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> tuple[NDArray[np.uint32], NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    The intervals of *(starts2, ends2)* that no interval of *(starts, ends)*
+    overlaps: :func:`complement_overlaps` seen from the second set.
+
+    Returns
+    -------
+    idx2, starts2, ends2
+        Indices into the second set, sorted, with their coordinates.
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> starts  = np.array([ 1, 10, 30], dtype=np.int32)
+    >>> ends    = np.array([ 5, 15, 35], dtype=np.int32)
+    >>> starts2 = np.array([ 3, 20],     dtype=np.int32)
+    >>> ends2   = np.array([ 6, 25],     dtype=np.int32)
+    >>> set2_uncovered(starts=starts, ends=ends,
+    ...                starts2=starts2, ends2=ends2)
+    (array([1], dtype=uint32), array([20], dtype=int32), array([25], dtype=int32))
+    """
+    return _dispatch_binary(
+        "complement_set2_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack,
+    )
+
+
+def anchor_profile(
+    *,
+    anchors: NDArray[RangeInt],
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    half_width: int,
+    bin_size: int,
+    anchor_groups: NDArray[GroupIdInt] | None = None,
+    groups: NDArray[GroupIdInt] | None = None,
+    anchor_strands: NDArray[np.bool_] | None = None,
+) -> NDArray[np.uint32]:
+    """
+    Feature counts in fixed bins around anchor points, e.g. for a metaplot
+    around TSSs.
+
+    The window ``[anchor - half_width, anchor + half_width)`` of every anchor
+    is cut into bins of *bin_size* and the features *(starts, ends)*
+    overlapping each bin are counted, in a single call without building
+    the windows in Python.
+
+    Parameters
+    ----------
+    anchor_strands
+        Optional ``True`` for plus-strand anchors.  Bins run 5′→3′, so on
+        minus-strand anchors the first column is the rightmost bin.
+
+    Returns
+    -------
+    NDArray[np.uint32]
+        ``(len(anchors), ceil(2 * half_width / bin_size))`` count matrix.
+    """
+    anchors = np.asarray(anchors)
+    if anchor_strands is not None:
+        anchor_strands = np.asarray(anchor_strands, dtype=bool)
+    # The windows are passed instead of the points so the position dtype
+    # chosen for the kernel holds their edges.
+    return _dispatch_binary(
+        "anchor_profile_numpy",
+        anchor_groups,
+        anchors - half_width,
+        anchors + half_width,
+        groups,
+        starts,
+        ends,
+        half_width,
+        bin_size,
+        strands=anchor_strands,
+    )
+
+
+def count_overlaps(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int | NDArray[RangeInt] = 0,
+    validate: bool = False,
+    strand_mode: Literal["any", "same", "opposite"] = "any",
+    strands: NDArray[np.bool_] | None = None,
+    strands2: NDArray[np.bool_] | None = None,
+) -> NDArray[GroupIdInt]:
+    """
+    For every interval in *(starts, ends)*, count how many intervals in
+    *(starts2, ends2)* overlap it (distance ≤ ``slack``).
+
+    Parameters
+    ----------
+    starts, ends, starts2, ends2
+        Coordinate arrays (all same dtype ``RangeInt``).
+    groups, groups2
+        Optional group IDs (chromosomes, contigs, …).  If one is given, the
+        other **must** be given too.  Counts are computed *within* matching
+        groups only.
+    slack
+        Two intervals are considered overlapping if their gap is ≤ `slack`
+        (0 ⇒ they must actually touch/intersect).  An array gives one
+        tolerance per row of *(starts, ends)*.
+    strand_mode, strands, strands2
+        Count only subjects on the same (``"same"``) or the opposite
+        (``"opposite"``) strand, judged by the boolean *strands* and
+        *strands2* arrays; see :func:`overlaps`.
+
+    Returns
+    -------
+    counts : NDArray[GroupIdInt]
+        ``uint32`` array, length == ``len(starts)``, holding the per-row
+        overlap counts.
+    """
+    slack, slack_kw = _split_slack(slack, len(starts))
+    return _dispatch_binary(
+        "count_overlaps_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack,
+        **slack_kw,
+        validate=validate,
+        strand_mode=strand_mode,
+        strands=None if strands is None else np.asarray(strands, dtype=np.bool_),
+        strands2=None if strands2 is None else np.asarray(strands2, dtype=np.bool_),
+    )
+
+
+def any_overlap(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> NDArray[np.bool_]:
+    """
+    ``True`` for every interval in *(starts, ends)* overlapped by at least
+    one interval in *(starts2, ends2)*.
+
+    Equivalent to ``count_overlaps(...) > 0`` but stops looking at an
+    interval once its first match is found, which is much cheaper on dense
+    data.  Same parameters as :func:`count_overlaps`.
+    """
+    return _dispatch_binary(
+        "any_overlap_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack=slack,
+    )
+
+
+def evaluate_overlap(
+    *,
+    pred_starts: NDArray[RangeInt],
+    pred_ends: NDArray[RangeInt],
+    true_starts: NDArray[RangeInt],
+    true_ends: NDArray[RangeInt],
+    pred_groups: NDArray[GroupIdInt] | None = None,
+    true_groups: NDArray[GroupIdInt] | None = None,
+    min_overlap_fraction: float = 0.0,
+) -> tuple[float, float, float]:
+    """
+    Score predicted intervals against a ground truth.
+
+    A predicted and a true interval match when they overlap and the
+    prediction covers at least *min_overlap_fraction* of the true
+    interval's length.
+
+    Returns
+    -------
+    precision, recall, f1
+        *precision* is the fraction of predicted intervals with a match,
+        *recall* the fraction of true intervals with one.  Each is 0 for an
+        empty set.
+    """
+    return _dispatch_binary(
+        "evaluate_overlap_numpy",
+        pred_groups,
+        pred_starts,
+        pred_ends,
+        true_groups,
+        true_starts,
+        true_ends,
+        min_overlap_fraction=min_overlap_fraction,
+    )
+
+
+def jaccard(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    *,
+    overlap_stats: bool = False,
+) -> float | tuple[float, int, float, float]:
+    """
+    Jaccard index of two interval sets: bases covered by both over bases
+    covered by either, each set taken as its union.  0 when both are empty.
+
+    With *overlap_stats* the overlapping pairs are summarised as well, which
+    saves an :func:`overlaps` call just to characterise the intersection.
+
+    Returns
+    -------
+    float or (jaccard, n_pairs, mean_overlap, median_overlap)
+        *n_pairs* counts pairs sharing at least one base; the mean and
+        median of their overlap lengths are NaN when there are none.
+    """
+    index, stats = _dispatch_binary(
+        "jaccard_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        overlap_stats=overlap_stats,
+    )
+    if not overlap_stats:
+        return index
+    return (index, *stats)
+
+
+def count_overlaps_both(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
+    """
+    Overlap counts in both directions from a single sweep.
+
+    Same parameters as :func:`count_overlaps`.
+
+    Returns
+    -------
+    counts, counts2
+        *counts* is what :func:`count_overlaps` returns; *counts2* has one
+        entry per row of *(starts2, ends2)* holding how many intervals of the
+        first set overlap it.
+    """
+    return _dispatch_binary(
+        "count_overlaps_both_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack,
+    )
+
+
+def coverage_normalize(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    interval_starts: NDArray[RangeInt],
+    interval_ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    interval_groups: NDArray[GroupIdInt] | None = None,
+    method: Literal["rpm", "rpkm"] = "rpm",
+) -> NDArray[np.float64]:
+    """
+    Normalised read coverage of every region, e.g. for ChIP-seq or ATAC-seq.
+
+    *(starts, ends)* are the reads and *(interval_starts, interval_ends)*
+    the regions they are counted in (as in :func:`count_overlaps`).
+
+    Parameters
+    ----------
+    method
+        ``"rpm"`` – reads per million base pairs: the count divided by the
+        summed length of all regions in megabases.
+        ``"rpkm"`` – reads per kilobase per million reads: the count divided
+        by the region length in kilobases and the number of reads in
+        millions.
+
+    Returns
+    -------
+    NDArray[np.float64]
+        One value per region.  Zero-length regions get 0.
+    """
+    if method not in ("rpm", "rpkm"):
+        raise ValueError('method must be "rpm" or "rpkm"')
+    return _dispatch_binary(
+        f"coverage_normalize_{method}_numpy",
+        groups,
+        starts,
+        ends,
+        interval_groups,
+        interval_starts,
+        interval_ends,
+    )
+
+
+def coverage_rle(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+) -> tuple[NDArray[GroupIdInt], NDArray[RangeInt], NDArray[RangeInt], NDArray[np.uint32]]:
+    """
+    Per-base coverage depth, run-length encoded.
+
+    Returns ``(groups, starts, ends, depth)`` runs sorted by group and
+    position.  The depth is constant within a run and differs between
+    adjacent runs; uncovered stretches are omitted.  Far smaller than a dense
+    per-base array for sparse data.
+    """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
+    return _dispatch_unary(
+        "coverage_rle_numpy",
+        starts=starts,
+        ends=ends,
+        groups=groups,
+    )
+
+
+def overlap_matrix_dense(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    starts2: NDArray[RangeInt],
+    ends2: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    groups2: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> NDArray[np.uint32]:
+    """
+    Dense ``uint32`` matrix of shape ``(len(starts), len(starts2))`` whose
+    entry ``[i, j]`` is 1 if interval *i* overlaps interval *j* of the
+    second set, 0 otherwise.
+
+    Meant for small sets (up to ~10 000 intervals each); a
+    ``RuntimeWarning`` is emitted when the matrix would hold more than
+    1e8 entries (400 MB).
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> overlap_matrix_dense(np.array([0, 20]), np.array([10, 30]),
+    ...                      np.array([5, 10]), np.array([25, 20]))
+    array([[1, 0],
+           [1, 0]], dtype=uint32)
+    """
+    if len(starts) * len(starts2) > 1e8:
+        warnings.warn(
+            f"overlap_matrix_dense: a {len(starts)} x {len(starts2)} matrix "
+            "takes more than 400 MB",
+            RuntimeWarning,
+            stacklevel=2,
+        )
+    return _dispatch_binary(
+        "overlap_matrix_dense_numpy",
+        groups,
+        starts,
+        ends,
+        groups2,
+        starts2,
+        ends2,
+        slack,
+    )
+
+
+def sort_intervals(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    sort_reverse_direction: NDArray[np.bool_] | None = None,
+    descending: bool = False,
+    secondary: Literal["end", "length"] = "end",
+) -> NDArray[GroupIdInt]:
+    """
+    Return the permutation that sorts *(starts, ends)* (and their optional
+    ``groups``) in ascending genomic order.
+
+    Parameters
+    ----------
+    starts, ends
+        Coordinate arrays (dtype ``RangeInt``) of equal length.
+    groups
+        Optional per-row group IDs (chromosomes, contigs, …).  If supplied, the
+        sort is performed *within* each group; otherwise intervals are sorted
+        globally.
+    sort_reverse_direction
+        Optional boolean array (same length as *starts*) marking rows that
+        should be ordered **descendingly** within their group/position tier.
+        A value of *None* (default) means no per-row reversal.
+    descending
+        Order intervals within each group by descending coordinates.  Rows
+        flagged in *sort_reverse_direction* are flipped relative to this.
+    secondary
+        Tie-break after the start coordinate: ``"end"`` (default) or
+        ``"length"``.
+
+    Returns
+    -------
+    perm : NDArray[GroupIdInt]
+        ``uint32`` array whose elements are the indices that sort the input.
+
+    Notes
+    -----
+    *The heavy lifting happens in Rust; this wrapper only dispatches to the
+    correct concrete wrapper based on dtypes.*
+    """
+    return _dispatch_unary(
+        "sort_intervals_numpy",  # selects the Rust wrapper
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        sort_reverse_direction=sort_reverse_direction,
+        descending=descending,
+        secondary=secondary,
+    )
+
+def sort_intervals_apply(
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    sort_reverse_direction: NDArray[np.bool_] | None = None,
+    descending: bool = False,
+    secondary: Literal["end", "length"] = "end",
+    return_permutation: bool = False,
+) -> tuple[NDArray, ...]:
+    """
+    Sort intervals like :func:`sort_intervals` and return the sorted arrays.
+
+    The gather happens in Rust, which saves a fancy-indexing pass in Python
+    for large inputs.
+
+    Returns
+    -------
+    groups, starts, ends[, perm]
+        The permuted input arrays; *perm* is appended when
+        *return_permutation* is *True*.
+    """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
+    out_groups, out_starts, out_ends, perm = _dispatch_unary(
+        "sort_intervals_apply_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        sort_reverse_direction=sort_reverse_direction,
+        descending=descending,
+        secondary=secondary,
+    )
+    if return_permutation:
+        return out_groups, out_starts, out_ends, perm
+    return out_groups, out_starts, out_ends
+
+def sort_groups(
+    groups: NDArray[GroupIdInt],
+) -> NDArray[GroupIdInt]:
+    """
+    Return the permutation that sorts groups in order.
+
+    Parameters
+    ----------
+    groups
+        Optional per-row group IDs (chromosomes, contigs, …).  If supplied, the
+        sort is performed *within* each group; otherwise intervals are sorted
+        globally.
+
+    Returns
+    -------
+    perm : NDArray[GroupIdInt]
+        ``uint32`` array whose elements are the indices that sort the input.
+
+    Notes
+    -----
+    *The heavy lifting happens in Rust; this wrapper only dispatches to the
+    correct concrete wrapper based on dtypes.*
+    """
+    return _dispatch_unary(
+        "sort_groups_numpy",  # selects the Rust wrapper
+        groups=groups,
+    )
+
+
+def save_sorted_index(
+    path: str,
+    *,
+    starts: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+) -> None:
+    """
+    Sort intervals by group and start and write the sorted order to *path*.
+
+    The file can be read back with :func:`load_sorted_index` in another
+    process, so a database queried from many processes is sorted only once.
+    """
+    starts = np.asarray(starts)
+    groups = validate_groups(len(starts), groups)
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    rust_mod.save_sorted_index_numpy(
+        str(path), _cast(groups, np.dtype(np.uint32)), _cast(starts, np.dtype(np.int64))
+    )
+
+
+def load_sorted_index(
+    path: str,
+) -> tuple[NDArray[np.uint32], NDArray[np.int64], NDArray[np.uint32]]:
+    """
+    Read a file written by :func:`save_sorted_index`.
+
+    Returns
+    -------
+    groups, starts, indices
+        The sorted events: group and start of every interval, and its row in
+        the original input.
+    """
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    return rust_mod.load_sorted_index_numpy(str(path))
+
+
+def chrom_boundaries(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt],
+) -> tuple[NDArray[np.uint32], NDArray[np.uint32]]:
+    """
+    Sort order by group and start, plus the offset where each group's block
+    begins in that order, followed by the number of rows.
+
+    ``order[offsets[k]:offsets[k + 1]]`` are the rows of the *k*-th group,
+    so the arrays can be sliced per chromosome and the blocks dispatched to
+    separate workers.
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> chrom_boundaries(starts=np.array([5, 30, 1, 7, 10]),
+    ...                  ends=np.array([6, 31, 2, 8, 11]),
+    ...                  groups=np.array([2, 0, 2, 1, 0], dtype=np.uint32))
+    (array([4, 1, 3, 2, 0], dtype=uint32), array([0, 2, 3, 5], dtype=uint32))
+    """
+    return _dispatch_unary(
+        "chrom_boundaries_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+    )
+
+
+def cluster(
+    starts: NDArray[RangeInt],
+    ends:   NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    slack:  int = 0,
+    merge_touching: bool = False,
+    validate: bool = False,
+) -> tuple[NDArray[GroupIdInt], NDArray[GroupIdInt]]:
     """
-    Return the permutation that sorts *(starts, ends)* (and their optional
-    ``groups``) in ascending genomic order.
+    Group nearby/overlapping intervals into clusters.
 
     Parameters
     ----------
     starts, ends
-        Coordinate arrays (dtype ``RangeInt``) of equal length.
+        Coordinate arrays (same dtype ``RangeInt``, or float with *groups*).
     groups
-        Optional per-row group IDs (chromosomes, contigs, …).  If supplied, the
-        sort is performed *within* each group; otherwise intervals are sorted
-        globally.
-    sort_reverse_direction
-        Optional boolean array (same length as *starts*) marking rows that
-        should be ordered **descendingly** within their group/position tier.
-        A value of *None* (default) means no per-row reversal.
+        Optional group IDs (chromosome, contig …); clustering is performed
+        *within* each group.  If omitted, all intervals are considered to be
+        in the same group.
+    slack
+        Two intervals belong to the same cluster if their gap is ≤ `slack`
+        (0 ⇒ they must overlap), the same rule as :func:`merge`.
+    merge_touching
+        At ``slack=0``, also cluster intervals that only touch, as in
+        :func:`merge`.
 
     Returns
     -------
-    perm : NDArray[GroupIdInt]
-        ``uint32`` array whose elements are the indices that sort the input.
-
-    Notes
-    -----
-    *The heavy lifting happens in Rust; this wrapper only dispatches to the
-    correct concrete wrapper based on dtypes.*
+    cluster_ids , order_idx : tuple of ``uint32`` arrays
+        *cluster_ids* gives the cluster label per input row; *order_idx* is
+        the permutation that sorts the rows by cluster then position.
     """
     return _dispatch_unary(
-        "sort_intervals_numpy",  # selects the Rust wrapper
+        "cluster_numpy",      # dispatch key – matches the Rust wrapper base
         groups=groups,
         starts=starts,
         ends=ends,
-        sort_reverse_direction=sort_reverse_direction,
+        slack=slack,
+        merge_touching=merge_touching,
+        validate=validate,
     )
 
-def sort_groups(
-    groups: NDArray[GroupIdInt],
-) -> NDArray[GroupIdInt]:
+def cluster_stats(
+    starts: NDArray[RangeInt],
+    ends:   NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    slack:  int = 0,
+    merge_touching: bool = False,
+) -> tuple[
+    NDArray[np.uint32],
+    NDArray[np.uint32],
+    NDArray[np.float64],
+    NDArray[np.float64],
+    NDArray[RangeInt],
+    NDArray[RangeInt],
+]:
     """
-    Return the permutation that sorts groups in order.
-
-    Parameters
-    ----------
-    groups
-        Optional per-row group IDs (chromosomes, contigs, …).  If supplied, the
-        sort is performed *within* each group; otherwise intervals are sorted
-        globally.
+    Summary statistics per :func:`cluster` cluster.
 
     Returns
     -------
-    perm : NDArray[GroupIdInt]
-        ``uint32`` array whose elements are the indices that sort the input.
-
-    Notes
-    -----
-    *The heavy lifting happens in Rust; this wrapper only dispatches to the
-    correct concrete wrapper based on dtypes.*
+    cluster_ids, sizes, mean_starts, mean_ends, min_starts, max_ends
+        One row per cluster, with the same ids as :func:`cluster`: the
+        number of member intervals, the mean of their starts and ends, and
+        the span of the cluster.
     """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
     return _dispatch_unary(
-        "sort_groups_numpy",  # selects the Rust wrapper
+        "cluster_stats_numpy",
         groups=groups,
+        starts=starts,
+        ends=ends,
+        slack=slack,
+        merge_touching=merge_touching,
     )
 
+def cluster_transitions(
+    starts: NDArray[RangeInt],
+    ends:   NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    slack:  int = 0,
+    merge_touching: bool = False,
+) -> tuple[NDArray[RangeInt], NDArray[np.uint32]]:
+    """
+    Positions where the :func:`cluster` assignment changes.
+
+    Returns
+    -------
+    positions, new_cluster_ids
+        For every cluster after the first, the start of its leftmost
+        interval and its cluster id.  Ids change at group boundaries too,
+        so the first cluster of each later group is included.
+    """
+    return _dispatch_unary(
+        "cluster_transitions_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        slack=slack,
+        merge_touching=merge_touching,
+    )
 
-def cluster(
+def cluster_min_coverage(
     starts: NDArray[RangeInt],
     ends:   NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
     slack:  int = 0,
+    merge_touching: bool = False,
+    min_neighbours: int = 1,
 ) -> tuple[NDArray[GroupIdInt], NDArray[GroupIdInt]]:
     """
-    Group nearby/overlapping intervals into clusters.
+    Cluster intervals, keeping only members that overlap at least
+    `min_neighbours` other intervals.
 
     Parameters
     ----------
@@ -559,24 +1983,30 @@ def cluster(
         Coordinate arrays (same dtype ``RangeInt``).
     groups
         Optional group IDs (chromosome, contig …); clustering is performed
-        *within* each group.  If omitted, all intervals are considered to be
-        in the same group.
+        *within* each group.
     slack
         Two intervals belong to the same cluster if their gap is ≤ `slack`
-        (0 ⇒ they must touch/overlap).
+        (0 ⇒ they must overlap), the same rule as :func:`merge`.
+    merge_touching
+        At ``slack=0``, also cluster intervals that only touch, as in
+        :func:`merge`.
+    min_neighbours
+        Minimum number of *other* intervals a member must overlap.
 
     Returns
     -------
     cluster_ids , order_idx : tuple of ``uint32`` arrays
-        *cluster_ids* gives the cluster label per input row; *order_idx* is
-        the permutation that sorts the rows by cluster then position.
+        Same layout as :func:`cluster`, restricted to the qualifying rows.
+        Cluster IDs are renumbered to be consecutive after filtering.
     """
     return _dispatch_unary(
-        "cluster_numpy",      # dispatch key – matches the Rust wrapper base
+        "cluster_min_coverage_numpy",
         groups=groups,
         starts=starts,
         ends=ends,
         slack=slack,
+        merge_touching=merge_touching,
+        min_neighbours=min_neighbours,
     )
 
 def merge(
@@ -585,6 +2015,12 @@ def merge(
     ends:   NDArray[RangeInt],
     groups: NDArray[GroupIdInt] | None = None,
     slack:  int = 0,
+    presorted: bool = False,
+    merge_touching: bool = False,
+    anchor: Literal["first", "last", "largest", "score"] = "first",
+    scores: NDArray[np.float64] | None = None,
+    values: NDArray[np.float64] | None = None,
+    validate: bool = False,
 ) -> tuple[
     NDArray[GroupIdInt],  # indices
     NDArray[RangeInt],    # merged starts
@@ -597,29 +2033,132 @@ def merge(
     Parameters
     ----------
     starts, ends
-        Coordinate arrays (dtype ``RangeInt``).
+        Coordinate arrays (dtype ``RangeInt``, or float with *groups*).
     groups
         Optional group IDs (chromosome, contig …).  Merging is performed
         independently within each group.  Omit to merge globally.
     slack
         Two intervals are merged if their gap is ≤ `slack`
-        (0 ⇒ they must touch/intersect).
+        (0 ⇒ they must intersect, see *merge_touching*).  Slack only decides *which*
+        intervals are merged; merged regions always span the original outer
+        coordinates.
+    presorted
+        Set to *True* when the rows are already sorted by group and start
+        (e.g. a sorted BED file) to skip the internal sort.  Results are
+        undefined if the input is not actually sorted.
+    merge_touching
+        Coordinates are half-open, so ``[0, 10)`` and ``[10, 20)`` touch
+        without sharing a base.  By default only intervals sharing at least
+        one base are merged at ``slack=0``; with *True* touching intervals
+        are merged as well.  A positive *slack* always merges a gap of
+        exactly *slack*.
+    anchor
+        Which member's row index represents each merged region:
+        ``"first"`` (smallest start), ``"last"`` (largest end; what earlier
+        versions always returned), ``"largest"`` (widest interval) or
+        ``"score"`` (highest value in *scores*).
+    scores
+        Per-row scores, required for ``anchor="score"``.
+    values
+        Per-row values (e.g. a signal column) to aggregate over each merged
+        region.  When given, three more ``float64`` arrays are returned.
 
     Returns
     -------
-    indices, merged_starts, merged_ends, counts
-        *indices* is the ``uint32`` row index of the first interval that
-        contributed to each merged output.  *counts* reports how many original
-        intervals were collapsed into each merge.
+    indices, merged_starts, merged_ends, counts[, sums, maxs, means]
+        *indices* is the ``uint32`` row index of the representative interval
+        chosen by *anchor* for each merged output.  *counts* reports how many
+        original intervals were collapsed into each merge.  With *values*,
+        their sum, maximum and mean over the members of each region follow.
     """
+    if values is not None:
+        if groups is None:
+            groups = np.zeros(len(starts), dtype=np.uint32)
+        return _dispatch_unary(
+            "merge_values_numpy",
+            groups=groups,
+            starts=starts,
+            ends=ends,
+            values=np.asarray(values, dtype=np.float64),
+            slack=slack,
+            merge_touching=merge_touching,
+            anchor=anchor,
+            scores=None if scores is None else np.asarray(scores, dtype=np.float64),
+            validate=validate,
+        )
     return _dispatch_unary(
         "merge_numpy",        # base name of the Rust wrapper
         groups=groups,
         starts=starts,
         ends=ends,
         slack=slack,
+        presorted=presorted,
+        merge_touching=merge_touching,
+        anchor=anchor,
+        scores=None if scores is None else np.asarray(scores, dtype=np.float64),
+        validate=validate,
+    )
+
+
+def merge_ids(
+    *,
+    starts: NDArray[RangeInt],
+    ends:   NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    slack:  int = 0,
+    merge_touching: bool = False,
+) -> NDArray[np.uint32]:
+    """
+    For every input row, the id of the merged region it belongs to.
+
+    The id is the row's region's position in the output of :func:`merge`
+    called with the same *groups*, *slack* and *merge_touching*, so rows can
+    be grouped by merged region, e.g. with ``np.bincount`` or a group-by.
+    """
+    return _dispatch_unary(
+        "merge_ids_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        slack=slack,
+        merge_touching=merge_touching,
+    )
+
+
+def span(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt],
+) -> tuple[
+    NDArray[GroupIdInt],  # group ids
+    NDArray[RangeInt],    # smallest start
+    NDArray[RangeInt],    # largest end
+    NDArray[np.uint32],   # counts
+]:
+    """
+    Overall extent of every group: the smallest start and largest end of its
+    intervals, e.g. gene boundaries from a table of exons.
+
+    Unlike :func:`merge`, intervals need not overlap to count towards the
+    span.  Returns one row per distinct group id, in ascending order, with
+    the number of intervals in the group.
+
+    Examples
+    --------
+    >>> import numpy as np
+    >>> span(starts=np.array([50, 0, 10]), ends=np.array([60, 10, 20]),
+    ...      groups=np.array([3, 1, 3], dtype=np.uint32))
+    (array([1, 3], dtype=uint32), array([ 0, 10]), array([10, 60]), array([1, 2], dtype=uint32))
+    """
+    return _dispatch_unary(
+        "span_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
     )
 
+
 def max_disjoint(
     *,
     starts: NDArray[RangeInt],
@@ -670,6 +2209,7 @@ def complement(
     chrom_lens: NDArray[RangeInt],
     slack: int = 0,
     include_first_interval: bool = False,
+    min_gap: int = 0,
 ) -> tuple[
     NDArray[GroupIdInt],  # out_chrs
     NDArray[RangeInt],    # out_starts
@@ -694,6 +2234,9 @@ def complement(
     include_first_interval
         If *True*, emit a gap *before* the first input interval on each
         chromosome (from 0 to `start[0] − 1`).
+    min_gap
+        Only report gaps at least this many bases long.  Unlike *slack* this
+        does not move the intervals; it only filters the output.
 
     Returns
     -------
@@ -715,6 +2258,42 @@ def complement(
         chrom_len_ids=chrom_len_ids,
         chrom_lens=chrom_lens,
         include_first_interval=include_first_interval,
+        min_gap=min_gap,
+    )
+
+def gaps(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    slack: int = 0,
+) -> tuple[
+    NDArray[np.uint32],  # interval following each gap
+    NDArray[RangeInt],   # gap starts
+    NDArray[RangeInt],   # gap ends
+]:
+    """
+    Gaps between consecutive intervals of each group, e.g. introns from exons.
+
+    Unlike :func:`complement` no chromosome lengths are needed: nothing is
+    reported before the first or after the last interval of a group.
+    Overlapping and bookended intervals leave no gap, and gaps of at most
+    *slack* bases are skipped.  Reported gaps keep their exact coordinates.
+
+    Returns
+    -------
+    idx, gap_starts, gap_ends
+        *idx* is the input interval immediately **following** each gap, as
+        in :func:`complement`.
+    """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
+    return _dispatch_unary(
+        "gaps_numpy",
+        starts,
+        ends,
+        groups,
+        slack=slack,
     )
 
 def boundary(
@@ -765,84 +2344,244 @@ def window(
     negative_strand: NDArray[np.bool_],
     window_size: int,
     groups: NDArray[GroupIdInt] | None = None,
+    drop_partial: bool = False,
 ) -> tuple[
     NDArray[GroupIdInt],  # indices
     NDArray[RangeInt],    # windowed starts
     NDArray[RangeInt],    # windowed ends
 ]:
     """
-    Expand each interval upstream/downstream by `window_size` bases.
-
-    Expansion direction:
-
-    * **positive strand** (`negative_strand == False`)
-      → start − *window_size*, end + *window_size*
-    * **negative strand** (`negative_strand == True`)
-      → start + *window_size*, end − *window_size*
+    Expand each interval upstream/downstream by `window_size` bases.
+
+    Expansion direction:
+
+    * **positive strand** (`negative_strand == False`)
+      → start − *window_size*, end + *window_size*
+    * **negative strand** (`negative_strand == True`)
+      → start + *window_size*, end − *window_size*
+
+    Returns the permutation (`indices`) that sorts the window-shifted
+    intervals, plus the shifted coordinates.
+
+    With *drop_partial* the last, shorter-than-*window_size* window of each
+    group is left out, so only full windows are returned.
+
+    Notes
+    -----
+    All heavy lifting happens inside Rust; this wrapper only dispatches to the
+    correct concrete wrapper based on NumPy dtypes.
+    """
+    # `_dispatch_unary` accepts `groups=None` because this operation has no
+    # grouping column.
+    return _dispatch_unary(
+        "window_numpy",          # base name of the Rust wrapper
+        groups = _groups_or_arange(groups, len(starts)),
+        starts=starts,
+        ends=ends,
+        negative_strand=negative_strand,
+        window_size=window_size,
+        drop_partial=drop_partial,
+    )
+
+def tile(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    negative_strand: NDArray[np.bool_],
+    tile_size: int,
+    interval_fraction: bool = False,
+    validate: bool = False,
+) -> tuple[NDArray, ...]:
+    """
+    Split each interval into fixed-size tiles.
+
+    * For positive-strand rows (`negative_strand == False`) tiling proceeds
+      from 5'→3'.
+    * For negative-strand rows (`negative_strand == True`) tiling proceeds
+      from 3'→5'.
+
+    Parameters
+    ----------
+    starts, ends
+        Coordinate arrays (dtype ``RangeInt``).
+    negative_strand
+        Boolean array indicating strand per interval.
+    tile_size
+        Desired tile length in the same units as *starts/ends*.
+    interval_fraction
+        Also return *interval_fraction* as a fifth array.
+
+    Returns
+    -------
+    indices, tile_starts, tile_ends, overlap_fraction[, interval_fraction]
+        *indices* (`uint32`) is the permutation that sorts the tiles in
+        genomic order; *overlap_fraction* reports, for each tile, the fraction
+        of its bases that overlap the original interval (useful when the last
+        tile is truncated).  *interval_fraction* is the same overlap divided
+        by the length of the original interval instead, so it sums to 1 per
+        input row.
+    """
+    out = _dispatch_unary(
+        "tile_numpy",        # base name of the Rust wrapper
+        groups = None,
+        starts=starts,
+        ends=ends,
+        negative_strand=negative_strand,
+        tile_size=tile_size,
+        validate=validate,
+    )
+    return out if interval_fraction else out[:4]
+
+def tile_grouped(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    negative_strand: NDArray[np.bool_],
+    tile_size: int,
+    groups: NDArray[GroupIdInt],
+    chrom_ids: NDArray[GroupIdInt] | None = None,
+    chrom_length: NDArray[RangeInt] | None = None,
+    interval_fraction: bool = False,
+    tile_ids: bool = False,
+) -> tuple[NDArray, ...]:
+    """
+    :func:`tile` per group.  Rows should be sorted (or at least grouped) by
+    *groups*.
+
+    With *interval_fraction*, the interval fractions of :func:`tile` are
+    appended.  With *tile_ids*, a genome-wide tile id is appended last: the
+    id is ``tile_start // tile_size``; together with the group it identifies
+    the same genomic tile across feature sets, so tiles can be joined or
+    aggregated on it directly.
+
+    When *chrom_ids* and *chrom_length* are given, tiles are clamped to
+    ``[0, chrom_length]`` of their group and dropped when entirely outside;
+    overlap fractions still use the full *tile_size*.
+    """
+    if (chrom_ids is None) != (chrom_length is None):
+        raise ValueError("chrom_ids and chrom_length must be given together")
+
+    # Resolve on both coordinate arrays, so wider ends are not narrowed.
+    pos_dt = np.result_type(starts, ends)
+    starts, ends = _cast(starts, pos_dt), _cast(ends, pos_dt)
+
+    kw = {}
+    if chrom_ids is not None:
+        _, grp_t, pos_t = _resolve_rust_fn("tile_grouped_numpy", groups.dtype, pos_dt)
+        kw["chrom_ids"] = _cast(np.asarray(chrom_ids), grp_t)
+        kw["chrom_lengths"] = _cast(np.asarray(chrom_length), pos_t)
+
+    out = _dispatch_unary(
+        "tile_grouped_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        negative_strand=negative_strand,
+        tile_size=tile_size,
+        **kw,
+    )
+    extra = ([out[4]] if interval_fraction else []) + ([out[5]] if tile_ids else [])
+    return (*out[:4], *extra)
+
+def tile_aggregate_count(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt],
+    tile_size: int,
+) -> tuple[
+    NDArray[GroupIdInt],  # tile groups
+    NDArray[RangeInt],    # tile starts
+    NDArray[RangeInt],    # tile ends
+    NDArray[np.uint32],   # counts
+]:
+    """
+    Count how many intervals overlap each fixed-size tile.
+
+    One row is returned per covered tile (not per tile/interval pair),
+    sorted by group and tile start.  Tiles no interval touches are omitted.
+
+    Returns
+    -------
+    tile_groups, tile_starts, tile_ends, counts
+    """
+    return _dispatch_unary(
+        "tile_aggregate_count_numpy",
+        groups=groups,
+        starts=starts,
+        ends=ends,
+        tile_size=tile_size,
+    )
+
+def tile_scores(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt],
+    scores: NDArray[np.float64],
+    tile_size: int,
+    aggregation: Literal["sum", "mean", "max", "min"] = "sum",
+) -> tuple[
+    NDArray[GroupIdInt],  # tile groups
+    NDArray[RangeInt],    # tile starts
+    NDArray[RangeInt],    # tile ends
+    NDArray[np.float64],  # aggregated scores
+]:
+    """
+    Aggregate per-interval scores over fixed-size tiles, e.g. to bin a
+    ChIP-seq signal.
 
-    Returns the permutation (`indices`) that sorts the window-shifted
-    intervals, plus the shifted coordinates.
+    Each interval contributes its score times the fraction of the tile it
+    covers; the contributions in a tile are combined with *aggregation*.
+    Like :func:`tile_aggregate_count`, one row is returned per covered tile,
+    sorted by group and tile start.
 
-    Notes
-    -----
-    All heavy lifting happens inside Rust; this wrapper only dispatches to the
-    correct concrete wrapper based on NumPy dtypes.
+    Returns
+    -------
+    tile_groups, tile_starts, tile_ends, tile_scores
     """
-    # `_dispatch_unary` accepts `groups=None` because this operation has no
-    # grouping column.
     return _dispatch_unary(
-        "window_numpy",          # base name of the Rust wrapper
-        groups = _groups_or_arange(groups, len(starts)),
+        "tile_scores_numpy",
+        groups=groups,
         starts=starts,
         ends=ends,
-        negative_strand=negative_strand,
-        window_size=window_size,
+        scores=np.asarray(scores, dtype=np.float64),
+        tile_size=tile_size,
+        aggregation=aggregation,
     )
 
-def tile(
+def tile_n(
     *,
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
     negative_strand: NDArray[np.bool_],
-    tile_size: int,
+    n: int,
 ) -> tuple[
     NDArray[GroupIdInt],  # indices
-    NDArray[RangeInt],    # tile starts
-    NDArray[RangeInt],    # tile ends
-    NDArray[np.float64],  # overlap fraction
+    NDArray[RangeInt],    # bin starts
+    NDArray[RangeInt],    # bin ends
+    NDArray[np.uint32],   # bin ordinal
 ]:
     """
-    Split each interval into fixed-size tiles.
-
-    * For positive-strand rows (`negative_strand == False`) tiling proceeds
-      from 5'→3'.
-    * For negative-strand rows (`negative_strand == True`) tiling proceeds
-      from 3'→5'.
+    Split each interval into exactly `n` bins of (almost) equal length.
 
-    Parameters
-    ----------
-    starts, ends
-        Coordinate arrays (dtype ``RangeInt``).
-    negative_strand
-        Boolean array indicating strand per interval.
-    tile_size
-        Desired tile length in the same units as *starts/ends*.
+    When the length is not divisible by `n`, the first ``length % n`` bins
+    counted from the 5' end are one base longer.  Bins are emitted 5'→3'
+    (right→left for negative-strand rows), and *ordinal* gives each bin's
+    position 0‥n-1 in that order.  Intervals shorter than `n` only yield
+    their non-empty bins.
 
     Returns
     -------
-    indices, tile_starts, tile_ends, overlap_fraction
-        *indices* (`uint32`) is the permutation that sorts the tiles in
-        genomic order; *overlap_fraction* reports, for each tile, the fraction
-        of its bases that overlap the original interval (useful when the last
-        tile is truncated).
+    indices, bin_starts, bin_ends, ordinal
     """
     return _dispatch_unary(
-        "tile_numpy",        # base name of the Rust wrapper
+        "tile_n_numpy",
         groups = None,
         starts=starts,
         ends=ends,
         negative_strand=negative_strand,
-        tile_size=tile_size,
+        n=n,
     )
 
 def _as_vec(x, n: int, dtype) -> NDArray:
@@ -864,7 +2603,22 @@ def spliced_subsequence(                     # same public signature
     start: int | NDArray | list[int],
     end: int | NDArray | list[int] | None = None,
     force_plus_strand: bool = False,
+    validate_exons: bool = False,
 ) -> tuple[NDArray[GroupIdInt], NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Slice every transcript (one *groups* id per transcript) in spliced
+    coordinates, from *start* to *end* bases along its exons.
+
+    Offsets count from the 5′ end of each transcript (negative ones from its
+    3′ end, so ``start=-50`` keeps the last 50 nt).  *start* and *end* may
+    also be per-row arrays; a transcript uses the values of its first exon.  With *force_plus_strand* every transcript is read left to
+    right as if it were on the plus strand.
+
+    With *validate_exons*, first check that each transcript's exons share a
+    strand and are given in coordinate order without overlapping, raising
+    ``ValueError`` naming the offending transcript otherwise (e.g. when
+    several transcripts share a group id).
+    """
 
     n = len(starts)
     dtype = starts.dtype
@@ -892,9 +2646,38 @@ def spliced_subsequence(                     # same public signature
         slice_starts=slice_starts,
         slice_ends=slice_ends,
         force_plus_strand=force_plus_strand,
+        validate_exons=validate_exons,
     )[:3]
 
 
+def spliced_length(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt],
+    strand_flags: NDArray[np.bool_],
+) -> tuple[NDArray[GroupIdInt], NDArray[RangeInt]]:
+    """
+    Total spliced length (sum of exon lengths) of every transcript.
+
+    Useful to turn offsets counted from the 3′ end into absolute ones before
+    calling :func:`spliced_subsequence`.
+
+    Returns
+    -------
+    groups, lengths
+        One row per distinct *groups* id, in ascending order.
+    """
+    lengths = _dispatch_unary(
+        "spliced_length_numpy",
+        starts,
+        ends,
+        groups,
+        strand_flags=strand_flags,
+    )
+    return np.unique(groups), lengths
+
+
 def split(
     *,
     starts: NDArray[RangeInt],
@@ -902,6 +2685,7 @@ def split(
     groups: NDArray[GroupIdInt] | None = None,
     slack: int = 0,
     between: bool = False,
+    max_len: int | None = None,
 ) -> tuple[
     NDArray[GroupIdInt],  # indices
     NDArray[RangeInt],    # split starts
@@ -926,6 +2710,9 @@ def split(
     between
         If *True*, return the gaps; if *False* (default), return the block
         fragments.
+    max_len
+        Optional cap on the output length: longer fragments are cut every
+        *max_len* bases from their start.
 
     Returns
     -------
@@ -933,6 +2720,16 @@ def split(
         *indices* (`uint32`) identifies the input interval that produced each
         output fragment or gap.
     """
+    if max_len is not None:
+        return _dispatch_unary(
+            "split_max_length_numpy",
+            starts,
+            ends,
+            groups,
+            max_len=max_len,
+            slack=slack,
+            between=between,
+        )
     return _dispatch_unary(
         "split_numpy",   # base name of the Rust wrapper
         starts,
@@ -942,19 +2739,80 @@ def split(
         between=between,
     )
 
+def partition_into_layers(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+) -> NDArray[np.uint32]:
+    """
+    Divide intervals into non-overlapping layers (0, 1, 2, ...).
+
+    Intervals are visited in (group, start) order and greedily placed in the
+    first layer whose last interval ends at or before their start, so the
+    number of layers per group is the maximum overlap depth.  Bookended
+    intervals share a layer.
+
+    Returns
+    -------
+    NDArray[np.uint32]
+        Layer of every input row, in input order.
+    """
+    return _dispatch_unary(
+        "partition_into_layers_numpy",
+        starts,
+        ends,
+        groups,
+    )
+
 def extend(
     *,
     starts: NDArray[RangeInt],
     ends: NDArray[RangeInt],
     negative_strand: NDArray[np.bool_],
     groups: NDArray[GroupIdInt] | None = None,
-    ext_3: int,
-    ext_5: int,
+    ext: int | None = None,
+    ext_3: int | None = None,
+    ext_5: int | None = None,
+    chrs: NDArray[GroupIdInt] | None = None,
+    chrom_ids: NDArray[GroupIdInt] | None = None,
+    chrom_length: NDArray[RangeInt] | None = None,
 ) -> tuple[NDArray[RangeInt], NDArray[RangeInt]]:
-    """Extend intervals upstream/downstream; see full docstring above."""
+    """Extend intervals upstream/downstream; see full docstring above.
+
+    Give either *ext* (both ends) or *ext_3* and/or *ext_5*.  Without
+    *groups* every interval is extended on its own; with *groups* (e.g.
+    transcript ids) only each group's outermost rows move, and the 5′ end
+    follows the strand of the group's first row.
+
+    When *chrs* (one chromosome per row) and *chrom_ids*/*chrom_length*
+    (the length of each chromosome) are given, the extended coordinates are
+    clipped to ``[0, chrom_length]`` of each row's chromosome in the same
+    pass.
+    """
+    if not ((chrs is None) == (chrom_ids is None) == (chrom_length is None)):
+        raise ValueError("chrs, chrom_ids and chrom_length must be given together")
+
     if groups is None:
-        groups = np.zeros(starts.shape[0], dtype=np.uint32)
+        if chrom_ids is not None:
+            raise ValueError("chrs, chrom_ids and chrom_length need groups")
+        return _dispatch_unary(
+            "extend_rows_numpy",
+            starts=starts,
+            ends=ends,
+            groups=None,
+            negative_strand=negative_strand,
+            ext=ext,
+            ext_3=ext_3,
+            ext_5=ext_5,
+        )
 
+    kw = {}
+    if chrom_ids is not None:
+        _, grp_t, pos_t = _resolve_rust_fn("extend_numpy", groups.dtype, starts.dtype)
+        kw["chrs"] = _cast(np.asarray(chrs), grp_t)
+        kw["chrom_ids"] = _cast(np.asarray(chrom_ids), grp_t)
+        kw["chrom_lengths"] = _cast(np.asarray(chrom_length), pos_t)
 
     return _dispatch_unary(
         "extend_numpy",
@@ -962,8 +2820,80 @@ def extend(
         ends=ends,
         groups=groups,
         negative_strand=negative_strand,
+        ext=ext,
         ext_3=ext_3,
         ext_5=ext_5,
+        **kw,
+    )
+
+def midpoints(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+) -> NDArray[RangeInt]:
+    """
+    Midpoint ``(start + end) // 2`` of every interval, rounded down for odd
+    lengths.
+    """
+    return _dispatch_unary("midpoints_numpy", starts=starts, ends=ends, groups=None)
+
+
+def midpoint_intervals(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+) -> tuple[NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Unit-length intervals ``[mid, mid + 1)`` at every interval's midpoint
+    (see :func:`midpoints`), e.g. to turn peak summits into point features
+    before :func:`nearest`.
+    """
+    return _dispatch_unary("midpoint_intervals_numpy", starts=starts, ends=ends, groups=None)
+
+
+def extend_5prime(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    negative_strand: NDArray[np.bool_],
+    amount: int,
+) -> tuple[NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Extend every interval by *amount* at its 5′ end only.
+
+    The 5′ end is the start for plus-strand rows and the end for rows where
+    *negative_strand* is ``True``.
+    """
+    return _dispatch_unary(
+        "extend_5prime_numpy",
+        groups=None,
+        starts=starts,
+        ends=ends,
+        negative_strand=negative_strand,
+        amount=amount,
+    )
+
+
+def extend_3prime(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    negative_strand: NDArray[np.bool_],
+    amount: int,
+) -> tuple[NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Extend every interval by *amount* at its 3′ end only.
+
+    The 3′ end is the end for plus-strand rows and the start for rows where
+    *negative_strand* is ``True``.
+    """
+    return _dispatch_unary(
+        "extend_3prime_numpy",
+        groups=None,
+        starts=starts,
+        ends=ends,
+        negative_strand=negative_strand,
+        amount=amount,
     )
 
 def group_cumsum(
@@ -973,6 +2903,7 @@ def group_cumsum(
     negative_strand: NDArray[np.bool_],
     groups: NDArray[GroupIdInt] | None = None,
     sort: bool = True,
+    return_totals: bool = False,
 ) -> tuple[NDArray[np.uint32], NDArray[RangeInt], NDArray[RangeInt]]:
     """
     Strand-aware cumulative lengths of every interval.
@@ -992,14 +2923,20 @@ def group_cumsum(
         to belong to a single group (filled with zeros).
     sort : bool, default True
         Whether to sort the results by the original row order.
+    return_totals : bool, default False
+        Also return each group's total length (e.g. the spliced transcript
+        length), broadcast to every row of the group.
 
     Returns
     -------
     tuple of ndarray
-        ``(idx, cumsum_start, cumsum_end)``, where
+        ``(idx, cumsum_start, cumsum_end)``, or
+        ``(idx, cumsum_start, cumsum_end, group_total)`` with
+        *return_totals*, where
 
         * ``idx`` is ``uint32`` – original indices,
-        * ``cumsum_start`` / ``cumsum_end`` share the dtype of *starts*/*ends*.
+        * ``cumsum_start`` / ``cumsum_end`` / ``group_total`` share the dtype
+          of *starts*/*ends*.
 
     Notes
     -----
@@ -1011,7 +2948,7 @@ def group_cumsum(
         groups = np.zeros(starts.shape[0], dtype=np.uint32)
 
     return _dispatch_unary(
-        "group_cumsum_numpy",
+        "group_cumsum_totals_numpy" if return_totals else "group_cumsum_numpy",
         starts=starts,
         ends=ends,
         groups=groups,
@@ -1025,14 +2962,19 @@ def genome_bounds(
     groups: NDArray[GroupIdInt],
     starts: NDArray[RangeInt],
     ends:   NDArray[RangeInt],
+    chrom_ids: NDArray[GroupIdInt],
     chrom_length: NDArray[RangeInt],
     clip: bool = False,
     only_right: bool = False,
-) -> tuple[
-    NDArray[np.uintp],   # indices (usize → uintp)
-    NDArray[RangeInt],   # new starts
-    NDArray[RangeInt],   # new ends
-]:
+    return_clip_flags: bool = False,
+) -> (
+    tuple[
+        NDArray[np.uintp],   # indices (usize → uintp)
+        NDArray[RangeInt],   # new starts
+        NDArray[RangeInt],   # new ends
+    ]
+    | tuple[NDArray[np.uintp], NDArray[RangeInt], NDArray[RangeInt], NDArray[np.uint8]]
+):
     """
     Clip or flag intervals that extend beyond chromosome bounds.
 
@@ -1041,31 +2983,247 @@ def genome_bounds(
     groups, starts, ends
         Interval set. *groups* must reference the chromosomes in *chrom_ids*.
     chrom_ids, chrom_length
-        Parallel arrays mapping chromosome IDs to their total length.
+        Parallel arrays mapping chromosome IDs to their total length.  A
+        group missing from *chrom_ids* raises ``ValueError``.
     clip
         If *True*, coordinates are clipped to the bounds; if *False* only
         intervals lying wholly outside are returned.
     only_right
         When *True*, treat `ends > chrom_length` as out-of-bounds but ignore
         negative starts.
+    return_clip_flags
+        Also return a ``uint8`` flag per output row: ``0`` unchanged,
+        ``1`` start clipped, ``2`` end clipped, ``3`` both.  Flags are only
+        non-zero with *clip=True*.
 
     Returns
     -------
-    idx, new_starts, new_ends
+    idx, new_starts, new_ends[, flags]
         *idx* is the index of the input row affected.  If *clip=False* the
         two coordinate arrays echo the offending interval; if *clip=True*
         they hold the clipped coordinates.
     """
+    # The length table is passed as-is, so cast it to the kernel's dtypes.
+    _, grp_t, pos_t = _resolve_rust_fn("genome_bounds_numpy", groups.dtype, starts.dtype)
     return _dispatch_unary(
-        "genome_bounds_numpy",    # base name of the Rust wrapper
+        "genome_bounds_flags_numpy" if return_clip_flags else "genome_bounds_numpy",
         starts,
         ends,
         groups,
-        chrom_lengths=chrom_length,
+        chrom_ids=_cast(np.asarray(chrom_ids), grp_t),
+        chrom_lengths=_cast(np.asarray(chrom_length), pos_t),
         clip=clip,
         only_right=only_right,
     )
 
+
+def sample_random_intervals(
+    *,
+    chrom_ids: NDArray[GroupIdInt],
+    chrom_length: NDArray[RangeInt],
+    n: int,
+    min_len: int,
+    max_len: int,
+    seed: int = 0,
+) -> tuple[NDArray[GroupIdInt], NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Draw *n* random intervals, e.g. as test data or a permutation null.
+
+    Chromosomes are picked with probability proportional to *chrom_length*,
+    so positions are uniform over the genome.  Lengths are uniform in
+    ``[min_len, max_len]`` and every interval lies within its chromosome;
+    chromosomes shorter than *min_len* are never picked.  The same *seed*
+    gives the same intervals.
+
+    Returns
+    -------
+    groups, starts, ends
+        In the order drawn, with the dtypes of *chrom_ids* and
+        *chrom_length*.
+    """
+    chrom_ids = np.asarray(chrom_ids)
+    chrom_length = np.asarray(chrom_length)
+    if chrom_ids.shape != chrom_length.shape:
+        raise ValueError("chrom_ids and chrom_length must have identical length")
+
+    rust_fn, grp_t, pos_t = _resolve_rust_fn(
+        "sample_random_intervals_numpy", chrom_ids.dtype, chrom_length.dtype
+    )
+    groups, starts, ends = rust_fn(
+        _cast(chrom_ids, grp_t), _cast(chrom_length, pos_t), n, min_len, max_len, seed=seed
+    )
+    return (
+        groups.astype(chrom_ids.dtype, copy=False),
+        starts.astype(chrom_length.dtype, copy=False),
+        ends.astype(chrom_length.dtype, copy=False),
+    )
+
+
+def permute_starts(
+    *,
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    groups: NDArray[GroupIdInt] | None = None,
+    chrom_ids: NDArray[GroupIdInt],
+    chrom_length: NDArray[RangeInt],
+    seed: int = 0,
+) -> tuple[NDArray[RangeInt], NDArray[RangeInt]]:
+    """
+    Move every interval to a random position on its own chromosome, keeping
+    its length.
+
+    New starts are uniform over the positions that keep the interval within
+    ``[0, chrom_length]``.  The same *seed* gives the same placement.
+
+    Returns
+    -------
+    new_starts, new_ends
+        In input order.
+    """
+    if groups is None:
+        groups = np.zeros(len(starts), dtype=np.uint8)
+    _, grp_t, pos_t = _resolve_rust_fn("permute_starts_numpy", groups.dtype, starts.dtype)
+    return _dispatch_unary(
+        "permute_starts_numpy",
+        starts,
+        ends,
+        groups,
+        chrom_ids=_cast(np.asarray(chrom_ids), grp_t),
+        chrom_lengths=_cast(np.asarray(chrom_length), pos_t),
+        seed=seed,
+    )
+
+
+def outside_bounds_circular(
+    *,
+    groups: NDArray[GroupIdInt],
+    starts: NDArray[RangeInt],
+    ends: NDArray[RangeInt],
+    chrom_len_ids: NDArray[GroupIdInt],
+    chrom_lens: NDArray[RangeInt],
+    clip: bool = False,
+) -> tuple[
+    NDArray[np.uint32],  # input row of each output interval
+    NDArray[RangeInt],   # wrapped starts
+    NDArray[RangeInt],   # wrapped ends
+]:
+    """
+    Wrap intervals on circular chromosomes back onto ``[0, chrom_len)``.
+
+    Parameters
+    ----------
+    groups, starts, ends
+        Interval set.
+    chrom_len_ids, chrom_lens
+        Parallel arrays mapping chromosome IDs to their length.  A group
+        that is not listed raises ``ValueError``.
+    clip
+        If *True*, an interval crossing the origin is truncated at the
+        chromosome end instead of being split in two.
+
+    Returns
+    -------
+    idx, new_starts, new_ends
+        A wrapped interval that crosses the origin yields two rows,
+        ``[start, chrom_len)`` and ``[0, end)``, sharing the same *idx*.
+    """
+    _, grp_t, pos_t = _resolve_rust_fn("outside_bounds_circular_numpy", groups.dtype, starts.dtype)
+    return _dispatch_unary(
+        "outside_bounds_circular_numpy",
+        starts,
+        ends,
+        groups,
+        chrom_len_ids=_cast(np.asarray(chrom_len_ids), grp_t),
+        chrom_lens=_cast(np.asarray(chrom_lens), pos_t),
+        clip=clip,
+    )
+
+
+def read_narrowpeak(
+    path: str,
+) -> tuple[
+    NDArray[np.uint32],   # chromosome codes
+    NDArray[np.int32],    # starts
+    NDArray[np.int32],    # ends
+    NDArray[np.bool_],    # strands (True unless "-")
+    NDArray[np.float64],  # signalValue
+    NDArray[np.float64],  # pValue
+    NDArray[np.float64],  # qValue
+    list[str],            # chromosome names
+]:
+    """
+    Read an ENCODE narrowPeak (BED6+4) file.
+
+    Chromosomes are coded as ``uint32`` in order of first appearance;
+    ``chrom_names[code]`` gives the name back.  Header lines (``#``,
+    ``track``, ``browser``) are skipped.
+
+    Returns
+    -------
+    chr_codes, starts, ends, strands, signal, pvalue, qvalue, chrom_names
+    """
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    return rust_mod.read_narrowpeak_numpy(str(path))
+
+
+def read_gtf(
+    path: str,
+    feature_types: Sequence[str] = ("exon",),
+) -> tuple[
+    NDArray[np.uint32],   # chromosome codes
+    NDArray[np.int32],    # starts (0-based)
+    NDArray[np.int32],    # ends (half-open)
+    NDArray[np.bool_],    # strands (True unless "-")
+    list[str],            # chromosome names
+]:
+    """
+    Read the rows of a GTF file whose feature column is in *feature_types*.
+
+    Coordinates are converted from GTF's 1-based inclusive convention to
+    0-based half-open intervals.  Chromosomes are coded as in
+    :func:`read_narrowpeak`.
+
+    Returns
+    -------
+    chr_codes, starts, ends, strands, chrom_names
+    """
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    return rust_mod.read_gtf_numpy(str(path), list(feature_types))
+
+
+def write_gtf(
+    path: str,
+    chr_codes: NDArray[np.integer],
+    starts: NDArray[np.integer],
+    ends: NDArray[np.integer],
+    strands: NDArray[np.bool_],
+    features: Sequence[str],
+    attributes: Sequence[str],
+    chrom_names: Sequence[str],
+) -> None:
+    """
+    Write intervals as a GTF file; the inverse of :func:`read_gtf`.
+
+    Coordinates are converted back to GTF's 1-based inclusive convention and
+    ``chrom_names[code]`` names each chromosome.  *features* fills column 3,
+    *attributes* is written verbatim into column 9, and the source, score
+    and frame columns are ``.``.  Coordinates must fit in int32; larger
+    values raise a ``ValueError`` rather than being truncated.
+    """
+    if len(starts) > 0:
+        check_min_max_with_slack(starts, ends, 0, np.int32)
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    rust_mod.write_gtf_numpy(
+        str(path),
+        np.ascontiguousarray(chr_codes, dtype=np.uint32),
+        np.ascontiguousarray(starts, dtype=np.int32),
+        np.ascontiguousarray(ends, dtype=np.int32),
+        np.ascontiguousarray(strands, dtype=np.bool_),
+        list(features),
+        list(attributes),
+        list(chrom_names),
+    )
+
 def minimal_integer_dtype(arr: NDArray[np.integer]) -> np.dtype:
     """Return the narrowest integer dtype that can hold *arr*,
     preserving the signed/unsigned kind of the original dtype.
@@ -1299,6 +3457,12 @@ def _cast(
 
 
 # ---- resolve the correct Rust wrapper ------------------------------
+def _result_class(name: str) -> type:
+    """Class (e.g. ``NearestIndex_u32_i64``) exported by the Rust module."""
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    return getattr(rust_mod, name)
+
+
 def _resolve_rust_fn(
     prefix: str,
     grp_dt: np.dtype,
@@ -1311,7 +3475,12 @@ def _resolve_rust_fn(
         raise TypeError(f"Unsupported dtype pair: {grp_dt}, {pos_dt}") from exc
 
     rust_mod = importlib.import_module(".ruranges", package="ruranges")
-    rust_fn = getattr(rust_mod, f"{prefix}_{suffix}")
+    try:
+        rust_fn = getattr(rust_mod, f"{prefix}_{suffix}")
+    except AttributeError as exc:
+        if pos_dt is not None and pos_dt.kind == "f":
+            raise TypeError(f"{prefix.removesuffix('_numpy')} does not support float positions") from exc
+        raise
     return rust_fn, tgt_grp, tgt_pos
 
 
@@ -1326,6 +3495,9 @@ def _dispatch_unary(
     """Common body for functions that take one (chroms, starts, ends) trio."""
     roles = RETURN_SIGNATURES[prefix]
 
+    if extra_kw.pop("validate", False):
+        validate_intervals(starts, ends)
+
     length = check_array_lengths(starts, ends, groups)
 
     groups_validated = validate_groups(length, groups)
@@ -1347,6 +3519,56 @@ def _dispatch_unary(
     return cast_kernel_outputs(prefix, raw, roles, grp_t, pos_t, grp_orig, pos_orig)
 
 
+_VALIDATE_KERNELS = {
+    np.dtype(np.int16): "validate_intervals_numpy_i16",
+    np.dtype(np.int32): "validate_intervals_numpy_i32",
+    np.dtype(np.int64): "validate_intervals_numpy_i64",
+}
+
+
+def validate_intervals(starts: NDArray[RangeInt], ends: NDArray[RangeInt]) -> None:
+    """
+    Raise ``ValueError`` unless every interval has ``start < end``.
+
+    Most operations accept ``validate=True`` to run this check first.  It is
+    off by default because zero-length intervals are valid input with
+    defined behaviour:
+
+    * :func:`overlaps`, :func:`count_overlaps` and :func:`point_overlaps`
+      treat ``[p, p)`` as a point that overlaps intervals with
+      ``start <= p < end``, and never other points.
+    * :func:`merge` joins a point with regions containing (or, with
+      *merge_touching*, touching) it; an isolated point becomes a
+      zero-length region.
+    * :func:`tile` returns the tile containing the point, with an overlap
+      fraction of 0.
+
+    :func:`window`, :func:`tile_n` and :func:`tile_aggregate_count` silently
+    skip zero-length and reversed intervals.
+    """
+    starts = np.asarray(starts)
+    ends = np.asarray(ends)
+    pos_t = np.result_type(starts, ends)
+    if pos_t not in _VALIDATE_KERNELS:
+        pos_t = np.dtype(np.int64)
+    rust_mod = importlib.import_module(".ruranges", package="ruranges")
+    getattr(rust_mod, _VALIDATE_KERNELS[pos_t])(_cast(starts, pos_t), _cast(ends, pos_t))
+
+
+def _split_slack(slack: Any, length: int) -> tuple[Any, dict[str, Any]]:
+    """Turn a scalar-or-array *slack* into ``(scalar, {"slacks": array})``.
+
+    Kernels accepting per-row slack take the array as the ``slacks``
+    keyword and ignore the scalar, which is then 0.
+    """
+    if np.ndim(slack) == 0:
+        return slack, {}
+    slacks = np.asarray(slack)
+    if slacks.shape != (length,):
+        raise ValueError("slack array must have the same length as starts")
+    return 0, {"slacks": slacks}
+
+
 def _dispatch_binary(
     prefix: str,
     groups: NDArray | None,
@@ -1361,6 +3583,10 @@ def _dispatch_binary(
     """Shared body for all two-interval-set operations with automatic
     down-casting to minimal integer dtypes."""
 
+    if extra_kw.pop("validate", False):
+        validate_intervals(starts, ends)
+        validate_intervals(starts2, ends2)
+
     length = check_array_lengths(starts, ends, groups)
     length2 = check_array_lengths(starts2, ends2, groups2)
 
@@ -1379,16 +3605,23 @@ def _dispatch_binary(
     grp_tmp = _common_integer_dtype(
         groups_validated, groups2_validated
     )  # signed/unsigned kept
-    pos_tmp = _common_integer_dtype(starts, ends, starts2, ends2)
+    # Per-row slack (``slacks=``) is cast along with the coordinates.
+    slacks = extra_kw.get("slacks")
+    pos_arrays = [starts, ends, starts2, ends2] + ([slacks] if slacks is not None else [])
+    if any(np.asarray(a).dtype.kind == "f" for a in pos_arrays):
+        # Float coordinates are passed through at their common precision.
+        pos_tmp = np.result_type(*pos_arrays)
+    else:
+        pos_tmp = _common_integer_dtype(*pos_arrays)
 
-    # Slack range check (only if the caller supplied slack > 0)
-    slack = extra_kw.get("slack", 0)
-    try:
-        if slack:
-            check_min_max_with_slack(starts, ends, slack, pos_tmp)
-    except ValueError:
-        # Too narrow → fall back to original pos dtype
-        pos_tmp = pos_orig
+        # Slack range check (only if the caller supplied slack > 0)
+        slack = extra_kw.get("slack", 0)
+        try:
+            if slack:
+                check_min_max_with_slack(starts, ends, slack, pos_tmp)
+        except ValueError:
+            # Too narrow → fall back to original pos dtype
+            pos_tmp = pos_orig
 
     # ------------------------------------------------------------------
     # 3.  Now resolve the Rust kernel for the *temporary* dtypes
@@ -1406,6 +3639,8 @@ def _dispatch_binary(
     e1 = ends.astype(pos_t, copy=False)
     s2 = starts2.astype(pos_t, copy=False)
     e2 = ends2.astype(pos_t, copy=False)
+    if slacks is not None:
+        extra_kw["slacks"] = slacks.astype(pos_t, copy=False)
 
     # ------------------------------------------------------------------
     # 5.  Dispatch & post-process results
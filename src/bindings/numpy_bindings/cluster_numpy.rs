@@ -1,23 +1,26 @@
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::{pyfunction, Py, PyResult, Python};
 
-use crate::cluster::sweep_line_cluster;
+use crate::cluster::{
+    sweep_line_cluster, sweep_line_cluster_min_coverage, sweep_line_cluster_stats, sweep_line_cluster_transitions,
+};
 
 
 macro_rules! define_cluster_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
         #[pyfunction]
-        #[pyo3(signature = (chrs, starts, ends, slack = 0))]
+        #[pyo3(signature = (chrs, starts, ends, slack = <$pos_ty>::default(), merge_touching = false))]
         #[allow(non_snake_case)]
         pub fn $fname(
             chrs:  PyReadonlyArray1<$chr_ty>,
             starts: PyReadonlyArray1<$pos_ty>,
             ends:   PyReadonlyArray1<$pos_ty>,
             slack:  $pos_ty,
+            merge_touching: bool,
             py: Python<'_>,
         ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
             let (cluster_ids, idx) = sweep_line_cluster(
-                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack,
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack, merge_touching,
             );
             Ok((
                 cluster_ids.into_pyarray(py).to_owned().into(),
@@ -37,4 +40,126 @@ define_cluster_numpy!(cluster_numpy_u16_i32, u16, i32);
 define_cluster_numpy!(cluster_numpy_u16_i16, u16, i16);
 define_cluster_numpy!(cluster_numpy_u8_i64,  u8,  i64);
 define_cluster_numpy!(cluster_numpy_u8_i32,  u8,  i32);
-define_cluster_numpy!(cluster_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_cluster_numpy!(cluster_numpy_u8_i16,  u8,  i16);
+// float coordinates, for non-genomic data
+define_cluster_numpy!(cluster_numpy_u32_f64, u32, f64);
+define_cluster_numpy!(cluster_numpy_u32_f32, u32, f32);
+define_cluster_numpy!(cluster_numpy_u64_f64, u64, f64);
+
+macro_rules! define_cluster_min_coverage_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, slack = 0, min_neighbours = 1, merge_touching = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:           PyReadonlyArray1<$chr_ty>,
+            starts:         PyReadonlyArray1<$pos_ty>,
+            ends:           PyReadonlyArray1<$pos_ty>,
+            slack:          $pos_ty,
+            min_neighbours: u32,
+            merge_touching: bool,
+            py: Python<'_>,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            let (cluster_ids, idx) = sweep_line_cluster_min_coverage(
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack, merge_touching, min_neighbours,
+            );
+            Ok((
+                cluster_ids.into_pyarray(py).to_owned().into(),
+                idx.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u64_i64, u64, i64);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u32_i64, u32, i64);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u32_i32, u32, i32);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u32_i16, u32, i16);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u16_i64, u16, i64);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u16_i32, u16, i32);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u16_i16, u16, i16);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u8_i64,  u8,  i64);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u8_i32,  u8,  i32);
+define_cluster_min_coverage_numpy!(cluster_min_coverage_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_cluster_transitions_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, slack = <$pos_ty>::default(), merge_touching = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:  PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            slack:  $pos_ty,
+            merge_touching: bool,
+            py: Python<'_>,
+        ) -> PyResult<(Py<PyArray1<$pos_ty>>, Py<PyArray1<u32>>)> {
+            let (positions, cluster_ids) = sweep_line_cluster_transitions(
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack, merge_touching,
+            );
+            Ok((
+                positions.into_pyarray(py).to_owned().into(),
+                cluster_ids.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u64_i64, u64, i64);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u32_i64, u32, i64);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u32_i32, u32, i32);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u32_i16, u32, i16);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u16_i64, u16, i64);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u16_i32, u16, i32);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u16_i16, u16, i16);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u8_i64,  u8,  i64);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u8_i32,  u8,  i32);
+define_cluster_transitions_numpy!(cluster_transitions_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_cluster_stats_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, slack = 0, merge_touching = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:   PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            slack:  $pos_ty,
+            merge_touching: bool,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<u32>>,     // cluster ids
+            Py<PyArray1<u32>>,     // sizes
+            Py<PyArray1<f64>>,     // mean starts
+            Py<PyArray1<f64>>,     // mean ends
+            Py<PyArray1<$pos_ty>>, // min starts
+            Py<PyArray1<$pos_ty>>, // max ends
+        )> {
+            let (ids, sizes, mean_starts, mean_ends, min_starts, max_ends) = sweep_line_cluster_stats(
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack, merge_touching,
+            );
+            Ok((
+                ids        .into_pyarray(py).to_owned().into(),
+                sizes      .into_pyarray(py).to_owned().into(),
+                mean_starts.into_pyarray(py).to_owned().into(),
+                mean_ends  .into_pyarray(py).to_owned().into(),
+                min_starts .into_pyarray(py).to_owned().into(),
+                max_ends   .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_cluster_stats_numpy!(cluster_stats_numpy_u64_i64, u64, i64);
+define_cluster_stats_numpy!(cluster_stats_numpy_u32_i64, u32, i64);
+define_cluster_stats_numpy!(cluster_stats_numpy_u32_i32, u32, i32);
+define_cluster_stats_numpy!(cluster_stats_numpy_u32_i16, u32, i16);
+define_cluster_stats_numpy!(cluster_stats_numpy_u16_i64, u16, i64);
+define_cluster_stats_numpy!(cluster_stats_numpy_u16_i32, u16, i32);
+define_cluster_stats_numpy!(cluster_stats_numpy_u16_i16, u16, i16);
+define_cluster_stats_numpy!(cluster_stats_numpy_u8_i64,  u8,  i64);
+define_cluster_stats_numpy!(cluster_stats_numpy_u8_i32,  u8,  i32);
+define_cluster_stats_numpy!(cluster_stats_numpy_u8_i16,  u8,  i16);
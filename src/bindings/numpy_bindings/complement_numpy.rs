@@ -2,7 +2,7 @@ use pyo3::prelude::*;
 use numpy::{IntoPyArray, PyReadonlyArray1, PyArray1};
 use rustc_hash::FxHashMap;
 
-use crate::complement_single::sweep_line_complement;
+use crate::complement_single::{sweep_line_complement, sweep_line_gaps};
 
 macro_rules! define_complement_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
@@ -14,7 +14,8 @@ macro_rules! define_complement_numpy {
             chrom_len_ids,
             chrom_lens,
             slack     = 0,
-            include_first_interval = false
+            include_first_interval = false,
+            min_gap   = 0
         ))]
         #[allow(non_snake_case)]
         pub fn $fname(
@@ -26,6 +27,7 @@ macro_rules! define_complement_numpy {
             chrom_lens: PyReadonlyArray1<$pos_ty>,
             slack: $pos_ty,
             include_first_interval: bool,
+            min_gap: $pos_ty,
         ) -> PyResult<(
             Py<PyArray1<$chr_ty>>,
             Py<PyArray1<$pos_ty>>,
@@ -53,6 +55,7 @@ macro_rules! define_complement_numpy {
                 slack,
                 &lens_map,
                 include_first_interval,
+                min_gap,
             );
 
             Ok((
@@ -75,4 +78,47 @@ define_complement_numpy!(complement_numpy_u16_i32, u16, i32);
 define_complement_numpy!(complement_numpy_u16_i16, u16, i16);
 define_complement_numpy!(complement_numpy_u8_i64,  u8,  i64);
 define_complement_numpy!(complement_numpy_u8_i32,  u8,  i32);
-define_complement_numpy!(complement_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_complement_numpy!(complement_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_gaps_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (groups, starts, ends, slack = 0))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python<'_>,
+            groups: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            slack: $pos_ty,
+        ) -> PyResult<(
+            Py<PyArray1<u32>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+        )> {
+            let (out_idx, out_starts, out_ends) = sweep_line_gaps(
+                groups.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                slack,
+            );
+
+            Ok((
+                out_idx.into_pyarray(py).to_owned().into(),
+                out_starts.into_pyarray(py).to_owned().into(),
+                out_ends.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_gaps_numpy!(gaps_numpy_u64_i64, u64, i64);
+define_gaps_numpy!(gaps_numpy_u32_i64, u32, i64);
+define_gaps_numpy!(gaps_numpy_u32_i32, u32, i32);
+define_gaps_numpy!(gaps_numpy_u32_i16, u32, i16);
+define_gaps_numpy!(gaps_numpy_u16_i64, u16, i64);
+define_gaps_numpy!(gaps_numpy_u16_i32, u16, i32);
+define_gaps_numpy!(gaps_numpy_u16_i16, u16, i16);
+define_gaps_numpy!(gaps_numpy_u8_i64,  u8,  i64);
+define_gaps_numpy!(gaps_numpy_u8_i32,  u8,  i32);
+define_gaps_numpy!(gaps_numpy_u8_i16,  u8,  i16);
@@ -1,7 +1,7 @@
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::{pyfunction, Py, PyResult, Python};
 
-use crate::complement::sweep_line_non_overlaps;
+use crate::complement::{sweep_line_non_overlaps, sweep_line_non_overlaps_mask, sweep_line_set2_uncovered};
 
 
 macro_rules! define_complement_overlaps_numpy {
@@ -38,4 +38,79 @@ define_complement_overlaps_numpy!(complement_overlaps_numpy_u16_i32, u16, i32);
 define_complement_overlaps_numpy!(complement_overlaps_numpy_u16_i16, u16, i16);
 define_complement_overlaps_numpy!(complement_overlaps_numpy_u8_i64,  u8,  i64);
 define_complement_overlaps_numpy!(complement_overlaps_numpy_u8_i32,  u8,  i32);
-define_complement_overlaps_numpy!(complement_overlaps_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_complement_overlaps_numpy!(complement_overlaps_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_no_overlap_mask_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            slack:   $pos_ty,
+        ) -> PyResult<Py<PyArray1<bool>>> {
+            let mask = sweep_line_non_overlaps_mask(
+                chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
+                chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+                slack,
+            );
+            Ok(mask.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u64_i64, u64, i64);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u32_i64, u32, i64);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u32_i32, u32, i32);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u32_i16, u32, i16);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u16_i64, u16, i64);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u16_i32, u16, i32);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u16_i16, u16, i16);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u8_i64,  u8,  i64);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u8_i32,  u8,  i32);
+define_no_overlap_mask_numpy!(no_overlap_mask_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_complement_set2_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            slack:   $pos_ty,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<$pos_ty>>, Py<PyArray1<$pos_ty>>)> {
+            let (idx2, out_starts, out_ends) = sweep_line_set2_uncovered(
+                chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
+                chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+                slack,
+            );
+            Ok((
+                idx2      .into_pyarray(py).to_owned().into(),
+                out_starts.into_pyarray(py).to_owned().into(),
+                out_ends  .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_complement_set2_numpy!(complement_set2_numpy_u64_i64, u64, i64);
+define_complement_set2_numpy!(complement_set2_numpy_u32_i64, u32, i64);
+define_complement_set2_numpy!(complement_set2_numpy_u32_i32, u32, i32);
+define_complement_set2_numpy!(complement_set2_numpy_u32_i16, u32, i16);
+define_complement_set2_numpy!(complement_set2_numpy_u16_i64, u16, i64);
+define_complement_set2_numpy!(complement_set2_numpy_u16_i32, u16, i32);
+define_complement_set2_numpy!(complement_set2_numpy_u16_i16, u16, i16);
+define_complement_set2_numpy!(complement_set2_numpy_u8_i64,  u8,  i64);
+define_complement_set2_numpy!(complement_set2_numpy_u8_i32,  u8,  i32);
+define_complement_set2_numpy!(complement_set2_numpy_u8_i16,  u8,  i16);
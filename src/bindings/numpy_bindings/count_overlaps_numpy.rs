@@ -1,11 +1,16 @@
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::{pyfunction, Py, PyResult, Python};
 
-use crate::overlaps::count_overlaps;
+use super::overlaps_numpy::{row_slacks, strand_slices};
+use crate::overlaps::{
+    any_overlap, count_overlaps, count_overlaps_both, count_overlaps_row_slack, count_overlaps_stranded, widen_by_row_slack,
+};
+use crate::ruranges_structs::StrandMode;
 
 macro_rules! define_count_overlaps_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
         #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack, slacks = None, strand_mode = "any", strands = None, strands2 = None))]
         #[allow(non_snake_case)]
         pub fn $fname(
             py: Python<'_>,
@@ -16,7 +21,43 @@ macro_rules! define_count_overlaps_numpy {
             starts2: PyReadonlyArray1<$pos_ty>,
             ends2:   PyReadonlyArray1<$pos_ty>,
             slack:   $pos_ty,
+            slacks:  Option<PyReadonlyArray1<$pos_ty>>,
+            strand_mode: &str,
+            strands:  Option<PyReadonlyArray1<bool>>,
+            strands2: Option<PyReadonlyArray1<bool>>,
         ) -> PyResult<Py<PyArray1<u32>>> {
+            let (mode, strand_slices) = strand_slices(
+                strand_mode, strands.as_ref(), strands2.as_ref(), starts.as_slice()?.len(), starts2.as_slice()?.len(),
+            )?;
+            let slacks = row_slacks(slacks.as_ref(), starts.as_slice()?.len())?;
+
+            if let (StrandMode::Same | StrandMode::Opposite, Some((s1, s2))) = (mode, strand_slices) {
+                let counts = match slacks {
+                    Some(slacks) => {
+                        let (wide_starts, wide_ends) = widen_by_row_slack(starts.as_slice()?, ends.as_slice()?, slacks);
+                        count_overlaps_stranded(
+                            chrs.as_slice()?,  &wide_starts,        &wide_ends,        s1,
+                            chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?, s2,
+                            0, strand_mode,
+                        )
+                    }
+                    None => count_overlaps_stranded(
+                        chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,  s1,
+                        chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?, s2,
+                        slack, strand_mode,
+                    ),
+                };
+                return Ok(counts.into_pyarray(py).to_owned().into());
+            }
+
+            if let Some(slacks) = slacks {
+                let counts = count_overlaps_row_slack(
+                    chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,   slacks,
+                    chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+                );
+                return Ok(counts.into_pyarray(py).to_owned().into());
+            }
+
             let counts = count_overlaps(
                 chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
                 chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
@@ -37,4 +78,78 @@ define_count_overlaps_numpy!(count_overlaps_numpy_u16_i32, u16, i32);
 define_count_overlaps_numpy!(count_overlaps_numpy_u16_i16, u16, i16);
 define_count_overlaps_numpy!(count_overlaps_numpy_u8_i64,  u8,  i64);
 define_count_overlaps_numpy!(count_overlaps_numpy_u8_i32,  u8,  i32);
-define_count_overlaps_numpy!(count_overlaps_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_count_overlaps_numpy!(count_overlaps_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_count_overlaps_both_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            slack:   $pos_ty,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            let (counts, counts2) = count_overlaps_both(
+                chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
+                chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+                slack,
+            );
+            Ok((
+                counts.into_pyarray(py).to_owned().into(),
+                counts2.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u64_i64, u64, i64);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u32_i64, u32, i64);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u32_i32, u32, i32);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u32_i16, u32, i16);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u16_i64, u16, i64);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u16_i32, u16, i32);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u16_i16, u16, i16);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u8_i64,  u8,  i64);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u8_i32,  u8,  i32);
+define_count_overlaps_both_numpy!(count_overlaps_both_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_any_overlap_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack = 0))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            slack:   $pos_ty,
+        ) -> PyResult<Py<PyArray1<bool>>> {
+            let found = any_overlap(
+                chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
+                chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+                slack,
+            );
+            Ok(found.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_any_overlap_numpy!(any_overlap_numpy_u64_i64, u64, i64);
+define_any_overlap_numpy!(any_overlap_numpy_u32_i64, u32, i64);
+define_any_overlap_numpy!(any_overlap_numpy_u32_i32, u32, i32);
+define_any_overlap_numpy!(any_overlap_numpy_u32_i16, u32, i16);
+define_any_overlap_numpy!(any_overlap_numpy_u16_i64, u16, i64);
+define_any_overlap_numpy!(any_overlap_numpy_u16_i32, u16, i32);
+define_any_overlap_numpy!(any_overlap_numpy_u16_i16, u16, i16);
+define_any_overlap_numpy!(any_overlap_numpy_u8_i64,  u8,  i64);
+define_any_overlap_numpy!(any_overlap_numpy_u8_i32,  u8,  i32);
+define_any_overlap_numpy!(any_overlap_numpy_u8_i16,  u8,  i16);
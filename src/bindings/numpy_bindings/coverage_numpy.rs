@@ -0,0 +1,170 @@
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
+
+use crate::coverage::{anchor_profile, coverage_normalize_rpkm, coverage_normalize_rpm, coverage_rle};
+
+macro_rules! define_coverage_normalize_rpm_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, interval_chrs, interval_starts, interval_ends))]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:            PyReadonlyArray1<$chr_ty>,
+            starts:          PyReadonlyArray1<$pos_ty>,
+            ends:            PyReadonlyArray1<$pos_ty>,
+            interval_chrs:   PyReadonlyArray1<$chr_ty>,
+            interval_starts: PyReadonlyArray1<$pos_ty>,
+            interval_ends:   PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<Py<PyArray1<f64>>> {
+            let values = coverage_normalize_rpm(
+                chrs.as_slice()?,          starts.as_slice()?,          ends.as_slice()?,
+                interval_chrs.as_slice()?, interval_starts.as_slice()?, interval_ends.as_slice()?,
+            );
+            Ok(values.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+macro_rules! define_coverage_normalize_rpkm_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, interval_chrs, interval_starts, interval_ends))]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:            PyReadonlyArray1<$chr_ty>,
+            starts:          PyReadonlyArray1<$pos_ty>,
+            ends:            PyReadonlyArray1<$pos_ty>,
+            interval_chrs:   PyReadonlyArray1<$chr_ty>,
+            interval_starts: PyReadonlyArray1<$pos_ty>,
+            interval_ends:   PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<Py<PyArray1<f64>>> {
+            let values = coverage_normalize_rpkm(
+                chrs.as_slice()?,          starts.as_slice()?,          ends.as_slice()?,
+                interval_chrs.as_slice()?, interval_starts.as_slice()?, interval_ends.as_slice()?,
+            );
+            Ok(values.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u64_i64, u64, i64);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u32_i64, u32, i64);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u32_i32, u32, i32);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u32_i16, u32, i16);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u16_i64, u16, i64);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u16_i32, u16, i32);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u16_i16, u16, i16);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u8_i64,  u8,  i64);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u8_i32,  u8,  i32);
+define_coverage_normalize_rpm_numpy!(coverage_normalize_rpm_numpy_u8_i16,  u8,  i16);
+
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u64_i64, u64, i64);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u32_i64, u32, i64);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u32_i32, u32, i32);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u32_i16, u32, i16);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u16_i64, u16, i64);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u16_i32, u16, i32);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u16_i16, u16, i16);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u8_i64,  u8,  i64);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u8_i32,  u8,  i32);
+define_coverage_normalize_rpkm_numpy!(coverage_normalize_rpkm_numpy_u8_i16,  u8,  i16);
+
+// The anchors arrive as their full windows `[pos - half_width, pos +
+// half_width)` so the Python side picks a position dtype that holds them.
+macro_rules! define_anchor_profile_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, window_starts, _window_ends, chrs2, starts2, ends2, half_width, bin_size, strands = None))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:          PyReadonlyArray1<$chr_ty>,
+            window_starts: PyReadonlyArray1<$pos_ty>,
+            _window_ends:  PyReadonlyArray1<$pos_ty>,
+            chrs2:         PyReadonlyArray1<$chr_ty>,
+            starts2:       PyReadonlyArray1<$pos_ty>,
+            ends2:         PyReadonlyArray1<$pos_ty>,
+            half_width:    $pos_ty,
+            bin_size:      $pos_ty,
+            strands:       Option<PyReadonlyArray1<bool>>,
+        ) -> PyResult<Py<PyArray2<u32>>> {
+            if bin_size <= 0 || half_width < 0 {
+                return Err(PyValueError::new_err(
+                    "bin_size must be positive and half_width non-negative",
+                ));
+            }
+            let chrs = chrs.as_slice()?;
+            let strands = match strands.as_ref() {
+                Some(s) => {
+                    let s = s.as_slice()?;
+                    if s.len() != chrs.len() {
+                        return Err(PyValueError::new_err("strands must have one entry per anchor"));
+                    }
+                    Some(s)
+                }
+                None => None,
+            };
+            let positions: Vec<$pos_ty> = window_starts.as_slice()?.iter().map(|&s| s + half_width).collect();
+
+            let counts = anchor_profile(
+                chrs, &positions, strands,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                half_width, bin_size,
+            );
+            let n_bins = if chrs.is_empty() { 0 } else { counts.len() / chrs.len() };
+            let matrix = Array2::from_shape_vec((chrs.len(), n_bins), counts)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            Ok(matrix.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_anchor_profile_numpy!(anchor_profile_numpy_u64_i64, u64, i64);
+define_anchor_profile_numpy!(anchor_profile_numpy_u32_i64, u32, i64);
+define_anchor_profile_numpy!(anchor_profile_numpy_u32_i32, u32, i32);
+define_anchor_profile_numpy!(anchor_profile_numpy_u32_i16, u32, i16);
+define_anchor_profile_numpy!(anchor_profile_numpy_u16_i64, u16, i64);
+define_anchor_profile_numpy!(anchor_profile_numpy_u16_i32, u16, i32);
+define_anchor_profile_numpy!(anchor_profile_numpy_u16_i16, u16, i16);
+define_anchor_profile_numpy!(anchor_profile_numpy_u8_i64,  u8,  i64);
+define_anchor_profile_numpy!(anchor_profile_numpy_u8_i32,  u8,  i32);
+define_anchor_profile_numpy!(anchor_profile_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_coverage_rle_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends))]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:   PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<(Py<PyArray1<$chr_ty>>,
+                       Py<PyArray1<$pos_ty>>,
+                       Py<PyArray1<$pos_ty>>,
+                       Py<PyArray1<u32>>)> {
+            let (run_chrs, run_starts, run_ends, depths) =
+                coverage_rle(chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?);
+
+            Ok((
+                run_chrs  .into_pyarray(py).to_owned().into(),
+                run_starts.into_pyarray(py).to_owned().into(),
+                run_ends  .into_pyarray(py).to_owned().into(),
+                depths    .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_coverage_rle_numpy!(coverage_rle_numpy_u64_i64, u64, i64);
+define_coverage_rle_numpy!(coverage_rle_numpy_u32_i64, u32, i64);
+define_coverage_rle_numpy!(coverage_rle_numpy_u32_i32, u32, i32);
+define_coverage_rle_numpy!(coverage_rle_numpy_u32_i16, u32, i16);
+define_coverage_rle_numpy!(coverage_rle_numpy_u16_i64, u16, i64);
+define_coverage_rle_numpy!(coverage_rle_numpy_u16_i32, u16, i32);
+define_coverage_rle_numpy!(coverage_rle_numpy_u16_i16, u16, i16);
+define_coverage_rle_numpy!(coverage_rle_numpy_u8_i64,  u8,  i64);
+define_coverage_rle_numpy!(coverage_rle_numpy_u8_i32,  u8,  i32);
+define_coverage_rle_numpy!(coverage_rle_numpy_u8_i16,  u8,  i16);
@@ -0,0 +1,84 @@
+use numpy::PyReadonlyArray1;
+use pyo3::{exceptions::PyValueError, pyfunction, PyResult};
+
+use crate::evaluate::{evaluate_overlap, jaccard, jaccard_with_overlap_stats};
+use crate::helpers::check_pair_lengths;
+
+macro_rules! define_evaluate_overlap_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, min_overlap_fraction = 0.0))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            min_overlap_fraction: f64,
+        ) -> PyResult<(f64, f64, f64)> {
+            check_pair_lengths(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            Ok(evaluate_overlap(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                min_overlap_fraction,
+            ))
+        }
+    };
+}
+
+macro_rules! define_jaccard_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, overlap_stats = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            overlap_stats: bool,
+        ) -> PyResult<(f64, Option<(usize, f64, f64)>)> {
+            let (chrs, starts, ends) = (chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?);
+            let (chrs2, starts2, ends2) = (chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?);
+            check_pair_lengths(chrs, starts, ends, chrs2, starts2, ends2).map_err(PyValueError::new_err)?;
+
+            if overlap_stats {
+                let (index, stats) = jaccard_with_overlap_stats(chrs, starts, ends, chrs2, starts2, ends2);
+                Ok((index, Some((stats.n_pairs, stats.mean, stats.median))))
+            } else {
+                Ok((jaccard(chrs, starts, ends, chrs2, starts2, ends2), None))
+            }
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u64_i64, u64, i64);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u32_i64, u32, i64);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u32_i32, u32, i32);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u32_i16, u32, i16);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u16_i64, u16, i64);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u16_i32, u16, i32);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u16_i16, u16, i16);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u8_i64,  u8,  i64);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u8_i32,  u8,  i32);
+define_evaluate_overlap_numpy!(evaluate_overlap_numpy_u8_i16,  u8,  i16);
+define_jaccard_numpy!(jaccard_numpy_u64_i64, u64, i64);
+define_jaccard_numpy!(jaccard_numpy_u32_i64, u32, i64);
+define_jaccard_numpy!(jaccard_numpy_u32_i32, u32, i32);
+define_jaccard_numpy!(jaccard_numpy_u32_i16, u32, i16);
+define_jaccard_numpy!(jaccard_numpy_u16_i64, u16, i64);
+define_jaccard_numpy!(jaccard_numpy_u16_i32, u16, i32);
+define_jaccard_numpy!(jaccard_numpy_u16_i16, u16, i16);
+define_jaccard_numpy!(jaccard_numpy_u8_i64,  u8,  i64);
+define_jaccard_numpy!(jaccard_numpy_u8_i32,  u8,  i32);
+define_jaccard_numpy!(jaccard_numpy_u8_i16,  u8,  i16);
@@ -1,5 +1,6 @@
-use pyo3::prelude::*;
+use pyo3::{exceptions::PyValueError, prelude::*};
 use numpy::{IntoPyArray, PyReadonlyArray1, PyArray1};
+use rustc_hash::FxHashMap;
 
 use crate::extend;
 
@@ -11,23 +12,53 @@ macro_rules! define_extend_numpy {
             starts,
             ends,
             negative_strand,      // optional (Python requires a default)
-            ext_3,
-            ext_5
+            ext = None,
+            ext_3 = None,
+            ext_5 = None,
+            chrs = None,
+            chrom_ids = None,
+            chrom_lengths = None
         ))]
+        #[allow(clippy::too_many_arguments)]
         pub fn $fname(
             groups:           PyReadonlyArray1<$grp_ty>,
             starts:           PyReadonlyArray1<$pos_ty>,
             ends:             PyReadonlyArray1<$pos_ty>,
             negative_strand:  PyReadonlyArray1<bool>,
-            ext_3: $pos_ty,
-            ext_5: $pos_ty,
+            ext:   Option<$pos_ty>,
+            ext_3: Option<$pos_ty>,
+            ext_5: Option<$pos_ty>,
+            chrs:             Option<PyReadonlyArray1<$grp_ty>>,
+            chrom_ids:        Option<PyReadonlyArray1<$grp_ty>>,
+            chrom_lengths:    Option<PyReadonlyArray1<$pos_ty>>,
             py: Python<'_>,
         ) -> PyResult<(Py<PyArray1<$pos_ty>>, Py<PyArray1<$pos_ty>>)> {
 
+            let lens_map = match (&chrs, chrom_ids, chrom_lengths) {
+                (Some(_), Some(ids), Some(lens)) => {
+                    let (ids, lens) = (ids.as_slice()?, lens.as_slice()?);
+                    if ids.len() != lens.len() {
+                        return Err(PyValueError::new_err(
+                            "chrom_ids and chrom_lengths must have identical length",
+                        ));
+                    }
+                    Some(ids.iter().copied().zip(lens.iter().copied()).collect::<FxHashMap<_, _>>())
+                }
+                (None, None, None) => None,
+                _ => {
+                    return Err(PyValueError::new_err(
+                        "chrs, chrom_ids and chrom_lengths must be given together",
+                    ))
+                }
+            };
+            let chrs = chrs.as_ref().map(|c| c.as_slice()).transpose()?;
+            let chrom_lens = chrs.zip(lens_map.as_ref());
+
             let (new_starts, new_ends) = extend::extend_grp(
                     groups.as_slice()?, starts.as_slice()?, ends.as_slice()?,
-                    negative_strand.as_slice()?, ext_3, ext_5,
-                );
+                    negative_strand.as_slice()?, ext, ext_3, ext_5, chrom_lens,
+                )
+                .map_err(PyValueError::new_err)?;
 
             Ok((
                 new_starts.into_pyarray(py).to_owned().into(),
@@ -46,4 +77,66 @@ define_extend_numpy!(extend_numpy_u16_i32, u16, i32);
 define_extend_numpy!(extend_numpy_u16_i16, u16, i16);
 define_extend_numpy!(extend_numpy_u8_i64,  u8,  i64);
 define_extend_numpy!(extend_numpy_u8_i32,  u8,  i32);
-define_extend_numpy!(extend_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_extend_numpy!(extend_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_extend_rows_numpy {
+    ($fname:ident, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (starts, ends, negative_strand, ext = None, ext_3 = None, ext_5 = None))]
+        pub fn $fname(
+            starts:           PyReadonlyArray1<$pos_ty>,
+            ends:             PyReadonlyArray1<$pos_ty>,
+            negative_strand:  PyReadonlyArray1<bool>,
+            ext:   Option<$pos_ty>,
+            ext_3: Option<$pos_ty>,
+            ext_5: Option<$pos_ty>,
+            py: Python<'_>,
+        ) -> PyResult<(Py<PyArray1<$pos_ty>>, Py<PyArray1<$pos_ty>>)> {
+            let (new_starts, new_ends) = extend::extend(
+                    starts.as_slice()?, ends.as_slice()?, negative_strand.as_slice()?, ext, ext_3, ext_5,
+                )
+                .map_err(PyValueError::new_err)?;
+
+            Ok((
+                new_starts.into_pyarray(py).to_owned().into(),
+                new_ends  .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_extend_rows_numpy!(extend_rows_numpy_i64, i64);
+define_extend_rows_numpy!(extend_rows_numpy_i32, i32);
+define_extend_rows_numpy!(extend_rows_numpy_i16, i16);
+
+// `$kernel` is `extend_5prime` or `extend_3prime`; both share a signature.
+macro_rules! define_extend_prime_numpy {
+    ($fname:ident, $kernel:ident, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (starts, ends, negative_strand, amount))]
+        pub fn $fname(
+            starts:           PyReadonlyArray1<$pos_ty>,
+            ends:             PyReadonlyArray1<$pos_ty>,
+            negative_strand:  PyReadonlyArray1<bool>,
+            amount:           $pos_ty,
+            py: Python<'_>,
+        ) -> PyResult<(Py<PyArray1<$pos_ty>>, Py<PyArray1<$pos_ty>>)> {
+            let (new_starts, new_ends) = extend::$kernel(
+                starts.as_slice()?, ends.as_slice()?, negative_strand.as_slice()?, amount,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            Ok((
+                new_starts.into_pyarray(py).to_owned().into(),
+                new_ends  .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_extend_prime_numpy!(extend_5prime_numpy_i64, extend_5prime, i64);
+define_extend_prime_numpy!(extend_5prime_numpy_i32, extend_5prime, i32);
+define_extend_prime_numpy!(extend_5prime_numpy_i16, extend_5prime, i16);
+define_extend_prime_numpy!(extend_3prime_numpy_i64, extend_3prime, i64);
+define_extend_prime_numpy!(extend_3prime_numpy_i32, extend_3prime, i32);
+define_extend_prime_numpy!(extend_3prime_numpy_i16, extend_3prime, i16);
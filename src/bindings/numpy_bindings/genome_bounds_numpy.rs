@@ -3,7 +3,11 @@ use pyo3::{exceptions::PyValueError, prelude::*};
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
 use pyo3::{pyfunction, Py, PyResult, Python};
 
-use crate::outside_bounds::outside_bounds;
+use rustc_hash::FxHashMap;
+
+use crate::outside_bounds::{
+    chrom_lens_per_row, outside_bounds, outside_bounds_circular, outside_bounds_with_flags,
+};
 
 macro_rules! define_genome_bounds_numpy {
     ($fname:ident, $grp_ty:ty, $pos_ty:ty) => {
@@ -12,15 +16,17 @@ macro_rules! define_genome_bounds_numpy {
             groups,
             starts,
             ends,
-            chrom_lengths,     //  <-- single vector, same length as rows
+            chrom_ids,
+            chrom_lengths,
             clip = false,
             only_right = false
         ))]
-        #[allow(non_snake_case)]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
         pub fn $fname(
             groups:         PyReadonlyArray1<$grp_ty>,
             starts:         PyReadonlyArray1<$pos_ty>,
             ends:           PyReadonlyArray1<$pos_ty>,
+            chrom_ids:      PyReadonlyArray1<$grp_ty>,
             chrom_lengths:  PyReadonlyArray1<$pos_ty>,
             clip:           bool,
             only_right:     bool,
@@ -30,21 +36,13 @@ macro_rules! define_genome_bounds_numpy {
             Py<PyArray1<$pos_ty>>,
             Py<PyArray1<$pos_ty>>,
         )> {
-            use pyo3::exceptions::PyValueError;
-
-            // Fast length consistency check while we still hold the gil.
-            let n = starts.len()?;
-            if ends.len()? != n || groups.len()? != n || chrom_lengths.len()? != n {
-                return Err(PyValueError::new_err(
-                    "`groups`, `starts`, `ends`, and `chrom_lengths` must all have the same length",
-                ));
-            }
+            let lens_map = chrom_lens_map(&chrom_ids, &chrom_lengths)?;
 
             let (idx, new_starts, new_ends) = outside_bounds(
                 groups.as_slice()?,
                 starts.as_slice()?,
                 ends.as_slice()?,
-                chrom_lengths.as_slice()?,
+                &lens_map,
                 clip,
                 only_right,
             )
@@ -60,6 +58,30 @@ macro_rules! define_genome_bounds_numpy {
     };
 }
 
+/// Builds the chromosome-length lookup from two parallel arrays.
+pub(crate) fn chrom_lens_map<G, T>(
+    chrom_ids: &PyReadonlyArray1<G>,
+    chrom_lengths: &PyReadonlyArray1<T>,
+) -> PyResult<FxHashMap<G, T>>
+where
+    G: numpy::Element + std::hash::Hash + Eq + Copy,
+    T: numpy::Element + Copy,
+{
+    let keys = chrom_ids.as_slice()?;
+    let vals = chrom_lengths.as_slice()?;
+    if keys.len() != vals.len() {
+        return Err(PyValueError::new_err(
+            "chrom_ids and chrom_lengths must have identical length",
+        ));
+    }
+
+    let mut lens_map = FxHashMap::with_capacity_and_hasher(keys.len(), Default::default());
+    for (&k, &v) in keys.iter().zip(vals.iter()) {
+        lens_map.insert(k, v);
+    }
+    Ok(lens_map)
+}
+
 // ── concrete instantiations ────────────────────────────────────────────
 define_genome_bounds_numpy!(genome_bounds_numpy_u64_i64, u64, i64);
 define_genome_bounds_numpy!(genome_bounds_numpy_u32_i64, u32, i64);
@@ -70,4 +92,135 @@ define_genome_bounds_numpy!(genome_bounds_numpy_u16_i32, u16, i32);
 define_genome_bounds_numpy!(genome_bounds_numpy_u16_i16, u16, i16);
 define_genome_bounds_numpy!(genome_bounds_numpy_u8_i64,  u8,  i64);
 define_genome_bounds_numpy!(genome_bounds_numpy_u8_i32,  u8,  i32);
-define_genome_bounds_numpy!(genome_bounds_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_genome_bounds_numpy!(genome_bounds_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_genome_bounds_flags_numpy {
+    ($fname:ident, $grp_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature=(
+            groups,
+            starts,
+            ends,
+            chrom_ids,
+            chrom_lengths,
+            clip = false,
+            only_right = false
+        ))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            groups:         PyReadonlyArray1<$grp_ty>,
+            starts:         PyReadonlyArray1<$pos_ty>,
+            ends:           PyReadonlyArray1<$pos_ty>,
+            chrom_ids:      PyReadonlyArray1<$grp_ty>,
+            chrom_lengths:  PyReadonlyArray1<$pos_ty>,
+            clip:           bool,
+            only_right:     bool,
+            py:             Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<u32>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<u8>>,
+        )> {
+            let lens_map = chrom_lens_map(&chrom_ids, &chrom_lengths)?;
+            let row_lens = chrom_lens_per_row(groups.as_slice()?, &lens_map)
+                .map_err(PyValueError::new_err)?;
+
+            let (idx, new_starts, new_ends, flags) = outside_bounds_with_flags(
+                groups.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                &row_lens,
+                clip,
+                only_right,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            Ok((
+                idx.into_pyarray(py).to_owned().into(),
+                new_starts.into_pyarray(py).to_owned().into(),
+                new_ends.into_pyarray(py).to_owned().into(),
+                flags.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u64_i64, u64, i64);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u32_i64, u32, i64);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u32_i32, u32, i32);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u32_i16, u32, i16);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u16_i64, u16, i64);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u16_i32, u16, i32);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u16_i16, u16, i16);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u8_i64,  u8,  i64);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u8_i32,  u8,  i32);
+define_genome_bounds_flags_numpy!(genome_bounds_flags_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_outside_bounds_circular_numpy {
+    ($fname:ident, $grp_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature=(groups, starts, ends, chrom_len_ids, chrom_lens, clip = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            groups:         PyReadonlyArray1<$grp_ty>,
+            starts:         PyReadonlyArray1<$pos_ty>,
+            ends:           PyReadonlyArray1<$pos_ty>,
+            chrom_len_ids:  PyReadonlyArray1<$grp_ty>,
+            chrom_lens:     PyReadonlyArray1<$pos_ty>,
+            clip:           bool,
+            py:             Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<u32>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+        )> {
+            let n = starts.len()?;
+            if ends.len()? != n || groups.len()? != n {
+                return Err(PyValueError::new_err(
+                    "`groups`, `starts`, and `ends` must all have the same length",
+                ));
+            }
+
+            let keys = chrom_len_ids.as_slice()?;
+            let vals = chrom_lens.as_slice()?;
+            if keys.len() != vals.len() {
+                return Err(PyValueError::new_err(
+                    "chrom_len_ids and chrom_lens must have identical length",
+                ));
+            }
+
+            let mut lens_map: FxHashMap<$grp_ty, $pos_ty> =
+                FxHashMap::with_capacity_and_hasher(keys.len(), Default::default());
+            for (&k, &v) in keys.iter().zip(vals.iter()) {
+                lens_map.insert(k, v);
+            }
+
+            let (idx, new_starts, new_ends) = outside_bounds_circular(
+                groups.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                &lens_map,
+                clip,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            Ok((
+                idx.into_pyarray(py).to_owned().into(),
+                new_starts.into_pyarray(py).to_owned().into(),
+                new_ends.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u64_i64, u64, i64);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u32_i64, u32, i64);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u32_i32, u32, i32);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u32_i16, u32, i16);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u16_i64, u16, i64);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u16_i32, u16, i32);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u16_i16, u16, i16);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u8_i64,  u8,  i64);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u8_i32,  u8,  i32);
+define_outside_bounds_circular_numpy!(outside_bounds_circular_numpy_u8_i16,  u8,  i16);
@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
+
+use crate::io::bed::read_narrowpeak_file;
+use crate::io::gtf::{read_gtf_file_filtered, write_gtf_file};
+
+/// `(chr_codes, starts, ends, strands, signal, pvalue, qvalue, chrom_names)`
+type NarrowPeakArrays = (
+    Py<PyArray1<u32>>,
+    Py<PyArray1<i32>>,
+    Py<PyArray1<i32>>,
+    Py<PyArray1<bool>>,
+    Py<PyArray1<f64>>,
+    Py<PyArray1<f64>>,
+    Py<PyArray1<f64>>,
+    Vec<String>,
+);
+
+#[pyfunction]
+pub fn read_narrowpeak_numpy(py: Python<'_>, path: &str) -> PyResult<NarrowPeakArrays> {
+    let peaks = read_narrowpeak_file(PathBuf::from(path))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let data = peaks.data;
+    let strands = data.strands.unwrap_or_default();
+
+    Ok((
+        data.chroms.into_pyarray(py).to_owned().into(),
+        data.starts.into_pyarray(py).to_owned().into(),
+        data.ends.into_pyarray(py).to_owned().into(),
+        strands.into_pyarray(py).to_owned().into(),
+        peaks.signal_value.into_pyarray(py).to_owned().into(),
+        peaks.p_value.into_pyarray(py).to_owned().into(),
+        peaks.q_value.into_pyarray(py).to_owned().into(),
+        peaks.chrom_names,
+    ))
+}
+
+/// `(chr_codes, starts, ends, strands, chrom_names)`
+type GtfArrays = (
+    Py<PyArray1<u32>>,
+    Py<PyArray1<i32>>,
+    Py<PyArray1<i32>>,
+    Py<PyArray1<bool>>,
+    Vec<String>,
+);
+
+#[pyfunction]
+#[pyo3(signature = (path, feature_types = vec!["exon".to_string()]))]
+pub fn read_gtf_numpy(py: Python<'_>, path: &str, feature_types: Vec<String>) -> PyResult<GtfArrays> {
+    let feature_types: Vec<&str> = feature_types.iter().map(String::as_str).collect();
+    let gtf = read_gtf_file_filtered(PathBuf::from(path), &feature_types)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let data = gtf.data;
+    let strands = data.strands.unwrap_or_default();
+
+    Ok((
+        data.chroms.into_pyarray(py).to_owned().into(),
+        data.starts.into_pyarray(py).to_owned().into(),
+        data.ends.into_pyarray(py).to_owned().into(),
+        strands.into_pyarray(py).to_owned().into(),
+        gtf.chrom_names,
+    ))
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+pub fn write_gtf_numpy(
+    path: &str,
+    chrs: PyReadonlyArray1<u32>,
+    starts: PyReadonlyArray1<i32>,
+    ends: PyReadonlyArray1<i32>,
+    strands: PyReadonlyArray1<bool>,
+    features: Vec<String>,
+    attributes: Vec<String>,
+    chrom_names: Vec<String>,
+) -> PyResult<()> {
+    let features: Vec<&str> = features.iter().map(String::as_str).collect();
+    let attributes: Vec<&str> = attributes.iter().map(String::as_str).collect();
+    write_gtf_file(
+        PathBuf::from(path),
+        chrs.as_slice()?,
+        starts.as_slice()?,
+        ends.as_slice()?,
+        strands.as_slice()?,
+        &features,
+        &attributes,
+        &chrom_names,
+    )
+    .map_err(|e| PyValueError::new_err(e.to_string()))
+}
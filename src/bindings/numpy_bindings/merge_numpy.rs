@@ -1,19 +1,23 @@
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 
-use crate::merge::sweep_line_merge;
+use crate::merge::{sweep_line_merge, sweep_line_merge_ids, sweep_line_merge_values, sweep_line_span, MergeAnchor};
 
 
 macro_rules! define_merge_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
         #[pyfunction]
-        #[pyo3(signature = (chrs, starts, ends, slack = 0))]
-        #[allow(non_snake_case)]
+        #[pyo3(signature = (chrs, starts, ends, slack = <$pos_ty>::default(), presorted = false, merge_touching = false, anchor = "first", scores = None))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
         pub fn $fname(
             chrs:   PyReadonlyArray1<$chr_ty>,
             starts: PyReadonlyArray1<$pos_ty>,
             ends:   PyReadonlyArray1<$pos_ty>,
             slack:  $pos_ty,
+            presorted: bool,
+            merge_touching: bool,
+            anchor: &str,
+            scores: Option<PyReadonlyArray1<f64>>,
             py: Python<'_>,
         ) -> PyResult<(
             Py<PyArray1<u32>>,
@@ -21,8 +25,30 @@ macro_rules! define_merge_numpy {
             Py<PyArray1<$pos_ty>>,
             Py<PyArray1<u32>>,
         )> {
+            if anchor.parse::<MergeAnchor>().is_err() {
+                return Err(PyValueError::new_err(
+                    "anchor must be one of \"first\", \"last\", \"largest\" or \"score\"",
+                ));
+            }
+            let scores = match scores.as_ref() {
+                Some(scores) => {
+                    let scores = scores.as_slice()?;
+                    if scores.len() != starts.as_slice()?.len() {
+                        return Err(PyValueError::new_err(
+                            "scores must have the same length as starts",
+                        ));
+                    }
+                    Some(scores)
+                }
+                None if anchor.eq_ignore_ascii_case("score") => {
+                    return Err(PyValueError::new_err("anchor \"score\" requires scores"));
+                }
+                None => None,
+            };
+
             let (idx, m_starts, m_ends, counts) = sweep_line_merge(
-                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack,
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack, presorted,
+                merge_touching, anchor, scores,
             );
             Ok((
                 idx      .into_pyarray(py).to_owned().into(),
@@ -44,4 +70,154 @@ define_merge_numpy!(merge_numpy_u16_i32, u16, i32);
 define_merge_numpy!(merge_numpy_u16_i16, u16, i16);
 define_merge_numpy!(merge_numpy_u8_i64,  u8,  i64);
 define_merge_numpy!(merge_numpy_u8_i32,  u8,  i32);
-define_merge_numpy!(merge_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_merge_numpy!(merge_numpy_u8_i16,  u8,  i16);
+// float coordinates, for non-genomic data
+define_merge_numpy!(merge_numpy_u32_f64, u32, f64);
+define_merge_numpy!(merge_numpy_u32_f32, u32, f32);
+define_merge_numpy!(merge_numpy_u64_f64, u64, f64);
+macro_rules! define_merge_values_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, values, slack = 0, merge_touching = false, anchor = "first", scores = None))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            chrs:   PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            values: PyReadonlyArray1<f64>,
+            slack:  $pos_ty,
+            merge_touching: bool,
+            anchor: &str,
+            scores: Option<PyReadonlyArray1<f64>>,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<u32>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<u32>>,
+            Py<PyArray1<f64>>,
+            Py<PyArray1<f64>>,
+            Py<PyArray1<f64>>,
+        )> {
+            if anchor.parse::<MergeAnchor>().is_err() {
+                return Err(PyValueError::new_err(
+                    "anchor must be one of \"first\", \"last\", \"largest\" or \"score\"",
+                ));
+            }
+            let n = starts.as_slice()?.len();
+            let scores = match scores.as_ref() {
+                Some(scores) => {
+                    let scores = scores.as_slice()?;
+                    if scores.len() != n {
+                        return Err(PyValueError::new_err(
+                            "scores must have the same length as starts",
+                        ));
+                    }
+                    Some(scores)
+                }
+                None if anchor.eq_ignore_ascii_case("score") => {
+                    return Err(PyValueError::new_err("anchor \"score\" requires scores"));
+                }
+                None => None,
+            };
+
+            let (idx, m_starts, m_ends, counts, sums, maxs, means) = sweep_line_merge_values(
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack,
+                merge_touching, anchor, scores, values.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
+            Ok((
+                idx      .into_pyarray(py).to_owned().into(),
+                m_starts .into_pyarray(py).to_owned().into(),
+                m_ends   .into_pyarray(py).to_owned().into(),
+                counts   .into_pyarray(py).to_owned().into(),
+                sums     .into_pyarray(py).to_owned().into(),
+                maxs     .into_pyarray(py).to_owned().into(),
+                means    .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_merge_values_numpy!(merge_values_numpy_u64_i64, u64, i64);
+define_merge_values_numpy!(merge_values_numpy_u32_i64, u32, i64);
+define_merge_values_numpy!(merge_values_numpy_u32_i32, u32, i32);
+define_merge_values_numpy!(merge_values_numpy_u32_i16, u32, i16);
+define_merge_values_numpy!(merge_values_numpy_u16_i64, u16, i64);
+define_merge_values_numpy!(merge_values_numpy_u16_i32, u16, i32);
+define_merge_values_numpy!(merge_values_numpy_u16_i16, u16, i16);
+define_merge_values_numpy!(merge_values_numpy_u8_i64,  u8,  i64);
+define_merge_values_numpy!(merge_values_numpy_u8_i32,  u8,  i32);
+define_merge_values_numpy!(merge_values_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_span_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:   PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<$chr_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<u32>>,
+        )> {
+            let (s_chrs, s_starts, s_ends, counts) = sweep_line_span(
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?,
+            );
+            Ok((
+                s_chrs   .into_pyarray(py).to_owned().into(),
+                s_starts .into_pyarray(py).to_owned().into(),
+                s_ends   .into_pyarray(py).to_owned().into(),
+                counts   .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_span_numpy!(span_numpy_u64_i64, u64, i64);
+define_span_numpy!(span_numpy_u32_i64, u32, i64);
+define_span_numpy!(span_numpy_u32_i32, u32, i32);
+define_span_numpy!(span_numpy_u32_i16, u32, i16);
+define_span_numpy!(span_numpy_u16_i64, u16, i64);
+define_span_numpy!(span_numpy_u16_i32, u16, i32);
+define_span_numpy!(span_numpy_u16_i16, u16, i16);
+define_span_numpy!(span_numpy_u8_i64,  u8,  i64);
+define_span_numpy!(span_numpy_u8_i32,  u8,  i32);
+define_span_numpy!(span_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_merge_ids_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, slack = 0, merge_touching = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:   PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            slack:  $pos_ty,
+            merge_touching: bool,
+            py: Python<'_>,
+        ) -> PyResult<Py<PyArray1<u32>>> {
+            let merge_ids = sweep_line_merge_ids(
+                chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?, slack, merge_touching,
+            );
+            Ok(merge_ids.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_merge_ids_numpy!(merge_ids_numpy_u64_i64, u64, i64);
+define_merge_ids_numpy!(merge_ids_numpy_u32_i64, u32, i64);
+define_merge_ids_numpy!(merge_ids_numpy_u32_i32, u32, i32);
+define_merge_ids_numpy!(merge_ids_numpy_u32_i16, u32, i16);
+define_merge_ids_numpy!(merge_ids_numpy_u16_i64, u16, i64);
+define_merge_ids_numpy!(merge_ids_numpy_u16_i32, u16, i32);
+define_merge_ids_numpy!(merge_ids_numpy_u16_i16, u16, i16);
+define_merge_ids_numpy!(merge_ids_numpy_u8_i64,  u8,  i64);
+define_merge_ids_numpy!(merge_ids_numpy_u8_i32,  u8,  i32);
+define_merge_ids_numpy!(merge_ids_numpy_u8_i16,  u8,  i16);
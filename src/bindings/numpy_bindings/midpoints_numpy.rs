@@ -0,0 +1,58 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
+
+use crate::midpoints::{midpoint_intervals, midpoints};
+
+fn check_lengths(starts: usize, ends: usize) -> PyResult<()> {
+    if starts != ends {
+        return Err(PyValueError::new_err(format!(
+            "starts and ends must have the same length ({} != {})",
+            starts, ends
+        )));
+    }
+    Ok(())
+}
+
+macro_rules! define_midpoints_numpy {
+    ($fname:ident, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (starts, ends))]
+        pub fn $fname(
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            py: Python<'_>,
+        ) -> PyResult<Py<PyArray1<$pos_ty>>> {
+            let (starts, ends) = (starts.as_slice()?, ends.as_slice()?);
+            check_lengths(starts.len(), ends.len())?;
+            Ok(midpoints(starts, ends).into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_midpoints_numpy!(midpoints_numpy_i64, i64);
+define_midpoints_numpy!(midpoints_numpy_i32, i32);
+define_midpoints_numpy!(midpoints_numpy_i16, i16);
+
+macro_rules! define_midpoint_intervals_numpy {
+    ($fname:ident, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (starts, ends))]
+        pub fn $fname(
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            py: Python<'_>,
+        ) -> PyResult<(Py<PyArray1<$pos_ty>>, Py<PyArray1<$pos_ty>>)> {
+            let (starts, ends) = (starts.as_slice()?, ends.as_slice()?);
+            check_lengths(starts.len(), ends.len())?;
+            let (new_starts, new_ends) = midpoint_intervals(starts, ends);
+            Ok((
+                new_starts.into_pyarray(py).to_owned().into(),
+                new_ends  .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_midpoint_intervals_numpy!(midpoint_intervals_numpy_i64, i64);
+define_midpoint_intervals_numpy!(midpoint_intervals_numpy_i32, i32);
+define_midpoint_intervals_numpy!(midpoint_intervals_numpy_i16, i16);
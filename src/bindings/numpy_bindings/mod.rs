@@ -18,3 +18,10 @@ pub mod split_numpy;
 pub mod genome_bounds_numpy;
 pub mod group_cumsum_numpy;
 pub mod map_to_global_numpy;
+pub mod io_numpy;
+pub mod validate_numpy;
+pub mod multiprocessing_numpy;
+pub mod coverage_numpy;
+pub mod sample_numpy;
+pub mod evaluate_numpy;
+pub mod midpoints_numpy;
@@ -0,0 +1,34 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::{pyfunction, Py, PyResult, Python};
+
+use crate::multiprocessing::chrom_boundaries;
+
+macro_rules! define_chrom_boundaries_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            _ends: PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            let (order, offsets) = chrom_boundaries(chrs.as_slice()?, starts.as_slice()?);
+            Ok((
+                order.into_pyarray(py).to_owned().into(),
+                offsets.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u64_i64, u64, i64);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u32_i64, u32, i64);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u32_i32, u32, i32);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u32_i16, u32, i16);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u16_i64, u16, i64);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u16_i32, u16, i32);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u16_i16, u16, i16);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u8_i64,  u8,  i64);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u8_i32,  u8,  i32);
+define_chrom_boundaries_numpy!(chrom_boundaries_numpy_u8_i16,  u8,  i16);
@@ -1,11 +1,54 @@
-use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use std::str::FromStr;
 
-use crate::nearest::nearest;
+use num_traits::Zero;
+use numpy::{Element, IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 
+use crate::helpers::{check_pair_lengths, check_set_lengths};
+use crate::nearest::{nearest_with_ties, Direction, DistanceMetric, NearestIndex, NearestSortBy};
+
+/// Optional settings of `nearest_numpy`, passed as keyword arguments;
+/// `None` values keep the default.
+struct NearestOptions<'py, T: Element> {
+    exclude_group_ids: Option<PyReadonlyArray1<'py, u32>>,
+    exclude_group_ids2: Option<PyReadonlyArray1<'py, u32>>,
+    sort_by: String,
+    min_distance: T,
+    distance_metric: String,
+}
+
+impl<'py, T: Element + Zero + FromPyObject<'py>> NearestOptions<'py, T> {
+    fn from_kwargs(kwargs: Option<&Bound<'py, PyDict>>) -> PyResult<Self> {
+        let mut options = NearestOptions {
+            exclude_group_ids: None,
+            exclude_group_ids2: None,
+            sort_by: "idx".to_string(),
+            min_distance: T::zero(),
+            distance_metric: "edge".to_string(),
+        };
+        for (key, value) in kwargs.into_iter().flat_map(|kwargs| kwargs.iter()) {
+            if value.is_none() {
+                continue;
+            }
+            match key.extract::<String>()?.as_str() {
+                "exclude_group_ids" => options.exclude_group_ids = Some(value.extract()?),
+                "exclude_group_ids2" => options.exclude_group_ids2 = Some(value.extract()?),
+                "sort_by" => options.sort_by = value.extract()?,
+                "min_distance" => options.min_distance = value.extract()?,
+                "distance_metric" => options.distance_metric = value.extract()?,
+                other => return Err(PyValueError::new_err(format!("unexpected keyword argument {other:?}"))),
+            }
+        }
+        Ok(options)
+    }
+}
 
 macro_rules! define_nearest_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        /// Keyword options: `exclude_group_ids`, `exclude_group_ids2`,
+        /// `sort_by`, `min_distance` and `distance_metric`, as in `nearest`.
         #[pyfunction]
         #[pyo3(signature = (
             chrs, starts, ends,
@@ -13,9 +56,10 @@ macro_rules! define_nearest_numpy {
             slack = 0,                // <$pos_ty>::from(0) at call-site
             k = 1,
             include_overlaps = true,
-            direction = "any"
+            direction = "any",
+            **options
         ))]
-        #[allow(non_snake_case)]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
         pub fn $fname(
             py: Python<'_>,
             chrs:   PyReadonlyArray1<$chr_ty>,
@@ -28,19 +72,67 @@ macro_rules! define_nearest_numpy {
             k: usize,
             include_overlaps: bool,
             direction: &str,
+            options: Option<&Bound<'_, PyDict>>,
         ) -> PyResult<(Py<PyArray1<u32>>,
                        Py<PyArray1<u32>>,
-                       Py<PyArray1<$pos_ty>>)> {
-            let (idx1, idx2, dist) = nearest(
+                       Py<PyArray1<$pos_ty>>,
+                       Py<PyArray1<u32>>)> {
+            let NearestOptions {
+                exclude_group_ids, exclude_group_ids2, sort_by, min_distance, distance_metric,
+            } = NearestOptions::<$pos_ty>::from_kwargs(options)?;
+            let sort_by = sort_by.as_str();
+            let distance_metric = distance_metric.as_str();
+            if NearestSortBy::from_str(sort_by).is_err() {
+                return Err(PyValueError::new_err("sort_by must be \"idx\" or \"distance\""));
+            }
+            DistanceMetric::from_str(distance_metric).map_err(PyValueError::new_err)?;
+            if Direction::from_str(direction).is_err() {
+                return Err(PyValueError::new_err(
+                    "direction must be \"forward\", \"backward\" or \"any\"",
+                ));
+            }
+            check_pair_lengths(
                 chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
                 chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
-                slack, k, include_overlaps, direction,
+            )
+            .map_err(PyValueError::new_err)?;
+            // A single id array is enough when both sets are the same rows.
+            let ids = match exclude_group_ids.as_ref() {
+                Some(ids) => Some(ids.as_slice()?),
+                None => None,
+            };
+            let ids2 = match exclude_group_ids2.as_ref() {
+                Some(ids2) => Some(ids2.as_slice()?),
+                None => ids,
+            };
+            if ids.is_none() && exclude_group_ids2.is_some() {
+                return Err(PyValueError::new_err(
+                    "exclude_group_ids2 needs exclude_group_ids",
+                ));
+            }
+            let exclude = match (ids, ids2) {
+                (Some(ids), Some(ids2)) => {
+                    if ids.len() != starts.as_slice()?.len() || ids2.len() != starts2.as_slice()?.len() {
+                        return Err(PyValueError::new_err(
+                            "exclude_group_ids must match the lengths of the interval sets",
+                        ));
+                    }
+                    Some((ids, ids2))
+                }
+                _ => None,
+            };
+
+            let (idx1, idx2, dist, n_ties) = nearest_with_ties(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                slack, k, include_overlaps, direction, exclude, sort_by, min_distance, distance_metric,
             );
 
             Ok((
                 idx1.into_pyarray(py).to_owned().into(),
                 idx2.into_pyarray(py).to_owned().into(),
                 dist.into_pyarray(py).to_owned().into(),
+                n_ties.into_pyarray(py).to_owned().into(),
             ))
         }
     };
@@ -57,3 +149,82 @@ define_nearest_numpy!(nearest_numpy_u16_i16, u16, i16);
 define_nearest_numpy!(nearest_numpy_u8_i64,  u8,  i64);
 define_nearest_numpy!(nearest_numpy_u8_i32,  u8,  i32);
 define_nearest_numpy!(nearest_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_nearest_index_numpy {
+    ($cls:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyclass(module = "ruranges", frozen)]
+        #[allow(non_camel_case_types)]
+        pub struct $cls {
+            index: NearestIndex<$chr_ty, $pos_ty>,
+        }
+
+        #[pymethods]
+        impl $cls {
+            #[new]
+            fn new(
+                chrs2:   PyReadonlyArray1<$chr_ty>,
+                starts2: PyReadonlyArray1<$pos_ty>,
+                ends2:   PyReadonlyArray1<$pos_ty>,
+            ) -> PyResult<Self> {
+                check_set_lengths("2", chrs2.len()?, starts2.len()?, ends2.len()?)
+                    .map_err(PyValueError::new_err)?;
+                Ok(Self {
+                    index: NearestIndex::new(
+                        chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                    ),
+                })
+            }
+
+            #[pyo3(signature = (
+                chrs, starts, ends,
+                slack = 0,
+                k = 1,
+                include_overlaps = true,
+                direction = "any"
+            ))]
+            #[allow(clippy::too_many_arguments)]
+            fn query(
+                &self,
+                py: Python<'_>,
+                chrs:   PyReadonlyArray1<$chr_ty>,
+                starts: PyReadonlyArray1<$pos_ty>,
+                ends:   PyReadonlyArray1<$pos_ty>,
+                slack: $pos_ty,
+                k: usize,
+                include_overlaps: bool,
+                direction: &str,
+            ) -> PyResult<(Py<PyArray1<u32>>,
+                           Py<PyArray1<u32>>,
+                           Py<PyArray1<$pos_ty>>)> {
+                if Direction::from_str(direction).is_err() {
+                    return Err(PyValueError::new_err(
+                        "direction must be \"forward\", \"backward\" or \"any\"",
+                    ));
+                }
+                check_set_lengths("", chrs.len()?, starts.len()?, ends.len()?)
+                    .map_err(PyValueError::new_err)?;
+                let (idx1, idx2, dist) = self.index.query(
+                    chrs.as_slice()?, starts.as_slice()?, ends.as_slice()?,
+                    slack, k, include_overlaps, direction,
+                );
+
+                Ok((
+                    idx1.into_pyarray(py).to_owned().into(),
+                    idx2.into_pyarray(py).to_owned().into(),
+                    dist.into_pyarray(py).to_owned().into(),
+                ))
+            }
+        }
+    };
+}
+
+define_nearest_index_numpy!(NearestIndex_u64_i64, u64, i64);
+define_nearest_index_numpy!(NearestIndex_u32_i64, u32, i64);
+define_nearest_index_numpy!(NearestIndex_u32_i32, u32, i32);
+define_nearest_index_numpy!(NearestIndex_u32_i16, u32, i16);
+define_nearest_index_numpy!(NearestIndex_u16_i64, u16, i64);
+define_nearest_index_numpy!(NearestIndex_u16_i32, u16, i32);
+define_nearest_index_numpy!(NearestIndex_u16_i16, u16, i16);
+define_nearest_index_numpy!(NearestIndex_u8_i64,  u8,  i64);
+define_nearest_index_numpy!(NearestIndex_u8_i32,  u8,  i32);
+define_nearest_index_numpy!(NearestIndex_u8_i16,  u8,  i16);
@@ -1,13 +1,99 @@
-use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use std::str::FromStr;
 
-use crate::overlaps::overlaps;
+use numpy::ndarray::Array2;
+use numpy::{Element, IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 
+use crate::helpers::{check_pair_lengths, check_set_lengths, check_strands};
+use crate::overlaps::{
+    annotate_overlaps, contained_overlaps, intersection_size, overlap_count_matrix, OverlapChunks, overlap_matrix, overlaps, overlaps_row_slack, overlaps_within_group,
+    pairs_within, point_overlaps, query_single_overlap,
+};
+use crate::ruranges_structs::{AnnotateType, ContainmentType, OverlapSortBy, OverlapType, StrandMode};
+
+
+type StrandSlices<'a> = Option<(&'a [bool], &'a [bool])>;
+
+/// `strands`/`strands2` of an overlap binding as slices, after checking them
+/// against `strand_mode` and the lengths of both sets.
+pub(crate) fn strand_slices<'a>(
+    strand_mode: &str,
+    strands: Option<&'a PyReadonlyArray1<bool>>,
+    strands2: Option<&'a PyReadonlyArray1<bool>>,
+    len: usize,
+    len2: usize,
+) -> PyResult<(StrandMode, StrandSlices<'a>)> {
+    let slices = match (strands, strands2) {
+        (Some(s1), Some(s2)) => Some((s1.as_slice()?, s2.as_slice()?)),
+        (None, None) => None,
+        _ => return Err(PyValueError::new_err("strands and strands2 must be given together")),
+    };
+    let mode = check_strands(strand_mode, slices, len, len2).map_err(PyValueError::new_err)?;
+    Ok((mode, slices))
+}
+
+/// The per-row `slacks` of an overlap binding, checked to have one entry
+/// per set-1 interval.
+pub(crate) fn row_slacks<'a, T: Element>(
+    slacks: Option<&'a PyReadonlyArray1<T>>,
+    len: usize,
+) -> PyResult<Option<&'a [T]>> {
+    let Some(slacks) = slacks else { return Ok(None) };
+    let slacks = slacks.as_slice()?;
+    if slacks.len() != len {
+        return Err(PyValueError::new_err("slack array must have the same length as starts"));
+    }
+    Ok(Some(slacks))
+}
+
+/// Optional filters of `chromsweep_numpy`, passed as keyword arguments;
+/// `None` values keep the default.
+struct OverlapOptions<'py, T: Element> {
+    slacks: Option<PyReadonlyArray1<'py, T>>,
+    max_per_query: Option<usize>,
+    min_overlap_bp: Option<T>,
+    strand_mode: String,
+    strands: Option<PyReadonlyArray1<'py, bool>>,
+    strands2: Option<PyReadonlyArray1<'py, bool>>,
+}
+
+impl<'py, T: Element + FromPyObject<'py>> OverlapOptions<'py, T> {
+    fn from_kwargs(kwargs: Option<&Bound<'py, PyDict>>) -> PyResult<Self> {
+        let mut options = OverlapOptions {
+            slacks: None,
+            max_per_query: None,
+            min_overlap_bp: None,
+            strand_mode: "any".to_string(),
+            strands: None,
+            strands2: None,
+        };
+        for (key, value) in kwargs.into_iter().flat_map(|kwargs| kwargs.iter()) {
+            if value.is_none() {
+                continue;
+            }
+            match key.extract::<String>()?.as_str() {
+                "slacks" => options.slacks = Some(value.extract()?),
+                "max_per_query" => options.max_per_query = Some(value.extract()?),
+                "min_overlap_bp" => options.min_overlap_bp = Some(value.extract()?),
+                "strand_mode" => options.strand_mode = value.extract()?,
+                "strands" => options.strands = Some(value.extract()?),
+                "strands2" => options.strands2 = Some(value.extract()?),
+                other => return Err(PyValueError::new_err(format!("unexpected keyword argument {other:?}"))),
+            }
+        }
+        Ok(options)
+    }
+}
 
 macro_rules! define_chromsweep_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        /// Keyword options: `slacks`, `max_per_query`, `min_overlap_bp`,
+        /// `strand_mode`, `strands` and `strands2`, as in `overlaps`.
         #[pyfunction]
-        #[allow(non_snake_case)]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack, overlap_type, sort_by, contained, **options))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
         pub fn $fname(
             py: Python,
             chrs: PyReadonlyArray1<$chr_ty>,
@@ -18,28 +104,44 @@ macro_rules! define_chromsweep_numpy {
             ends2: PyReadonlyArray1<$pos_ty>,
             slack: $pos_ty,
             overlap_type: &str,
-            sort_output: bool,
+            sort_by: &str,
             contained: bool,
+            options: Option<&Bound<'_, PyDict>>,
         ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            OverlapType::from_str(overlap_type).map_err(PyValueError::new_err)?;
+            OverlapSortBy::from_str(sort_by).map_err(PyValueError::new_err)?;
+            let options = OverlapOptions::<$pos_ty>::from_kwargs(options)?;
+            check_pair_lengths(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
             let chrs_slice = chrs.as_slice()?;
             let starts_slice = starts.as_slice()?;
             let ends_slice = ends.as_slice()?;
             let chrs_slice2 = chrs2.as_slice()?;
             let starts_slice2 = starts2.as_slice()?;
             let ends_slice2 = ends2.as_slice()?;
+            let strand_mode = options.strand_mode.as_str();
+            let (_, strand_slices) = strand_slices(
+                strand_mode, options.strands.as_ref(), options.strands2.as_ref(),
+                starts_slice.len(), starts_slice2.len(),
+            )?;
 
-            let (idx1, idx2) = overlaps(
-                chrs_slice,
-                starts_slice,
-                ends_slice,
-                chrs_slice2,
-                starts_slice2,
-                ends_slice2,
-                slack,
-                overlap_type,
-                sort_output,
-                contained,
-            );
+            let (idx1, idx2) = match row_slacks(options.slacks.as_ref(), starts_slice.len())? {
+                Some(slacks) => overlaps_row_slack(
+                    chrs_slice, starts_slice, ends_slice, slacks,
+                    chrs_slice2, starts_slice2, ends_slice2,
+                    overlap_type, sort_by, contained, options.max_per_query, options.min_overlap_bp,
+                    strand_mode, strand_slices,
+                ),
+                None => overlaps(
+                    chrs_slice, starts_slice, ends_slice,
+                    chrs_slice2, starts_slice2, ends_slice2,
+                    slack, overlap_type, sort_by, contained, options.max_per_query, options.min_overlap_bp,
+                    strand_mode, strand_slices,
+                ),
+            };
             Ok((
                 idx1.into_pyarray(py).to_owned().into(),
                 idx2.into_pyarray(py).to_owned().into(),
@@ -58,3 +160,462 @@ define_chromsweep_numpy!(chromsweep_numpy_u16_i16, u16, i16);
 define_chromsweep_numpy!(chromsweep_numpy_u8_i64,  u8,  i64);
 define_chromsweep_numpy!(chromsweep_numpy_u8_i32,  u8,  i32);
 define_chromsweep_numpy!(chromsweep_numpy_u8_i16,  u8,  i16);
+// float coordinates, for non-genomic data
+define_chromsweep_numpy!(chromsweep_numpy_u32_f64, u32, f64);
+define_chromsweep_numpy!(chromsweep_numpy_u32_f32, u32, f32);
+define_chromsweep_numpy!(chromsweep_numpy_u64_f64, u64, f64);
+
+
+macro_rules! define_overlap_matrix_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, slack = 0))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            slack: $pos_ty,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            let (rows, cols) = overlap_matrix(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                slack,
+            );
+            Ok((
+                rows.into_pyarray(py).to_owned().into(),
+                cols.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    }
+}
+
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u64_i64, u64, i64);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u32_i64, u32, i64);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u32_i32, u32, i32);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u32_i16, u32, i16);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u16_i64, u16, i64);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u16_i32, u16, i32);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u16_i16, u16, i16);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u8_i64,  u8,  i64);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u8_i32,  u8,  i32);
+define_overlap_matrix_numpy!(overlap_matrix_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_overlap_matrix_dense_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            chrs2: PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2: PyReadonlyArray1<$pos_ty>,
+            slack: $pos_ty,
+        ) -> PyResult<Py<PyArray2<u32>>> {
+            let chrs_slice = chrs.as_slice()?;
+            let chrs_slice2 = chrs2.as_slice()?;
+            let matrix = overlap_count_matrix(
+                chrs_slice,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                chrs_slice2,
+                starts2.as_slice()?,
+                ends2.as_slice()?,
+                slack,
+            );
+            let matrix = Array2::from_shape_vec((chrs_slice.len(), chrs_slice2.len()), matrix)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            Ok(matrix.into_pyarray(py).to_owned().into())
+        }
+    }
+}
+
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u64_i64, u64, i64);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u32_i64, u32, i64);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u32_i32, u32, i32);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u32_i16, u32, i16);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u16_i64, u16, i64);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u16_i32, u16, i32);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u16_i16, u16, i16);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u8_i64,  u8,  i64);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u8_i32,  u8,  i32);
+define_overlap_matrix_dense_numpy!(overlap_matrix_dense_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_pairs_within_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, max_gap, max_pairs = None))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            max_gap: $pos_ty,
+            max_pairs: Option<usize>,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>, Py<PyArray1<$pos_ty>>)> {
+            let (idx, idx2, gaps) = pairs_within(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                max_gap,
+                max_pairs,
+            );
+            Ok((
+                idx.into_pyarray(py).to_owned().into(),
+                idx2.into_pyarray(py).to_owned().into(),
+                gaps.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    }
+}
+
+define_pairs_within_numpy!(pairs_within_numpy_u64_i64, u64, i64);
+define_pairs_within_numpy!(pairs_within_numpy_u32_i64, u32, i64);
+define_pairs_within_numpy!(pairs_within_numpy_u32_i32, u32, i32);
+define_pairs_within_numpy!(pairs_within_numpy_u32_i16, u32, i16);
+define_pairs_within_numpy!(pairs_within_numpy_u16_i64, u16, i64);
+define_pairs_within_numpy!(pairs_within_numpy_u16_i32, u16, i32);
+define_pairs_within_numpy!(pairs_within_numpy_u16_i16, u16, i16);
+define_pairs_within_numpy!(pairs_within_numpy_u8_i64,  u8,  i64);
+define_pairs_within_numpy!(pairs_within_numpy_u8_i32,  u8,  i32);
+define_pairs_within_numpy!(pairs_within_numpy_u8_i16,  u8,  i16);
+
+// Takes the same arguments as the other two-set kernels so it can go through
+// `_dispatch_binary`; the point set is passed with `ends == positions`.
+macro_rules! define_point_overlaps_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            positions: PyReadonlyArray1<$pos_ty>,
+            _ends: PyReadonlyArray1<$pos_ty>,
+            chrs2: PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2: PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            let (idx, idx2) = point_overlaps(
+                chrs.as_slice()?,
+                positions.as_slice()?,
+                chrs2.as_slice()?,
+                starts2.as_slice()?,
+                ends2.as_slice()?,
+            );
+            Ok((
+                idx.into_pyarray(py).to_owned().into(),
+                idx2.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    }
+}
+
+define_point_overlaps_numpy!(point_overlaps_numpy_u64_i64, u64, i64);
+define_point_overlaps_numpy!(point_overlaps_numpy_u32_i64, u32, i64);
+define_point_overlaps_numpy!(point_overlaps_numpy_u32_i32, u32, i32);
+define_point_overlaps_numpy!(point_overlaps_numpy_u32_i16, u32, i16);
+define_point_overlaps_numpy!(point_overlaps_numpy_u16_i64, u16, i64);
+define_point_overlaps_numpy!(point_overlaps_numpy_u16_i32, u16, i32);
+define_point_overlaps_numpy!(point_overlaps_numpy_u16_i16, u16, i16);
+define_point_overlaps_numpy!(point_overlaps_numpy_u8_i64,  u8,  i64);
+define_point_overlaps_numpy!(point_overlaps_numpy_u8_i32,  u8,  i32);
+define_point_overlaps_numpy!(point_overlaps_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_chromsweep_within_group_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack, group_ids, group_ids2))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            chrs2: PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2: PyReadonlyArray1<$pos_ty>,
+            slack: $pos_ty,
+            group_ids: PyReadonlyArray1<u32>,
+            group_ids2: PyReadonlyArray1<u32>,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            let group_ids = group_ids.as_slice()?;
+            let group_ids2 = group_ids2.as_slice()?;
+            if group_ids.len() != starts.as_slice()?.len() || group_ids2.len() != starts2.as_slice()?.len() {
+                return Err(PyValueError::new_err(
+                    "group_ids must match the lengths of the interval sets",
+                ));
+            }
+            let (idx1, idx2) = overlaps_within_group(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                group_ids,
+                chrs2.as_slice()?,
+                starts2.as_slice()?,
+                ends2.as_slice()?,
+                group_ids2,
+                slack,
+            );
+            Ok((
+                idx1.into_pyarray(py).to_owned().into(),
+                idx2.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u64_i64, u64, i64);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u32_i64, u32, i64);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u32_i32, u32, i32);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u32_i16, u32, i16);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u16_i64, u16, i64);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u16_i32, u16, i32);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u16_i16, u16, i16);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u8_i64,  u8,  i64);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u8_i32,  u8,  i32);
+define_chromsweep_within_group_numpy!(chromsweep_within_group_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_query_single_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (db_chrs, db_starts, db_ends, query_chr, query_start, query_end, slack = 0))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python,
+            db_chrs: PyReadonlyArray1<$chr_ty>,
+            db_starts: PyReadonlyArray1<$pos_ty>,
+            db_ends: PyReadonlyArray1<$pos_ty>,
+            query_chr: $chr_ty,
+            query_start: $pos_ty,
+            query_end: $pos_ty,
+            slack: $pos_ty,
+        ) -> PyResult<Py<PyArray1<u32>>> {
+            check_set_lengths("", db_chrs.len()?, db_starts.len()?, db_ends.len()?)
+                .map_err(PyValueError::new_err)?;
+            let hits = query_single_overlap(
+                db_chrs.as_slice()?,
+                db_starts.as_slice()?,
+                db_ends.as_slice()?,
+                query_chr,
+                query_start,
+                query_end,
+                slack,
+            );
+            Ok(hits.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_query_single_numpy!(query_single_numpy_u64_i64, u64, i64);
+define_query_single_numpy!(query_single_numpy_u32_i64, u32, i64);
+define_query_single_numpy!(query_single_numpy_u32_i32, u32, i32);
+define_query_single_numpy!(query_single_numpy_u32_i16, u32, i16);
+define_query_single_numpy!(query_single_numpy_u16_i64, u16, i64);
+define_query_single_numpy!(query_single_numpy_u16_i32, u16, i32);
+define_query_single_numpy!(query_single_numpy_u16_i16, u16, i16);
+define_query_single_numpy!(query_single_numpy_u8_i64,  u8,  i64);
+define_query_single_numpy!(query_single_numpy_u8_i32,  u8,  i32);
+define_query_single_numpy!(query_single_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_contained_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack = 0, how = "a_in_b"))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            chrs2: PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2: PyReadonlyArray1<$pos_ty>,
+            slack: $pos_ty,
+            how: &str,
+        ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            if ContainmentType::from_str(how).is_err() {
+                return Err(PyValueError::new_err("how must be \"a_in_b\", \"b_in_a\" or \"either\""));
+            }
+            let (idx1, idx2) = contained_overlaps(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                chrs2.as_slice()?,
+                starts2.as_slice()?,
+                ends2.as_slice()?,
+                slack,
+                how,
+            );
+            Ok((
+                idx1.into_pyarray(py).to_owned().into(),
+                idx2.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_contained_numpy!(contained_numpy_u64_i64, u64, i64);
+define_contained_numpy!(contained_numpy_u32_i64, u32, i64);
+define_contained_numpy!(contained_numpy_u32_i32, u32, i32);
+define_contained_numpy!(contained_numpy_u32_i16, u32, i16);
+define_contained_numpy!(contained_numpy_u16_i64, u16, i64);
+define_contained_numpy!(contained_numpy_u16_i32, u16, i32);
+define_contained_numpy!(contained_numpy_u16_i16, u16, i16);
+define_contained_numpy!(contained_numpy_u8_i64,  u8,  i64);
+define_contained_numpy!(contained_numpy_u8_i32,  u8,  i32);
+define_contained_numpy!(contained_numpy_u8_i16,  u8,  i16);
+
+// The total can exceed any single coordinate, so only i64 positions are
+// instantiated.
+macro_rules! define_intersection_size_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<$pos_ty> {
+            Ok(intersection_size(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            ))
+        }
+    };
+}
+
+define_intersection_size_numpy!(intersection_size_numpy_u64_i64, u64, i64);
+define_intersection_size_numpy!(intersection_size_numpy_u32_i64, u32, i64);
+define_intersection_size_numpy!(intersection_size_numpy_u16_i64, u16, i64);
+define_intersection_size_numpy!(intersection_size_numpy_u8_i64,  u8,  i64);
+
+// Queries without an overlapping annotation get `u32::MAX`.
+macro_rules! define_annotate_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, labels2, slack = 0, overlap_type = "first"))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            chrs2: PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2: PyReadonlyArray1<$pos_ty>,
+            labels2: PyReadonlyArray1<u32>,
+            slack: $pos_ty,
+            overlap_type: &str,
+        ) -> PyResult<Py<PyArray1<u32>>> {
+            AnnotateType::from_str(overlap_type).map_err(PyValueError::new_err)?;
+            check_pair_lengths(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
+            if labels2.as_slice()?.len() != chrs2.as_slice()?.len() {
+                return Err(PyValueError::new_err("labels2 must have the same length as starts2"));
+            }
+
+            let labels: Vec<u32> = annotate_overlaps(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                labels2.as_slice()?, slack, overlap_type,
+            )
+            .into_iter()
+            .map(|label| label.unwrap_or(u32::MAX))
+            .collect();
+            Ok(labels.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_annotate_numpy!(annotate_numpy_u64_i64, u64, i64);
+define_annotate_numpy!(annotate_numpy_u32_i64, u32, i64);
+define_annotate_numpy!(annotate_numpy_u32_i32, u32, i32);
+define_annotate_numpy!(annotate_numpy_u32_i16, u32, i16);
+define_annotate_numpy!(annotate_numpy_u16_i64, u16, i64);
+define_annotate_numpy!(annotate_numpy_u16_i32, u16, i32);
+define_annotate_numpy!(annotate_numpy_u16_i16, u16, i16);
+define_annotate_numpy!(annotate_numpy_u8_i64,  u8,  i64);
+define_annotate_numpy!(annotate_numpy_u8_i32,  u8,  i32);
+define_annotate_numpy!(annotate_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_overlap_chunks_numpy {
+    ($cls:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyclass(module = "ruranges")]
+        #[allow(non_camel_case_types)]
+        pub struct $cls {
+            chunks: OverlapChunks<$chr_ty, $pos_ty>,
+        }
+
+        #[pymethods]
+        impl $cls {
+            #[new]
+            #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack = 0, chunk_size = 1_000_000))]
+            #[allow(clippy::too_many_arguments)]
+            fn new(
+                chrs:    PyReadonlyArray1<$chr_ty>,
+                starts:  PyReadonlyArray1<$pos_ty>,
+                ends:    PyReadonlyArray1<$pos_ty>,
+                chrs2:   PyReadonlyArray1<$chr_ty>,
+                starts2: PyReadonlyArray1<$pos_ty>,
+                ends2:   PyReadonlyArray1<$pos_ty>,
+                slack: $pos_ty,
+                chunk_size: usize,
+            ) -> PyResult<Self> {
+                if chunk_size == 0 {
+                    return Err(PyValueError::new_err("chunk_size must be positive"));
+                }
+                check_pair_lengths(
+                    chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                    chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                )
+                .map_err(PyValueError::new_err)?;
+                Ok(Self {
+                    chunks: OverlapChunks::new(
+                        chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                        chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+                        slack, chunk_size,
+                    ),
+                })
+            }
+
+            fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+                slf
+            }
+
+            fn __next__(
+                mut slf: PyRefMut<'_, Self>,
+                py: Python<'_>,
+            ) -> Option<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+                let (idx1, idx2): (Vec<u32>, Vec<u32>) =
+                    slf.chunks.next()?.into_iter().map(|p| (p.idx, p.idx2)).unzip();
+                Some((
+                    idx1.into_pyarray(py).to_owned().into(),
+                    idx2.into_pyarray(py).to_owned().into(),
+                ))
+            }
+        }
+    };
+}
+
+define_overlap_chunks_numpy!(OverlapChunks_u64_i64, u64, i64);
+define_overlap_chunks_numpy!(OverlapChunks_u32_i64, u32, i64);
+define_overlap_chunks_numpy!(OverlapChunks_u32_i32, u32, i32);
+define_overlap_chunks_numpy!(OverlapChunks_u32_i16, u32, i16);
+define_overlap_chunks_numpy!(OverlapChunks_u16_i64, u16, i64);
+define_overlap_chunks_numpy!(OverlapChunks_u16_i32, u16, i32);
+define_overlap_chunks_numpy!(OverlapChunks_u16_i16, u16, i16);
+define_overlap_chunks_numpy!(OverlapChunks_u8_i64,  u8,  i64);
+define_overlap_chunks_numpy!(OverlapChunks_u8_i32,  u8,  i32);
+define_overlap_chunks_numpy!(OverlapChunks_u8_i16,  u8,  i16);
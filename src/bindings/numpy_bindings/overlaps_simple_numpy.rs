@@ -1,6 +1,8 @@
 
+use std::str::FromStr;
+
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 use crate::ruranges_structs::OverlapType;
 
 use crate::overlaps_simple::sweep_line_overlaps; // adjust module path if needed
@@ -22,6 +24,7 @@ macro_rules! define_sweepline_numpy {
             contained: bool,
             no_checks: bool,
         ) -> PyResult<(Py<PyArray1<u32>>, Py<PyArray1<u32>>)> {
+            OverlapType::from_str(overlap_type).map_err(PyValueError::new_err)?;
             let chrs_slice = chrs.as_slice()?;
             let starts_slice = starts.as_slice()?;
             let ends_slice = ends.as_slice()?;
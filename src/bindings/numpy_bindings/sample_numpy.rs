@@ -0,0 +1,100 @@
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
+
+use super::genome_bounds_numpy::chrom_lens_map;
+use crate::sample::{permute_starts, sample_random_intervals};
+
+macro_rules! define_sample_random_intervals_numpy {
+    ($fname:ident, $grp_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrom_ids, chrom_lengths, n, min_len, max_len, seed = 0))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrom_ids:     PyReadonlyArray1<$grp_ty>,
+            chrom_lengths: PyReadonlyArray1<$pos_ty>,
+            n:       usize,
+            min_len: $pos_ty,
+            max_len: $pos_ty,
+            seed:    u64,
+        ) -> PyResult<(
+            Py<PyArray1<$grp_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+        )> {
+            let lens_map = chrom_lens_map(&chrom_ids, &chrom_lengths)?;
+
+            let data = sample_random_intervals(
+                chrom_ids.as_slice()?,
+                &lens_map,
+                n,
+                min_len,
+                max_len,
+                seed,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            Ok((
+                data.chroms.into_pyarray(py).to_owned().into(),
+                data.starts.into_pyarray(py).to_owned().into(),
+                data.ends.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+macro_rules! define_permute_starts_numpy {
+    ($fname:ident, $grp_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (groups, starts, ends, chrom_ids, chrom_lengths, seed = 0))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python<'_>,
+            groups:        PyReadonlyArray1<$grp_ty>,
+            starts:        PyReadonlyArray1<$pos_ty>,
+            ends:          PyReadonlyArray1<$pos_ty>,
+            chrom_ids:     PyReadonlyArray1<$grp_ty>,
+            chrom_lengths: PyReadonlyArray1<$pos_ty>,
+            seed:          u64,
+        ) -> PyResult<(Py<PyArray1<$pos_ty>>, Py<PyArray1<$pos_ty>>)> {
+            let lens_map = chrom_lens_map(&chrom_ids, &chrom_lengths)?;
+
+            let (new_starts, new_ends) = permute_starts(
+                groups.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                &lens_map,
+                seed,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            Ok((
+                new_starts.into_pyarray(py).to_owned().into(),
+                new_ends.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u64_i64, u64, i64);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u32_i64, u32, i64);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u32_i32, u32, i32);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u32_i16, u32, i16);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u16_i64, u16, i64);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u16_i32, u16, i32);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u16_i16, u16, i16);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u8_i64,  u8,  i64);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u8_i32,  u8,  i32);
+define_sample_random_intervals_numpy!(sample_random_intervals_numpy_u8_i16,  u8,  i16);
+
+define_permute_starts_numpy!(permute_starts_numpy_u64_i64, u64, i64);
+define_permute_starts_numpy!(permute_starts_numpy_u32_i64, u32, i64);
+define_permute_starts_numpy!(permute_starts_numpy_u32_i32, u32, i32);
+define_permute_starts_numpy!(permute_starts_numpy_u32_i16, u32, i16);
+define_permute_starts_numpy!(permute_starts_numpy_u16_i64, u16, i64);
+define_permute_starts_numpy!(permute_starts_numpy_u16_i32, u16, i32);
+define_permute_starts_numpy!(permute_starts_numpy_u16_i16, u16, i16);
+define_permute_starts_numpy!(permute_starts_numpy_u8_i64,  u8,  i64);
+define_permute_starts_numpy!(permute_starts_numpy_u8_i32,  u8,  i32);
+define_permute_starts_numpy!(permute_starts_numpy_u8_i16,  u8,  i16);
@@ -1,21 +1,38 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 
 use crate::sorts;
 
 macro_rules! define_sort_intervals_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
         #[pyfunction]
-        #[pyo3(signature = (chrs, starts, ends, sort_reverse_direction = None))]
+        #[pyo3(signature = (
+            chrs,
+            starts,
+            ends,
+            sort_reverse_direction = None,
+            descending = false,
+            secondary = "end"
+        ))]
         #[allow(non_snake_case)]
         pub fn $fname(
             chrs: PyReadonlyArray1<$chr_ty>,
             starts: PyReadonlyArray1<$pos_ty>,
             ends: PyReadonlyArray1<$pos_ty>,
             sort_reverse_direction: Option<PyReadonlyArray1<bool>>,
+            descending: bool,
+            secondary: &str,
             py: Python<'_>,
         ) -> PyResult<Py<PyArray1<u32>>> {
-            let idx = sorts::sort_order_idx(
+            if secondary.parse::<sorts::SecondarySortKey>().is_err() {
+                return Err(PyValueError::new_err(
+                    "secondary must be either \"end\" or \"length\"",
+                ));
+            }
+            let idx = sorts::sort_order_idx_by(
                 chrs.as_slice()?,
                 starts.as_slice()?,
                 ends.as_slice()?,
@@ -23,6 +40,8 @@ macro_rules! define_sort_intervals_numpy {
                     Some(arr) => Some(arr.as_slice()?),
                     None => None,
                 },
+                descending,
+                secondary,
             );
             Ok(idx.into_pyarray(py).to_owned().into())
         }
@@ -46,6 +65,58 @@ macro_rules! define_sort_groups_numpy {
     };
 }
 
+macro_rules! define_sort_intervals_apply_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (
+            chrs,
+            starts,
+            ends,
+            sort_reverse_direction = None,
+            descending = false,
+            secondary = "end"
+        ))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            sort_reverse_direction: Option<PyReadonlyArray1<bool>>,
+            descending: bool,
+            secondary: &str,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<$chr_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<$pos_ty>>,
+            Py<PyArray1<u32>>,
+        )> {
+            if secondary.parse::<sorts::SecondarySortKey>().is_err() {
+                return Err(PyValueError::new_err(
+                    "secondary must be either \"end\" or \"length\"",
+                ));
+            }
+            let (s_chrs, s_starts, s_ends, idx) = sorts::sort_intervals_apply(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                match &sort_reverse_direction {
+                    Some(arr) => Some(arr.as_slice()?),
+                    None => None,
+                },
+                descending,
+                secondary,
+            );
+            Ok((
+                s_chrs   .into_pyarray(py).to_owned().into(),
+                s_starts .into_pyarray(py).to_owned().into(),
+                s_ends   .into_pyarray(py).to_owned().into(),
+                idx      .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
 define_sort_intervals_numpy!(sort_intervals_numpy_u64_i64, u64, i64);
 define_sort_intervals_numpy!(sort_intervals_numpy_u32_i64, u32, i64);
 define_sort_intervals_numpy!(sort_intervals_numpy_u32_i32, u32, i32);
@@ -60,4 +131,125 @@ define_sort_intervals_numpy!(sort_intervals_numpy_u8_i16,  u8,  i16);
 define_sort_groups_numpy!(sort_groups_numpy_u64, u64);
 define_sort_groups_numpy!(sort_groups_numpy_u32, u32);
 define_sort_groups_numpy!(sort_groups_numpy_u16, u16);
-define_sort_groups_numpy!(sort_groups_numpy_u8,  u8);
\ No newline at end of file
+define_sort_groups_numpy!(sort_groups_numpy_u8,  u8);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u64_i64, u64, i64);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u32_i64, u32, i64);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u32_i32, u32, i32);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u32_i16, u32, i16);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u16_i64, u16, i64);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u16_i32, u16, i32);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u16_i16, u16, i16);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u8_i64,  u8,  i64);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u8_i32,  u8,  i32);
+define_sort_intervals_apply_numpy!(sort_intervals_apply_numpy_u8_i16,  u8,  i16);
+
+/// `(chrs, positions, is_start, first_set, idx)`
+type EventColumns<C, T> = (
+    Py<PyArray1<C>>,
+    Py<PyArray1<T>>,
+    Py<PyArray1<bool>>,
+    Py<PyArray1<bool>>,
+    Py<PyArray1<u32>>,
+);
+
+// Debugging aid: the two-set event stream in the order the overlap sweeps
+// consume it, one column per `GenericEvent` field.
+macro_rules! define_dump_events_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, slack = 0))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs: PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+            chrs2: PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2: PyReadonlyArray1<$pos_ty>,
+            slack: $pos_ty,
+        ) -> PyResult<EventColumns<$chr_ty, $pos_ty>> {
+            let events = sorts::build_sorted_events_points_last(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                chrs2.as_slice()?,
+                starts2.as_slice()?,
+                ends2.as_slice()?,
+                slack,
+            );
+
+            let ev_chrs: Vec<$chr_ty> = events.iter().map(|e| e.chr).collect();
+            let ev_pos: Vec<$pos_ty> = events.iter().map(|e| e.pos).collect();
+            let ev_is_start: Vec<bool> = events.iter().map(|e| e.is_start).collect();
+            let ev_first_set: Vec<bool> = events.iter().map(|e| e.first_set).collect();
+            let ev_idx: Vec<u32> = events.iter().map(|e| e.idx).collect();
+
+            Ok((
+                ev_chrs.into_pyarray(py).to_owned().into(),
+                ev_pos.into_pyarray(py).to_owned().into(),
+                ev_is_start.into_pyarray(py).to_owned().into(),
+                ev_first_set.into_pyarray(py).to_owned().into(),
+                ev_idx.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_dump_events_numpy!(dump_events_numpy_u64_i64, u64, i64);
+define_dump_events_numpy!(dump_events_numpy_u32_i64, u32, i64);
+define_dump_events_numpy!(dump_events_numpy_u32_i32, u32, i32);
+define_dump_events_numpy!(dump_events_numpy_u32_i16, u32, i16);
+define_dump_events_numpy!(dump_events_numpy_u16_i64, u16, i64);
+define_dump_events_numpy!(dump_events_numpy_u16_i32, u16, i32);
+define_dump_events_numpy!(dump_events_numpy_u16_i16, u16, i16);
+define_dump_events_numpy!(dump_events_numpy_u8_i64,  u8,  i64);
+define_dump_events_numpy!(dump_events_numpy_u8_i32,  u8,  i32);
+define_dump_events_numpy!(dump_events_numpy_u8_i16,  u8,  i16);
+
+/// Sorts `(chrs, starts)` into start events and writes them to `path`, so
+/// other processes can load the sorted order instead of sorting again.
+#[pyfunction]
+pub fn save_sorted_index_numpy(
+    path: &str,
+    chrs: PyReadonlyArray1<u32>,
+    starts: PyReadonlyArray1<i64>,
+) -> PyResult<()> {
+    let chrs = chrs.as_slice()?;
+    let starts = starts.as_slice()?;
+    if chrs.len() != starts.len() {
+        return Err(PyValueError::new_err("chrs and starts must have the same length"));
+    }
+    let events = sorts::build_sorted_events_single_collection_separate_outputs(chrs, starts, 0);
+
+    let file = File::create(path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    sorts::serialize_sorted_events(&events, &mut writer)
+        .and_then(|_| writer.flush())
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// `(chrs, positions, idxs)`
+type SortedIndexArrays = (Py<PyArray1<u32>>, Py<PyArray1<i64>>, Py<PyArray1<u32>>);
+
+/// The events saved by [`save_sorted_index_numpy`], in sorted order.
+#[pyfunction]
+pub fn load_sorted_index_numpy(py: Python<'_>, path: &str) -> PyResult<SortedIndexArrays> {
+    let file = File::open(path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let events = sorts::deserialize_sorted_events::<u32, i64>(&mut BufReader::new(file))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    let mut chrs = Vec::with_capacity(events.len());
+    let mut positions = Vec::with_capacity(events.len());
+    let mut idxs = Vec::with_capacity(events.len());
+    for e in events {
+        chrs.push(e.chr);
+        positions.push(e.pos);
+        idxs.push(e.idx);
+    }
+    Ok((
+        chrs.into_pyarray(py).to_owned().into(),
+        positions.into_pyarray(py).to_owned().into(),
+        idxs.into_pyarray(py).to_owned().into(),
+    ))
+}
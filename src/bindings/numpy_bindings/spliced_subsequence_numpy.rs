@@ -1,7 +1,8 @@
 use pyo3::prelude::*;
 use numpy::{IntoPyArray, PyReadonlyArray1, PyArray1};
 
-use crate::spliced_subsequence::{spliced_subseq, spliced_subseq_multi};
+use crate::helpers::check_set_lengths;
+use crate::spliced_subsequence::{spliced_length, spliced_subseq, spliced_subseq_multi, validate_exon_order};
 
 /// -------------------------------------------------------------------------
 /// single-slice wrappers
@@ -16,7 +17,8 @@ macro_rules! define_spliced_subsequence_numpy {
             strand_flags,
             start,
             end     = None,
-            force_plus_strand = false
+            force_plus_strand = false,
+            validate_exons = false
         ))]
         #[allow(non_snake_case)]
         pub fn $fname(
@@ -27,6 +29,7 @@ macro_rules! define_spliced_subsequence_numpy {
             start:              $pos_ty,
             end:                Option<$pos_ty>,
             force_plus_strand:  bool,
+            validate_exons:     bool,
             py: Python<'_>,
         ) -> PyResult<(
             Py<PyArray1<u32>>,      // indices
@@ -34,6 +37,15 @@ macro_rules! define_spliced_subsequence_numpy {
             Py<PyArray1<$pos_ty>>,  // new ends
             Py<PyArray1<bool>>,     // strand  True='+', False='-'
         )> {
+            if validate_exons {
+                validate_exon_order(
+                    chrs.as_slice()?,
+                    starts.as_slice()?,
+                    ends.as_slice()?,
+                    strand_flags.as_slice()?,
+                )
+                .map_err(pyo3::exceptions::PyValueError::new_err)?;
+            }
             let (idx, new_starts, new_ends, strands) = spliced_subseq(
                 chrs.as_slice()?,
                 starts.as_slice()?,
@@ -76,7 +88,8 @@ macro_rules! define_spliced_subsequence_multi_numpy {
             strand_flags,
             slice_starts,
             slice_ends,
-            force_plus_strand = false
+            force_plus_strand = false,
+            validate_exons = false
         ))]
         #[allow(non_snake_case)]
         pub fn $fname(
@@ -87,6 +100,7 @@ macro_rules! define_spliced_subsequence_multi_numpy {
             slice_starts:    PyReadonlyArray1<$pos_ty>,
             slice_ends:      PyReadonlyArray1<$pos_ty>,
             force_plus_strand: bool,
+            validate_exons:    bool,
             py: Python<'_>,
         ) -> PyResult<(
             Py<PyArray1<u32>>,
@@ -94,6 +108,15 @@ macro_rules! define_spliced_subsequence_multi_numpy {
             Py<PyArray1<$pos_ty>>,
             Py<PyArray1<bool>>,
         )> {
+            if validate_exons {
+                validate_exon_order(
+                    chrs.as_slice()?,
+                    starts.as_slice()?,
+                    ends.as_slice()?,
+                    strand_flags.as_slice()?,
+                )
+                .map_err(pyo3::exceptions::PyValueError::new_err)?;
+            }
             let ends_opt: Vec<Option<$pos_ty>> = slice_ends
                 .as_slice()?
                 .iter()
@@ -132,3 +155,43 @@ define_spliced_subsequence_multi_numpy!(spliced_subsequence_multi_numpy_u16_i16,
 define_spliced_subsequence_multi_numpy!(spliced_subsequence_multi_numpy_u8_i64,  u8,  i64);
 define_spliced_subsequence_multi_numpy!(spliced_subsequence_multi_numpy_u8_i32,  u8,  i32);
 define_spliced_subsequence_multi_numpy!(spliced_subsequence_multi_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_spliced_length_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, strand_flags))]
+        pub fn $fname(
+            chrs:         PyReadonlyArray1<$chr_ty>,
+            starts:       PyReadonlyArray1<$pos_ty>,
+            ends:         PyReadonlyArray1<$pos_ty>,
+            strand_flags: PyReadonlyArray1<bool>,
+            py: Python<'_>,
+        ) -> PyResult<Py<PyArray1<$pos_ty>>> {
+            check_set_lengths("", chrs.len()?, starts.len()?, ends.len()?)
+                .map_err(pyo3::exceptions::PyValueError::new_err)?;
+            if strand_flags.len()? != chrs.len()? {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "strand_flags must have the same length as chrs",
+                ));
+            }
+            let lengths = spliced_length(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                strand_flags.as_slice()?,
+            );
+            Ok(lengths.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_spliced_length_numpy!(spliced_length_numpy_u64_i64, u64, i64);
+define_spliced_length_numpy!(spliced_length_numpy_u32_i64, u32, i64);
+define_spliced_length_numpy!(spliced_length_numpy_u32_i32, u32, i32);
+define_spliced_length_numpy!(spliced_length_numpy_u32_i16, u32, i16);
+define_spliced_length_numpy!(spliced_length_numpy_u16_i64, u16, i64);
+define_spliced_length_numpy!(spliced_length_numpy_u16_i32, u16, i32);
+define_spliced_length_numpy!(spliced_length_numpy_u16_i16, u16, i16);
+define_spliced_length_numpy!(spliced_length_numpy_u8_i64,  u8,  i64);
+define_spliced_length_numpy!(spliced_length_numpy_u8_i32,  u8,  i32);
+define_spliced_length_numpy!(spliced_length_numpy_u8_i16,  u8,  i16);
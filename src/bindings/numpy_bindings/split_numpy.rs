@@ -1,7 +1,7 @@
 use pyo3::prelude::*;
 use numpy::{IntoPyArray, PyReadonlyArray1, PyArray1};
 
-use crate::split::sweep_line_split;
+use crate::split::{partition_into_layers, sweep_line_split, sweep_line_split_max_length};
 
 macro_rules! define_split_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
@@ -46,4 +46,86 @@ define_split_numpy!(split_numpy_u16_i32, u16, i32);
 define_split_numpy!(split_numpy_u16_i16, u16, i16);
 define_split_numpy!(split_numpy_u8_i64,  u8,  i64);
 define_split_numpy!(split_numpy_u8_i32,  u8,  i32);
-define_split_numpy!(split_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_split_numpy!(split_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_split_max_length_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, max_len, slack = 0, between = false))]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            max_len: $pos_ty,
+            slack:   $pos_ty,
+            between: bool,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<u32>>,      // indices
+            Py<PyArray1<$pos_ty>>,  // split starts
+            Py<PyArray1<$pos_ty>>,  // split ends
+        )> {
+            if max_len <= 0 {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "max_len must be positive",
+                ));
+            }
+            let (idx, s_starts, s_ends) = sweep_line_split_max_length(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                slack,
+                max_len,
+                between,
+            );
+            Ok((
+                idx      .into_pyarray(py).to_owned().into(),
+                s_starts .into_pyarray(py).to_owned().into(),
+                s_ends   .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_split_max_length_numpy!(split_max_length_numpy_u64_i64, u64, i64);
+define_split_max_length_numpy!(split_max_length_numpy_u32_i64, u32, i64);
+define_split_max_length_numpy!(split_max_length_numpy_u32_i32, u32, i32);
+define_split_max_length_numpy!(split_max_length_numpy_u32_i16, u32, i16);
+define_split_max_length_numpy!(split_max_length_numpy_u16_i64, u16, i64);
+define_split_max_length_numpy!(split_max_length_numpy_u16_i32, u16, i32);
+define_split_max_length_numpy!(split_max_length_numpy_u16_i16, u16, i16);
+define_split_max_length_numpy!(split_max_length_numpy_u8_i64,  u8,  i64);
+define_split_max_length_numpy!(split_max_length_numpy_u8_i32,  u8,  i32);
+define_split_max_length_numpy!(split_max_length_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_partition_into_layers_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends))]
+        pub fn $fname(
+            chrs:   PyReadonlyArray1<$chr_ty>,
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends:   PyReadonlyArray1<$pos_ty>,
+            py: Python<'_>,
+        ) -> PyResult<Py<PyArray1<u32>>> {
+            let layers = partition_into_layers(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+            );
+            Ok(layers.into_pyarray(py).to_owned().into())
+        }
+    };
+}
+
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u64_i64, u64, i64);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u32_i64, u32, i64);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u32_i32, u32, i32);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u32_i16, u32, i16);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u16_i64, u16, i64);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u16_i32, u16, i32);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u16_i16, u16, i16);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u8_i64,  u8,  i64);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u8_i32,  u8,  i32);
+define_partition_into_layers_numpy!(partition_into_layers_numpy_u8_i16,  u8,  i16);
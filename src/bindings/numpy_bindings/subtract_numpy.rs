@@ -1,7 +1,9 @@
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 
-use crate::subtract::sweep_line_subtract;
+use crate::helpers::check_pair_lengths;
+
+use crate::subtract::{sweep_line_clip_to, sweep_line_subtract, sweep_line_subtract_min_fraction};
 
 macro_rules! define_subtract_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
@@ -18,6 +20,12 @@ macro_rules! define_subtract_numpy {
         ) -> PyResult<(Py<PyArray1<u32>>,
                        Py<PyArray1<$pos_ty>>,
                        Py<PyArray1<$pos_ty>>)> {
+            check_pair_lengths(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
+
             let (idx, new_starts, new_ends) = sweep_line_subtract(
                 chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
                 chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
@@ -42,4 +50,99 @@ define_subtract_numpy!(subtract_numpy_u16_i32, u16, i32);
 define_subtract_numpy!(subtract_numpy_u16_i16, u16, i16);
 define_subtract_numpy!(subtract_numpy_u8_i64,  u8,  i64);
 define_subtract_numpy!(subtract_numpy_u8_i32,  u8,  i32);
-define_subtract_numpy!(subtract_numpy_u8_i16,  u8,  i16);
\ No newline at end of file
+define_subtract_numpy!(subtract_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_subtract_min_fraction_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, chrs2, starts2, ends2, min_fraction = 0.0))]
+        #[allow(non_snake_case, clippy::too_many_arguments)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+            min_fraction: f64,
+        ) -> PyResult<(Py<PyArray1<u32>>,
+                       Py<PyArray1<$pos_ty>>,
+                       Py<PyArray1<$pos_ty>>)> {
+            check_pair_lengths(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            let (idx, new_starts, new_ends) = sweep_line_subtract_min_fraction(
+                chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
+                chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+                min_fraction,
+            );
+
+            Ok((
+                idx        .into_pyarray(py).to_owned().into(),
+                new_starts .into_pyarray(py).to_owned().into(),
+                new_ends   .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u64_i64, u64, i64);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u32_i64, u32, i64);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u32_i32, u32, i32);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u32_i16, u32, i16);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u16_i64, u16, i64);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u16_i32, u16, i32);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u16_i16, u16, i16);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u8_i64,  u8,  i64);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u8_i32,  u8,  i32);
+define_subtract_min_fraction_numpy!(subtract_min_fraction_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_clip_to_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[allow(non_snake_case)]
+        pub fn $fname(
+            py: Python<'_>,
+            chrs:    PyReadonlyArray1<$chr_ty>,
+            starts:  PyReadonlyArray1<$pos_ty>,
+            ends:    PyReadonlyArray1<$pos_ty>,
+            chrs2:   PyReadonlyArray1<$chr_ty>,
+            starts2: PyReadonlyArray1<$pos_ty>,
+            ends2:   PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<(Py<PyArray1<u32>>,
+                       Py<PyArray1<$pos_ty>>,
+                       Py<PyArray1<$pos_ty>>)> {
+            check_pair_lengths(
+                chrs.as_slice()?,  starts.as_slice()?,  ends.as_slice()?,
+                chrs2.as_slice()?, starts2.as_slice()?, ends2.as_slice()?,
+            )
+            .map_err(PyValueError::new_err)?;
+
+            let (idx, new_starts, new_ends) = sweep_line_clip_to(
+                chrs.as_slice()?,   starts.as_slice()?,   ends.as_slice()?,
+                chrs2.as_slice()?,  starts2.as_slice()?,  ends2.as_slice()?,
+            );
+
+            Ok((
+                idx        .into_pyarray(py).to_owned().into(),
+                new_starts .into_pyarray(py).to_owned().into(),
+                new_ends   .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+define_clip_to_numpy!(clip_to_numpy_u64_i64, u64, i64);
+define_clip_to_numpy!(clip_to_numpy_u32_i64, u32, i64);
+define_clip_to_numpy!(clip_to_numpy_u32_i32, u32, i32);
+define_clip_to_numpy!(clip_to_numpy_u32_i16, u32, i16);
+define_clip_to_numpy!(clip_to_numpy_u16_i64, u16, i64);
+define_clip_to_numpy!(clip_to_numpy_u16_i32, u16, i32);
+define_clip_to_numpy!(clip_to_numpy_u16_i16, u16, i16);
+define_clip_to_numpy!(clip_to_numpy_u8_i64,  u8,  i64);
+define_clip_to_numpy!(clip_to_numpy_u8_i32,  u8,  i32);
+define_clip_to_numpy!(clip_to_numpy_u8_i16,  u8,  i16);
@@ -1,7 +1,11 @@
 use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
-use pyo3::{pyfunction, Py, PyResult, Python};
+use pyo3::{exceptions::PyValueError, pyfunction, Py, PyResult, Python};
 
-use crate::tile::tile;
+use super::genome_bounds_numpy::chrom_lens_map;
+use crate::tile::{
+    tile_aggregate_count, tile_aggregate_scores, tile_grouped_with_ids, tile_n, tile_with_interval_fraction,
+    TileAggregation,
+};
 
 
 macro_rules! define_tile_numpy {
@@ -18,9 +22,10 @@ macro_rules! define_tile_numpy {
             Py<PyArray1<usize>>,   // indices
             Py<PyArray1<$pos_ty>>, // tile starts
             Py<PyArray1<$pos_ty>>, // tile ends
-            Py<PyArray1<f64>>,     // overlap fraction
+            Py<PyArray1<f64>>,     // overlap fraction (of tile)
+            Py<PyArray1<f64>>,     // overlap fraction (of interval)
         )> {
-            let (t_starts, t_ends, idx, frac) = tile(
+            let (t_starts, t_ends, idx, frac, feature_frac) = tile_with_interval_fraction(
                 starts.as_slice()?,
                 ends.as_slice()?,
                 negative_strand.as_slice()?,
@@ -31,6 +36,7 @@ macro_rules! define_tile_numpy {
                 t_starts.into_pyarray(py).to_owned().into(),
                 t_ends  .into_pyarray(py).to_owned().into(),
                 frac    .into_pyarray(py).to_owned().into(),
+                feature_frac.into_pyarray(py).to_owned().into(),
             ))
         }
     };
@@ -39,4 +45,228 @@ macro_rules! define_tile_numpy {
 // ── concrete instantiations ────────────────────────────────────────────
 define_tile_numpy!(tile_numpy_i64, i64);
 define_tile_numpy!(tile_numpy_i32, i32);
-define_tile_numpy!(tile_numpy_i16, i16);
\ No newline at end of file
+define_tile_numpy!(tile_numpy_i16, i16);
+
+
+macro_rules! define_tile_grouped_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, negative_strand, tile_size, chrom_ids = None, chrom_lengths = None))]
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fname(
+            chrs:             PyReadonlyArray1<$chr_ty>,
+            starts:           PyReadonlyArray1<$pos_ty>,
+            ends:             PyReadonlyArray1<$pos_ty>,
+            negative_strand:  PyReadonlyArray1<bool>,
+            tile_size:        $pos_ty,
+            chrom_ids:        Option<PyReadonlyArray1<$chr_ty>>,
+            chrom_lengths:    Option<PyReadonlyArray1<$pos_ty>>,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<usize>>,   // indices
+            Py<PyArray1<$pos_ty>>, // tile starts
+            Py<PyArray1<$pos_ty>>, // tile ends
+            Py<PyArray1<f64>>,     // overlap fraction (of tile)
+            Py<PyArray1<f64>>,     // overlap fraction (of interval)
+            Py<PyArray1<$pos_ty>>, // tile id (tile_start / tile_size)
+        )> {
+            if tile_size <= 0 {
+                return Err(PyValueError::new_err("tile_size must be positive"));
+            }
+            let lens_map = match (chrom_ids, chrom_lengths) {
+                (Some(ids), Some(lens)) => Some(chrom_lens_map(&ids, &lens)?),
+                (None, None) => None,
+                _ => {
+                    return Err(PyValueError::new_err(
+                        "chrom_ids and chrom_lengths must be given together",
+                    ))
+                }
+            };
+            let (t_starts, t_ends, idx, frac, feature_frac, tile_ids) = tile_grouped_with_ids(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                negative_strand.as_slice()?,
+                tile_size,
+                lens_map.as_ref(),
+            )
+            .map_err(PyValueError::new_err)?;
+            Ok((
+                idx     .into_pyarray(py).to_owned().into(),
+                t_starts.into_pyarray(py).to_owned().into(),
+                t_ends  .into_pyarray(py).to_owned().into(),
+                frac    .into_pyarray(py).to_owned().into(),
+                feature_frac.into_pyarray(py).to_owned().into(),
+                tile_ids.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_tile_grouped_numpy!(tile_grouped_numpy_u64_i64, u64, i64);
+define_tile_grouped_numpy!(tile_grouped_numpy_u32_i64, u32, i64);
+define_tile_grouped_numpy!(tile_grouped_numpy_u32_i32, u32, i32);
+define_tile_grouped_numpy!(tile_grouped_numpy_u32_i16, u32, i16);
+define_tile_grouped_numpy!(tile_grouped_numpy_u16_i64, u16, i64);
+define_tile_grouped_numpy!(tile_grouped_numpy_u16_i32, u16, i32);
+define_tile_grouped_numpy!(tile_grouped_numpy_u16_i16, u16, i16);
+define_tile_grouped_numpy!(tile_grouped_numpy_u8_i64,  u8,  i64);
+define_tile_grouped_numpy!(tile_grouped_numpy_u8_i32,  u8,  i32);
+define_tile_grouped_numpy!(tile_grouped_numpy_u8_i16,  u8,  i16);
+
+
+macro_rules! define_tile_n_numpy {
+    ($fname:ident, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (starts, ends, negative_strand, n))]
+        pub fn $fname(
+            starts:           PyReadonlyArray1<$pos_ty>,
+            ends:             PyReadonlyArray1<$pos_ty>,
+            negative_strand:  PyReadonlyArray1<bool>,
+            n:                u32,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<usize>>,   // indices
+            Py<PyArray1<$pos_ty>>, // bin starts
+            Py<PyArray1<$pos_ty>>, // bin ends
+            Py<PyArray1<u32>>,     // bin ordinal (5'→3')
+        )> {
+            if n == 0 {
+                return Err(PyValueError::new_err("n must be positive"));
+            }
+            if <$pos_ty>::try_from(n).is_err() {
+                return Err(PyValueError::new_err(format!(
+                    "n = {} does not fit in the position dtype",
+                    n
+                )));
+            }
+            let (b_starts, b_ends, idx, ordinals) = tile_n(
+                starts.as_slice()?,
+                ends.as_slice()?,
+                negative_strand.as_slice()?,
+                n,
+            );
+            Ok((
+                idx      .into_pyarray(py).to_owned().into(),
+                b_starts .into_pyarray(py).to_owned().into(),
+                b_ends   .into_pyarray(py).to_owned().into(),
+                ordinals .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_tile_n_numpy!(tile_n_numpy_i64, i64);
+define_tile_n_numpy!(tile_n_numpy_i32, i32);
+define_tile_n_numpy!(tile_n_numpy_i16, i16);
+
+
+
+macro_rules! define_tile_aggregate_count_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, tile_size))]
+        pub fn $fname(
+            chrs:      PyReadonlyArray1<$chr_ty>,
+            starts:    PyReadonlyArray1<$pos_ty>,
+            ends:      PyReadonlyArray1<$pos_ty>,
+            tile_size: $pos_ty,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<$chr_ty>>, // tile chromosomes
+            Py<PyArray1<$pos_ty>>, // tile starts
+            Py<PyArray1<$pos_ty>>, // tile ends
+            Py<PyArray1<u32>>,     // number of overlapping intervals
+        )> {
+            if tile_size <= 0 {
+                return Err(PyValueError::new_err("tile_size must be positive"));
+            }
+            let (t_chrs, t_starts, t_ends, counts) = tile_aggregate_count(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                tile_size,
+            );
+            Ok((
+                t_chrs  .into_pyarray(py).to_owned().into(),
+                t_starts.into_pyarray(py).to_owned().into(),
+                t_ends  .into_pyarray(py).to_owned().into(),
+                counts  .into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u64_i64, u64, i64);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u32_i64, u32, i64);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u32_i32, u32, i32);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u32_i16, u32, i16);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u16_i64, u16, i64);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u16_i32, u16, i32);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u16_i16, u16, i16);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u8_i64,  u8,  i64);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u8_i32,  u8,  i32);
+define_tile_aggregate_count_numpy!(tile_aggregate_count_numpy_u8_i16,  u8,  i16);
+
+macro_rules! define_tile_scores_numpy {
+    ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
+        #[pyfunction]
+        #[pyo3(signature = (chrs, starts, ends, scores, tile_size, aggregation = "sum"))]
+        pub fn $fname(
+            chrs:        PyReadonlyArray1<$chr_ty>,
+            starts:      PyReadonlyArray1<$pos_ty>,
+            ends:        PyReadonlyArray1<$pos_ty>,
+            scores:      PyReadonlyArray1<f64>,
+            tile_size:   $pos_ty,
+            aggregation: &str,
+            py: Python<'_>,
+        ) -> PyResult<(
+            Py<PyArray1<$chr_ty>>, // tile chromosomes
+            Py<PyArray1<$pos_ty>>, // tile starts
+            Py<PyArray1<$pos_ty>>, // tile ends
+            Py<PyArray1<f64>>,     // aggregated scores
+        )> {
+            if tile_size <= 0 {
+                return Err(PyValueError::new_err("tile_size must be positive"));
+            }
+            if aggregation.parse::<TileAggregation>().is_err() {
+                return Err(PyValueError::new_err(
+                    "aggregation must be one of \"sum\", \"mean\", \"max\" or \"min\"",
+                ));
+            }
+            let scores = scores.as_slice()?;
+            if scores.len() != starts.as_slice()?.len() {
+                return Err(PyValueError::new_err("scores must have the same length as starts"));
+            }
+            let (t_chrs, t_starts, t_ends, t_scores) = tile_aggregate_scores(
+                chrs.as_slice()?,
+                starts.as_slice()?,
+                ends.as_slice()?,
+                scores,
+                tile_size,
+                aggregation,
+            );
+            Ok((
+                t_chrs  .into_pyarray(py).to_owned().into(),
+                t_starts.into_pyarray(py).to_owned().into(),
+                t_ends  .into_pyarray(py).to_owned().into(),
+                t_scores.into_pyarray(py).to_owned().into(),
+            ))
+        }
+    };
+}
+
+// ── concrete instantiations ────────────────────────────────────────────
+define_tile_scores_numpy!(tile_scores_numpy_u64_i64, u64, i64);
+define_tile_scores_numpy!(tile_scores_numpy_u32_i64, u32, i64);
+define_tile_scores_numpy!(tile_scores_numpy_u32_i32, u32, i32);
+define_tile_scores_numpy!(tile_scores_numpy_u32_i16, u32, i16);
+define_tile_scores_numpy!(tile_scores_numpy_u16_i64, u16, i64);
+define_tile_scores_numpy!(tile_scores_numpy_u16_i32, u16, i32);
+define_tile_scores_numpy!(tile_scores_numpy_u16_i16, u16, i16);
+define_tile_scores_numpy!(tile_scores_numpy_u8_i64,  u8,  i64);
+define_tile_scores_numpy!(tile_scores_numpy_u8_i32,  u8,  i32);
+define_tile_scores_numpy!(tile_scores_numpy_u8_i16,  u8,  i16);
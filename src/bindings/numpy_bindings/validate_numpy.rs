@@ -0,0 +1,20 @@
+use numpy::PyReadonlyArray1;
+use pyo3::{exceptions::PyValueError, pyfunction, PyResult};
+
+use crate::helpers::validate_intervals;
+
+macro_rules! define_validate_intervals_numpy {
+    ($fname:ident, $pos_ty:ty) => {
+        #[pyfunction]
+        pub fn $fname(
+            starts: PyReadonlyArray1<$pos_ty>,
+            ends: PyReadonlyArray1<$pos_ty>,
+        ) -> PyResult<()> {
+            validate_intervals(starts.as_slice()?, ends.as_slice()?).map_err(PyValueError::new_err)
+        }
+    };
+}
+
+define_validate_intervals_numpy!(validate_intervals_numpy_i64, i64);
+define_validate_intervals_numpy!(validate_intervals_numpy_i32, i32);
+define_validate_intervals_numpy!(validate_intervals_numpy_i16, i16);
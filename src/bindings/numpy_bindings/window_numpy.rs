@@ -6,13 +6,14 @@ use crate::tile::window_grouped;
 macro_rules! define_window_numpy {
     ($fname:ident, $chr_ty:ty, $pos_ty:ty) => {
         #[pyfunction]
-        #[pyo3(signature = (chrs, starts, ends, negative_strand, window_size))]
+        #[pyo3(signature = (chrs, starts, ends, negative_strand, window_size, drop_partial = false))]
         pub fn $fname(
             chrs: PyReadonlyArray1<$chr_ty>,
             starts:          PyReadonlyArray1<$pos_ty>,
             ends:            PyReadonlyArray1<$pos_ty>,
             negative_strand: PyReadonlyArray1<bool>,
             window_size:     $pos_ty,
+            drop_partial:    bool,
             py: Python<'_>,
         ) -> PyResult<(
             Py<PyArray1<usize>>,   // indices
@@ -26,6 +27,7 @@ macro_rules! define_window_numpy {
                 ends.as_slice()?,
                 negative_strand.as_slice()?,
                 window_size,
+                drop_partial,
             );
 
             Ok((
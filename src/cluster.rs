@@ -1,10 +1,21 @@
-use crate::{ruranges_structs::{GroupType, PositionType}, sorts};
+use crate::{overlaps::count_overlaps, ruranges_structs::{CoordinateType, GroupType, PositionType}, sorts};
 
-pub fn sweep_line_cluster<G: GroupType, T: PositionType>(
+/// Assigns every interval a cluster id, returned as `(cluster_ids, indices)`
+/// in sweep order.
+///
+/// Intervals are joined by the same gap rule as `sweep_line_merge`: an
+/// interval joins the current cluster when the gap between its start and the
+/// furthest end seen so far is at most `slack` bases, except that at
+/// `slack == 0` touching intervals only cluster with `merge_touching`. It is a
+/// plain distance test between neighbours, so it applies equally in both
+/// directions. An isolated zero-length interval is a cluster of its own.
+/// Ids are not consecutive across chromosomes.
+pub fn sweep_line_cluster<G: GroupType, T: CoordinateType>(
     chrs: &[G],
     starts: &[T],
     ends: &[T],
     slack: T,
+    merge_touching: bool,
 ) -> (Vec<u32>, Vec<u32>) {
     let mut indices = Vec::with_capacity(chrs.len());
     let mut cluster_ids = Vec::with_capacity(chrs.len());
@@ -13,7 +24,11 @@ pub fn sweep_line_cluster<G: GroupType, T: PositionType>(
         return (cluster_ids, indices);
     };
 
-    let events = sorts::build_sorted_events_single_collection(chrs, starts, ends, slack);
+    let joins_equal = sorts::joins_gap_of_slack(slack, merge_touching);
+    let events = sorts::build_sorted_events_gap_rule(chrs, starts, ends, slack, merge_touching);
+    // With ends first, a zero-length interval's end event precedes its own
+    // start, so such points are handled entirely at their start event.
+    let is_point = |idx: u32| !joins_equal && starts[idx as usize] == ends[idx as usize];
 
     let mut current_chr = events.first().unwrap().chr;
     let mut current_cluster = 0;
@@ -26,6 +41,17 @@ pub fn sweep_line_cluster<G: GroupType, T: PositionType>(
             current_chr = e.chr;
         }
 
+        if is_point(e.idx) {
+            if e.is_start {
+                indices.push(e.idx);
+                cluster_ids.push(current_cluster);
+                if active_intervals == 0 {
+                    current_cluster += 1;
+                }
+            }
+            continue;
+        }
+
         if e.is_start {
             indices.push(e.idx);
             cluster_ids.push(current_cluster);
@@ -40,3 +66,265 @@ pub fn sweep_line_cluster<G: GroupType, T: PositionType>(
 
     (cluster_ids, indices)
 }
+
+/// Where the cluster assignment changes along the sweep: for every cluster
+/// after the first, the start of its leftmost interval and its id, as
+/// `(positions, new_cluster_ids)`.
+///
+/// Ids follow [`sweep_line_cluster`], so the first cluster of each
+/// chromosome after the first one is reported too.
+pub fn sweep_line_cluster_transitions<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+) -> (Vec<T>, Vec<u32>) {
+    let (cluster_ids, indices) = sweep_line_cluster(chrs, starts, ends, slack, merge_touching);
+
+    let mut positions = Vec::new();
+    let mut new_cluster_ids = Vec::new();
+
+    for i in 1..cluster_ids.len() {
+        if cluster_ids[i] != cluster_ids[i - 1] {
+            positions.push(starts[indices[i] as usize]);
+            new_cluster_ids.push(cluster_ids[i]);
+        }
+    }
+
+    (positions, new_cluster_ids)
+}
+
+pub fn sweep_line_cluster_min_coverage<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+    min_neighbours: u32,
+) -> (Vec<u32>, Vec<u32>) {
+    let (cluster_ids, indices) = sweep_line_cluster(chrs, starts, ends, slack, merge_touching);
+
+    // Every interval overlaps itself once, so subtract that from the count.
+    let counts = count_overlaps(chrs, starts, ends, chrs, starts, ends, slack);
+
+    let mut out_cluster_ids = Vec::with_capacity(cluster_ids.len());
+    let mut out_indices = Vec::with_capacity(indices.len());
+
+    let mut previous_cluster = None;
+    let mut current_cluster = 0;
+
+    for (cluster_id, idx) in cluster_ids.into_iter().zip(indices) {
+        if counts[idx as usize].saturating_sub(1) < min_neighbours {
+            continue;
+        }
+
+        if let Some(previous) = previous_cluster {
+            if previous != cluster_id {
+                current_cluster += 1;
+            }
+        }
+        previous_cluster = Some(cluster_id);
+
+        out_cluster_ids.push(current_cluster);
+        out_indices.push(idx);
+    }
+
+    (out_cluster_ids, out_indices)
+}
+
+/// Per-cluster `(cluster_id, size, mean_start, mean_end, min_start,
+/// max_end)`, one row per cluster of [`sweep_line_cluster`] in the order
+/// the sweep visits them, with the same ids.
+pub type ClusterStats<T> = (Vec<u32>, Vec<u32>, Vec<f64>, Vec<f64>, Vec<T>, Vec<T>);
+
+/// Summary statistics for every cluster of [`sweep_line_cluster`],
+/// accumulated in one pass over its output.
+pub fn sweep_line_cluster_stats<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+) -> ClusterStats<T> {
+    let (cluster_ids, indices) = sweep_line_cluster(chrs, starts, ends, slack, merge_touching);
+
+    let mut out_ids = Vec::new();
+    let mut sizes: Vec<u32> = Vec::new();
+    let mut mean_starts: Vec<f64> = Vec::new();
+    let mut mean_ends: Vec<f64> = Vec::new();
+    let mut min_starts: Vec<T> = Vec::new();
+    let mut max_ends: Vec<T> = Vec::new();
+
+    // Members of one cluster are contiguous in the sweep output, so the
+    // running sums are turned into means when the next cluster begins.
+    let finish = |sizes: &[u32], mean_starts: &mut Vec<f64>, mean_ends: &mut Vec<f64>| {
+        if let Some(&n) = sizes.last() {
+            *mean_starts.last_mut().unwrap() /= n as f64;
+            *mean_ends.last_mut().unwrap() /= n as f64;
+        }
+    };
+
+    for (&cluster_id, &idx) in cluster_ids.iter().zip(&indices) {
+        let (start, end) = (starts[idx as usize], ends[idx as usize]);
+        if out_ids.last() != Some(&cluster_id) {
+            finish(&sizes, &mut mean_starts, &mut mean_ends);
+            out_ids.push(cluster_id);
+            sizes.push(0);
+            mean_starts.push(0.0);
+            mean_ends.push(0.0);
+            min_starts.push(start);
+            max_ends.push(end);
+        }
+        *sizes.last_mut().unwrap() += 1;
+        *mean_starts.last_mut().unwrap() += start.to_f64().unwrap();
+        *mean_ends.last_mut().unwrap() += end.to_f64().unwrap();
+        let min_start = min_starts.last_mut().unwrap();
+        *min_start = (*min_start).min(start);
+        let max_end = max_ends.last_mut().unwrap();
+        *max_end = (*max_end).max(end);
+    }
+    finish(&sizes, &mut mean_starts, &mut mean_ends);
+
+    (out_ids, sizes, mean_starts, mean_ends, min_starts, max_ends)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_stats_match_reference() {
+        // Clusters {0, 1, 2} and {3} on chr 0, {4} on chr 1.
+        let chrs = [0u32, 0, 0, 0, 1];
+        let starts = [0i64, 5, 12, 40, 7];
+        let ends = [10i64, 15, 20, 45, 9];
+
+        let (ids, sizes, mean_starts, mean_ends, min_starts, max_ends) =
+            sweep_line_cluster_stats(&chrs, &starts, &ends, 0, false);
+        let (cluster_ids, indices) = sweep_line_cluster(&chrs, &starts, &ends, 0, false);
+
+        assert_eq!(sizes, vec![3, 1, 1]);
+        for (row, &id) in ids.iter().enumerate() {
+            let members: Vec<usize> = cluster_ids
+                .iter()
+                .zip(&indices)
+                .filter(|(&c, _)| c == id)
+                .map(|(_, &i)| i as usize)
+                .collect();
+            let n = members.len() as f64;
+            assert_eq!(sizes[row] as usize, members.len());
+            assert_eq!(mean_starts[row], members.iter().map(|&i| starts[i] as f64).sum::<f64>() / n);
+            assert_eq!(mean_ends[row], members.iter().map(|&i| ends[i] as f64).sum::<f64>() / n);
+            assert_eq!(min_starts[row], members.iter().map(|&i| starts[i]).min().unwrap());
+            assert_eq!(max_ends[row], members.iter().map(|&i| ends[i]).max().unwrap());
+        }
+
+        // A single-interval cluster spans exactly that interval.
+        for (row, (s, e)) in [(1, (40i64, 45i64)), (2, (7, 9))] {
+            assert_eq!(min_starts[row], s);
+            assert_eq!(max_ends[row] - (e - s), min_starts[row]);
+        }
+    }
+
+    #[test]
+    fn test_cluster_transitions_at_new_clusters() {
+        // Clusters {0, 1} and {2} on chr 0, {3} on chr 1.
+        let chrs = [0u32, 0, 0, 1];
+        let starts = [0i64, 5, 30, 2];
+        let ends = [10i64, 15, 40, 8];
+
+        let (positions, ids) = sweep_line_cluster_transitions(&chrs, &starts, &ends, 0, false);
+
+        assert_eq!(positions, vec![30, 2]);
+        // Ids are not consecutive across chromosomes, as in `sweep_line_cluster`.
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_cluster_gap_equal_to_slack_is_joined() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 15];
+        let ends = [10i64, 20];
+
+        let (ids, _) = sweep_line_cluster(&chrs, &starts, &ends, 5, false);
+        assert_eq!(ids, vec![0, 0]);
+        let (ids, _) = sweep_line_cluster(&chrs, &starts, &ends, 4, false);
+        assert_eq!(ids, vec![0, 1]);
+        // Without slack, touching intervals only cluster with merge_touching.
+        let (ids, _) = sweep_line_cluster(&chrs, &[0i64, 10], &ends, 0, false);
+        assert_eq!(ids, vec![0, 1]);
+        let (ids, _) = sweep_line_cluster(&chrs, &[0i64, 10], &ends, 0, true);
+        assert_eq!(ids, vec![0, 0]);
+    }
+
+    #[test]
+    fn test_cluster_float_coordinates() {
+        let chrs = [0u32, 0, 0];
+        let starts = [0.0f64, 1.5, 4.0];
+        let ends = [2.0f64, 3.0, 5.0];
+
+        let (ids, idx) = sweep_line_cluster(&chrs, &starts, &ends, 0.0, false);
+        assert_eq!(idx, vec![0, 1, 2]);
+        assert_eq!(ids, vec![0, 0, 1]);
+
+        let (ids, _) = sweep_line_cluster(&chrs, &starts, &ends, 1.5, false);
+        assert_eq!(ids, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_cluster_min_coverage_renumbers_kept_clusters() {
+        // Clusters {0, 1}, {2} and {3, 4}; interval 2 has no neighbour, so its
+        // cluster disappears and {3, 4} becomes cluster 1.
+        let chrs = [0u32; 5];
+        let starts = [0i64, 5, 30, 50, 55];
+        let ends = [10i64, 15, 40, 60, 65];
+
+        let (ids, idx) = sweep_line_cluster_min_coverage(&chrs, &starts, &ends, 0, false, 1);
+        assert_eq!(idx, vec![0, 1, 3, 4]);
+        assert_eq!(ids, vec![0, 0, 1, 1]);
+
+        let (ids, idx) = sweep_line_cluster_min_coverage(&chrs, &starts, &ends, 0, false, 2);
+        assert!(ids.is_empty() && idx.is_empty());
+    }
+
+    #[test]
+    fn test_cluster_min_coverage_zero_matches_plain_cluster() {
+        let chrs = [0u32; 5];
+        let starts = [0i64, 5, 30, 50, 55];
+        let ends = [10i64, 15, 40, 60, 65];
+
+        for slack in [0, 10, 20] {
+            assert_eq!(
+                sweep_line_cluster_min_coverage(&chrs, &starts, &ends, slack, false, 0),
+                sweep_line_cluster(&chrs, &starts, &ends, slack, false),
+                "slack {slack}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cluster_matches_merge_regions() {
+        // Bookended, overlapping, a gap of exactly 5 and a point: cluster
+        // and merge must draw the same boundaries for every setting.
+        let chrs = [0u32; 6];
+        let starts = [0i64, 10, 15, 35, 47, 52];
+        let ends = [10i64, 20, 30, 40, 47, 60];
+
+        for slack in [0, 5] {
+            for merge_touching in [false, true] {
+                let (ids, idx) = sweep_line_cluster(&chrs, &starts, &ends, slack, merge_touching);
+                let merge_ids =
+                    crate::merge::sweep_line_merge_ids(&chrs, &starts, &ends, slack, merge_touching);
+                for pair in idx.iter().zip(&ids).collect::<Vec<_>>().windows(2) {
+                    let ((&a, &ca), (&b, &cb)) = (pair[0], pair[1]);
+                    assert_eq!(
+                        ca == cb,
+                        merge_ids[a as usize] == merge_ids[b as usize],
+                        "slack {slack}, merge_touching {merge_touching}, rows {a} and {b}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub fn sweep_line_non_overlaps<G: GroupType, T: PositionType>(
     // If either set is empty, none can overlap; return everything as “non-overlapping”.
     if chrs.is_empty() || chrs2.is_empty() {
         // Just return all indices as non-overlapping
-        return no_overlaps.to_vec();
+        return (0..chrs.len() as u32).collect();
     }
 
     // Build up the event list in ascending order (same as before)
@@ -32,7 +32,9 @@ pub fn sweep_line_non_overlaps<G: GroupType, T: PositionType>(
     let mut current_chr = events.first().unwrap().chr;
 
     for e in events {
-        // If chromosome changed, clear active sets
+        // If chromosome changed, clear active sets. Every interval ends on the
+        // chromosome it started on, so the sets are already empty here; the
+        // clear only guards against leaking state between chromosomes.
         if e.chr != current_chr {
             active1.clear();
             active2.clear();
@@ -60,17 +62,95 @@ pub fn sweep_line_non_overlaps<G: GroupType, T: PositionType>(
             // Interval is ending
             if e.first_set {
                 active1.remove(&e.idx);
-                if !overlapped.contains(&e.idx) {
+                // Only set-1 indices live in `overlapped`; set-2 indices share
+                // the same number space and must not clear them.
+                if !overlapped.remove(&e.idx) {
                     no_overlaps.push(e.idx);
                 }
             } else {
                 active2.remove(&e.idx);
             }
-
-            overlapped.remove(&e.idx);
         }
     }
 
     radsort::sort(&mut no_overlaps);
     no_overlaps
 }
+
+/// The set-2 intervals with no overlap in set 1, as `(idx2, start, end)`
+/// sorted by index: the anti-join seen from set 2.
+///
+/// Overlap within `slack` is symmetric, so this is [`sweep_line_non_overlaps`]
+/// with the two sets swapped.
+pub fn sweep_line_set2_uncovered<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> (Vec<u32>, Vec<T>, Vec<T>) {
+    let idxs = sweep_line_non_overlaps(chrs2, starts2, ends2, chrs, starts, ends, slack);
+
+    let out_starts = idxs.iter().map(|&i| starts2[i as usize]).collect();
+    let out_ends = idxs.iter().map(|&i| ends2[i as usize]).collect();
+    (idxs, out_starts, out_ends)
+}
+
+pub fn sweep_line_non_overlaps_mask<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> Vec<bool> {
+    let mut mask = vec![false; chrs.len()];
+
+    for idx in sweep_line_non_overlaps(chrs, starts, ends, chrs2, starts2, ends2, slack) {
+        mask[idx as usize] = true;
+    }
+
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set2_end_does_not_clear_set1_overlap() {
+        // Set-2 interval 0 lies inside set-1 interval 0 and ends first; its
+        // end must not undo the overlap recorded for set-1 interval 0.
+        let chrs = [0u32, 0];
+        let starts = [0i64, 30];
+        let ends = [20i64, 40];
+
+        let idx = sweep_line_non_overlaps(&chrs, &starts, &ends, &[0u32], &[5i64], &[10i64], 0);
+        assert_eq!(idx, vec![1]);
+
+        let mask = sweep_line_non_overlaps_mask(&chrs, &starts, &ends, &[0u32], &[5i64], &[10i64], 0);
+        assert_eq!(mask, vec![false, true]);
+    }
+
+    #[test]
+    fn test_set2_uncovered_returns_set2_coordinates() {
+        let chrs = [0u32, 0, 1];
+        let starts = [1i64, 10, 30];
+        let ends = [5i64, 15, 35];
+        let chrs2 = [0u32, 0, 0, 1];
+        let starts2 = [3i64, 20, 15, 0];
+        let ends2 = [6i64, 25, 18, 40];
+
+        let (idx, s, e) = sweep_line_set2_uncovered(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0);
+        assert_eq!(idx, vec![1, 2]);
+        assert_eq!(s, vec![20, 15]);
+        assert_eq!(e, vec![25, 18]);
+
+        // Slack widens set 1 to [4, 21) etc., which reaches both of them.
+        let (idx, _, _) = sweep_line_set2_uncovered(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 6);
+        assert!(idx.is_empty());
+    }
+}
@@ -2,6 +2,10 @@ use rustc_hash::FxHashMap;
 
 use crate::{ruranges_structs::{GroupType, PositionType}, sorts};
 
+/// Gaps between the (slack-extended) intervals of each chromosome.
+///
+/// Only gaps of at least `min_gap` bases are reported; unlike `slack`, which
+/// shifts the event positions, `min_gap` just filters the output.
 pub fn sweep_line_complement<G: GroupType, T: PositionType>(
     chrs: &[G],
     starts: &[T],
@@ -9,6 +13,7 @@ pub fn sweep_line_complement<G: GroupType, T: PositionType>(
     slack: T,
     chrom_lens: &FxHashMap<G, T>,
     include_first_interval: bool, // <-- new parameter
+    min_gap: T,
 ) -> (Vec<G>, Vec<T>, Vec<T>, Vec<u32>) {
     let mut out_chrs = Vec::with_capacity(chrs.len());
     let mut out_starts = Vec::with_capacity(chrs.len());
@@ -20,6 +25,15 @@ pub fn sweep_line_complement<G: GroupType, T: PositionType>(
         return (out_chrs, out_starts, out_ends, out_idxs);
     }
 
+    let mut emit = |chr: G, start: T, end: T, idx: u32| {
+        if end - start >= min_gap {
+            out_chrs.push(chr);
+            out_starts.push(start);
+            out_ends.push(end);
+            out_idxs.push(idx);
+        }
+    };
+
     // Build your events array, sorted by chr and pos
     let events = sorts::build_sorted_events_single_collection(chrs, starts, ends, slack);
 
@@ -39,10 +53,7 @@ pub fn sweep_line_complement<G: GroupType, T: PositionType>(
             // optionally close it out at the chromosome’s end
             if let Some(chlen) = chrom_lens.get(&current_chr) {
                 if in_complement {
-                    out_chrs.push(current_chr);
-                    out_starts.push(current_start);
-                    out_ends.push(*chlen);
-                    out_idxs.push(current_index);
+                    emit(current_chr, current_start, *chlen, current_index);
                 }
             }
 
@@ -61,10 +72,7 @@ pub fn sweep_line_complement<G: GroupType, T: PositionType>(
             // If coverage was zero, we just ended a hole
             if active_count == 1 && in_complement && current_start != e.pos {
                 // That hole ends at e.pos
-                out_chrs.push(current_chr);
-                out_starts.push(current_start);
-                out_ends.push(e.pos);
-                out_idxs.push(current_index);
+                emit(current_chr, current_start, e.pos, current_index);
 
                 // We're no longer in a hole
                 in_complement = false;
@@ -84,12 +92,115 @@ pub fn sweep_line_complement<G: GroupType, T: PositionType>(
     // End of all events: if we finished in a hole and have chromosome lengths
     if let Some(chlen) = chrom_lens.get(&current_chr) {
         if in_complement {
-            out_chrs.push(current_chr);
-            out_starts.push(current_start);
-            out_ends.push(*chlen);
-            out_idxs.push(current_index);
+            emit(current_chr, current_start, *chlen, current_index);
         }
     }
 
     (out_chrs, out_starts, out_ends, out_idxs)
 }
+
+/// Gaps between consecutive intervals on the same chromosome, e.g. introns
+/// from exons. Unlike [`sweep_line_complement`] no chromosome lengths are
+/// needed: nothing is reported before the first or after the last interval.
+///
+/// Overlapping and bookended intervals leave no gap, and gaps of at most
+/// `slack` bases are skipped; reported gaps keep their exact coordinates.
+/// Returns `(idx, gap_starts, gap_ends)`, where `idx` is the interval
+/// immediately following each gap, as in [`sweep_line_complement`].
+pub fn sweep_line_gaps<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+) -> (Vec<u32>, Vec<T>, Vec<T>) {
+    let mut out_idxs = Vec::new();
+    let mut out_starts = Vec::new();
+    let mut out_ends = Vec::new();
+
+    let events = sorts::build_sorted_events_single_collection(chrs, starts, ends, T::zero());
+    let Some(first) = events.first() else {
+        return (out_idxs, out_starts, out_ends);
+    };
+
+    let mut current_chr = first.chr;
+    let mut active_count = 0_i64;
+    // End of the last covered stretch on this chromosome, if any.
+    let mut gap_start: Option<T> = None;
+
+    for e in events {
+        if e.chr != current_chr {
+            current_chr = e.chr;
+            active_count = 0;
+            gap_start = None;
+        }
+
+        if e.is_start {
+            active_count += 1;
+            if active_count == 1 {
+                if let Some(gs) = gap_start {
+                    if e.pos - gs > slack {
+                        out_idxs.push(e.idx);
+                        out_starts.push(gs);
+                        out_ends.push(e.pos);
+                    }
+                }
+            }
+        } else {
+            active_count -= 1;
+            if active_count == 0 {
+                gap_start = Some(e.pos);
+            }
+        }
+    }
+
+    (out_idxs, out_starts, out_ends)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complement_min_gap_suppresses_short_gaps() {
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 30i64);
+
+        // [0,10) and [10,20) are adjacent, and [25,30) runs up to the end of
+        // the chromosome, leaving a zero-length gap at 30.
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 10, 25];
+        let ends = [10i64, 20, 30];
+
+        let (_, s, e, _) = sweep_line_complement(&chrs, &starts, &ends, 0, &lens, false, 0);
+        assert_eq!((s, e), (vec![20, 30], vec![25, 30]));
+
+        let (_, s, e, _) = sweep_line_complement(&chrs, &starts, &ends, 0, &lens, false, 1);
+        assert_eq!((s, e), (vec![20], vec![25]));
+
+        let (_, s, e, _) = sweep_line_complement(&chrs, &starts, &ends, 0, &lens, false, 6);
+        assert!(s.is_empty() && e.is_empty());
+    }
+
+    #[test]
+    fn test_gaps_between_consecutive_intervals() {
+        // A single interval has nothing to be between.
+        let (idx, s, e) = sweep_line_gaps(&[0u32], &[10i64], &[20i64], 0);
+        assert!(idx.is_empty() && s.is_empty() && e.is_empty());
+
+        // Two non-adjacent intervals leave one gap, attributed to the second.
+        let (idx, s, e) = sweep_line_gaps(&[0u32, 0], &[30i64, 10], &[40i64, 20], 0);
+        assert_eq!((idx, s, e), (vec![0], vec![20], vec![30]));
+
+        // Overlapping intervals, bookended ones and different chromosomes
+        // leave none.
+        let chrs = [0u32, 0, 0, 1];
+        let starts = [10i64, 15, 25, 30];
+        let ends = [20i64, 25, 30, 40];
+        let (idx, _, _) = sweep_line_gaps(&chrs, &starts, &ends, 0);
+        assert!(idx.is_empty());
+
+        // Gaps no longer than slack are skipped.
+        let (idx, _, _) = sweep_line_gaps(&[0u32, 0], &[10i64, 30], &[20i64, 40], 10);
+        assert!(idx.is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+use crate::overlaps::count_overlaps;
+use crate::ruranges_structs::{GroupType, PositionType};
+use crate::sorts::build_sorted_events_single_collection;
+
+/// Summed length (in bases) of the given intervals, as `f64`.
+fn total_length<T: PositionType>(starts: &[T], ends: &[T]) -> f64 {
+    starts
+        .iter()
+        .zip(ends)
+        .map(|(&s, &e)| (e - s).to_f64().unwrap())
+        .sum()
+}
+
+/// Per-interval read counts normalised to reads per million base pairs:
+/// `count / (total interval length / 1e6)`.
+///
+/// `chrs`/`starts`/`ends` are the reads and `interval_*` the regions they are
+/// counted in; the output has one entry per region. When the regions have no
+/// total length every value is 0.
+pub fn coverage_normalize_rpm<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    interval_chrs: &[G],
+    interval_starts: &[T],
+    interval_ends: &[T],
+) -> Vec<f64> {
+    let counts = count_overlaps(interval_chrs, interval_starts, interval_ends, chrs, starts, ends, T::zero());
+
+    let megabases = total_length(interval_starts, interval_ends) / 1_000_000.0;
+    if megabases <= 0.0 {
+        return vec![0.0; counts.len()];
+    }
+    counts.into_iter().map(|c| c as f64 / megabases).collect()
+}
+
+/// Per-interval read counts as reads per kilobase of region per million
+/// reads: `count / (region length / 1e3) / (number of reads / 1e6)`.
+///
+/// Same inputs as [`coverage_normalize_rpm`]. Zero-length regions, or an
+/// empty read set, give 0.
+pub fn coverage_normalize_rpkm<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    interval_chrs: &[G],
+    interval_starts: &[T],
+    interval_ends: &[T],
+) -> Vec<f64> {
+    let counts = count_overlaps(interval_chrs, interval_starts, interval_ends, chrs, starts, ends, T::zero());
+
+    let million_reads = chrs.len() as f64 / 1_000_000.0;
+    counts
+        .into_iter()
+        .zip(interval_starts.iter().zip(interval_ends))
+        .map(|(c, (&s, &e))| {
+            let kilobases = (e - s).to_f64().unwrap() / 1_000.0;
+            if kilobases <= 0.0 || million_reads == 0.0 {
+                0.0
+            } else {
+                c as f64 / kilobases / million_reads
+            }
+        })
+        .collect()
+}
+
+/// Counts of features in fixed bins around each anchor, for metaplots.
+///
+/// The window `[pos - half_width, pos + half_width)` of every anchor is cut
+/// into `bin_size` bins (the rightmost one shorter if `2 * half_width` is not
+/// a multiple) and the features overlapping each bin are counted. Bins run
+/// 5'->3': on minus-strand anchors (`anchor_strands[i] == false`) bin 0 is
+/// the one furthest right. Returns a row-major `anchors x bins` matrix.
+#[allow(clippy::too_many_arguments)]
+pub fn anchor_profile<G: GroupType, T: PositionType>(
+    anchor_chrs: &[G],
+    anchor_positions: &[T],
+    anchor_strands: Option<&[bool]>,
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    half_width: T,
+    bin_size: T,
+) -> Vec<u32> {
+    assert!(bin_size > T::zero(), "bin_size must be positive");
+
+    let width = half_width + half_width;
+    let n_bins = ((width + bin_size - T::one()) / bin_size).to_usize().unwrap();
+
+    // One bin interval per (anchor, bin), laid out in output order.
+    let n = anchor_chrs.len() * n_bins;
+    let mut bin_chrs = Vec::with_capacity(n);
+    let mut bin_starts = Vec::with_capacity(n);
+    let mut bin_ends = Vec::with_capacity(n);
+    for (i, (&chr, &pos)) in anchor_chrs.iter().zip(anchor_positions).enumerate() {
+        let forward = anchor_strands.is_none_or(|s| s[i]);
+        let left = pos - half_width;
+        for b in 0..n_bins {
+            let k = T::from(if forward { b } else { n_bins - 1 - b }).unwrap();
+            let bin_start = left + k * bin_size;
+            bin_chrs.push(chr);
+            bin_starts.push(bin_start);
+            bin_ends.push((bin_start + bin_size).min(pos + half_width));
+        }
+    }
+
+    count_overlaps(&bin_chrs, &bin_starts, &bin_ends, chrs, starts, ends, T::zero())
+}
+
+/// Base-pair coverage depth as runs: `(chr, start, end, depth)` records
+/// in chromosome and position order, with a constant depth inside each run
+/// and different depths in adjacent runs. Uncovered stretches are left out,
+/// so the output grows with the number of interval boundaries rather than
+/// with chromosome length.
+pub fn coverage_rle<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+) -> (Vec<G>, Vec<T>, Vec<T>, Vec<u32>) {
+    let mut out_chrs = Vec::new();
+    let mut out_starts: Vec<T> = Vec::new();
+    let mut out_ends: Vec<T> = Vec::new();
+    let mut out_depths: Vec<u32> = Vec::new();
+
+    let events = build_sorted_events_single_collection(chrs, starts, ends, T::zero());
+    let Some(first) = events.first() else {
+        return (out_chrs, out_starts, out_ends, out_depths);
+    };
+
+    let mut current_chr = first.chr;
+    let mut previous_pos = first.pos;
+    // Signed: a zero-length interval's end event precedes its start.
+    let mut depth: i64 = 0;
+
+    for e in events {
+        if e.chr != current_chr {
+            current_chr = e.chr;
+            depth = 0;
+        } else if e.pos > previous_pos && depth > 0 {
+            let extends_last = out_chrs.last() == Some(&current_chr)
+                && out_ends.last() == Some(&previous_pos)
+                && out_depths.last() == Some(&(depth as u32));
+            if extends_last {
+                *out_ends.last_mut().unwrap() = e.pos;
+            } else {
+                out_chrs.push(current_chr);
+                out_starts.push(previous_pos);
+                out_ends.push(e.pos);
+                out_depths.push(depth as u32);
+            }
+        }
+        depth += if e.is_start { 1 } else { -1 };
+        previous_pos = e.pos;
+    }
+
+    (out_chrs, out_starts, out_ends, out_depths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_rle_runs() {
+        // chr 0: [0,10) and [5,15) overlap, [15,20) bookends the second, and
+        // [30,30) is empty. chr 1: a single interval.
+        let chrs = [0u32, 0, 0, 0, 1];
+        let starts = [0i64, 5, 15, 30, 0];
+        let ends = [10i64, 15, 20, 30, 4];
+
+        let (c, s, e, d) = coverage_rle(&chrs, &starts, &ends);
+        assert_eq!(c, vec![0, 0, 0, 1]);
+        assert_eq!(s, vec![0, 5, 10, 0]);
+        assert_eq!(e, vec![5, 10, 20, 4]);
+        assert_eq!(d, vec![1, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_anchor_profile_is_strand_aware() {
+        // Anchors at 100 on both strands; a feature just right of the anchor.
+        let profile = anchor_profile(
+            &[0u32, 0],
+            &[100i64, 100],
+            Some(&[true, false]),
+            &[0u32, 0],
+            &[105i64, 60],
+            &[112i64, 61],
+            50,
+            20,
+        );
+        // Bins on +: [50,70) [70,90) [90,110) [110,130) [130,150).
+        assert_eq!(&profile[..5], &[1, 0, 1, 1, 0]);
+        // On - the same counts read right to left.
+        assert_eq!(&profile[5..], &[0, 1, 1, 0, 1]);
+    }
+
+    // Two regions (1 kb and 3 kb, 4 kb in total) and four reads: three in
+    // the first region, one in the second.
+    const READ_CHRS: [u32; 4] = [0, 0, 0, 0];
+    const READ_STARTS: [i64; 4] = [100, 200, 900, 2500];
+    const READ_ENDS: [i64; 4] = [150, 250, 950, 2550];
+    const REGION_CHRS: [u32; 2] = [0, 0];
+    const REGION_STARTS: [i64; 2] = [0, 1000];
+    const REGION_ENDS: [i64; 2] = [1000, 4000];
+
+    #[test]
+    fn test_coverage_normalize_rpm() {
+        let rpm = coverage_normalize_rpm(
+            &READ_CHRS, &READ_STARTS, &READ_ENDS,
+            &REGION_CHRS, &REGION_STARTS, &REGION_ENDS,
+        );
+        // 3 reads and 1 read per 0.004 Mb of regions.
+        assert_eq!(rpm, vec![750.0, 250.0]);
+    }
+
+    #[test]
+    fn test_coverage_normalize_rpkm() {
+        let rpkm = coverage_normalize_rpkm(
+            &READ_CHRS, &READ_STARTS, &READ_ENDS,
+            &REGION_CHRS, &REGION_STARTS, &REGION_ENDS,
+        );
+        // 3 reads / 1 kb / 4e-6 million reads, 1 read / 3 kb / 4e-6.
+        assert_eq!(rpkm[0], 750_000.0);
+        assert!((rpkm[1] - 250_000.0 / 3.0).abs() < 1e-6);
+    }
+}
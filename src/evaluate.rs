@@ -0,0 +1,240 @@
+use rustc_hash::FxHashSet;
+
+use crate::overlaps::sweep_line_overlaps;
+use crate::ruranges_structs::{GroupType, PositionType};
+use crate::sorts::build_sorted_events;
+
+/// Compares predicted intervals against a ground truth, returning
+/// `(precision, recall, f1)`.
+///
+/// A pair counts as a match when the predicted interval covers at least
+/// `min_overlap_fraction` of the true interval's length (and overlaps it at
+/// all). Precision is the fraction of predicted intervals with a match,
+/// recall the fraction of true intervals with one. Each is 0 for an empty
+/// set, and F1 is 0 when both are.
+pub fn evaluate_overlap<G: GroupType, T: PositionType>(
+    pred_chrs: &[G],
+    pred_starts: &[T],
+    pred_ends: &[T],
+    true_chrs: &[G],
+    true_starts: &[T],
+    true_ends: &[T],
+    min_overlap_fraction: f64,
+) -> (f64, f64, f64) {
+    let mut pred_hit = vec![false; pred_chrs.len()];
+    let mut true_hit = vec![false; true_chrs.len()];
+
+    let pairs = sweep_line_overlaps(
+        pred_chrs, pred_starts, pred_ends, true_chrs, true_starts, true_ends, T::zero(),
+    );
+    for pair in pairs {
+        let j = pair.idx2 as usize;
+        let true_len = (true_ends[j] - true_starts[j]).to_f64().unwrap();
+        if pair.overlap_len.to_f64().unwrap() >= min_overlap_fraction * true_len {
+            pred_hit[pair.idx as usize] = true;
+            true_hit[j] = true;
+        }
+    }
+
+    let fraction = |hits: &[bool]| {
+        if hits.is_empty() {
+            0.0
+        } else {
+            hits.iter().filter(|&&h| h).count() as f64 / hits.len() as f64
+        }
+    };
+    let precision = fraction(&pred_hit);
+    let recall = fraction(&true_hit);
+    let f1 = if precision + recall > 0.0 {
+        2.0 * precision * recall / (precision + recall)
+    } else {
+        0.0
+    };
+
+    (precision, recall, f1)
+}
+
+/// Number and length distribution of the overlapping pairs between two sets.
+///
+/// `mean` and `median` are NaN when there are no pairs; the median of an
+/// even count is the mean of the two middle lengths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlapLengthStats {
+    pub n_pairs: usize,
+    pub mean: f64,
+    pub median: f64,
+}
+
+/// Jaccard index of two interval sets: bases covered by both sets over bases
+/// covered by either, each set taken as its union. 0 when both are empty.
+pub fn jaccard<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+) -> f64 {
+    jaccard_sweep(chrs, starts, ends, chrs2, starts2, ends2, None)
+}
+
+/// The coverage sweep behind [`jaccard`]. With `pair_lengths`, it also
+/// records the length of every overlapping pair as the pair opens.
+fn jaccard_sweep<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+    mut pair_lengths: Option<&mut Vec<f64>>,
+) -> f64 {
+    if chrs.is_empty() && chrs2.is_empty() {
+        return 0.0;
+    }
+
+    let events = build_sorted_events(chrs, starts, ends, chrs2, starts2, ends2, T::zero());
+
+    let mut intersection = 0.0;
+    let mut union = 0.0;
+    let mut current_chr = events.first().unwrap().chr;
+    let mut previous_pos = events.first().unwrap().pos;
+    // Signed: a zero-length interval's end event precedes its start.
+    let mut active1: i64 = 0;
+    let mut active2: i64 = 0;
+    // Open intervals of each set, only tracked when pairs are recorded.
+    let mut open1: FxHashSet<u32> = FxHashSet::default();
+    let mut open2: FxHashSet<u32> = FxHashSet::default();
+
+    for e in events {
+        if e.chr != current_chr {
+            current_chr = e.chr;
+            active1 = 0;
+            active2 = 0;
+            open1.clear();
+            open2.clear();
+        } else if e.pos > previous_pos {
+            let span = (e.pos - previous_pos).to_f64().unwrap();
+            if active1 > 0 || active2 > 0 {
+                union += span;
+            }
+            if active1 > 0 && active2 > 0 {
+                intersection += span;
+            }
+        }
+
+        let delta = if e.is_start { 1 } else { -1 };
+        if e.first_set {
+            active1 += delta;
+        } else {
+            active2 += delta;
+        }
+        previous_pos = e.pos;
+
+        if let Some(lengths) = pair_lengths.as_deref_mut() {
+            let i = e.idx as usize;
+            let (own, other, own_bounds, other_bounds) = if e.first_set {
+                (&mut open1, &open2, (starts, ends), (starts2, ends2))
+            } else {
+                (&mut open2, &open1, (starts2, ends2), (starts, ends))
+            };
+            if !e.is_start {
+                own.remove(&e.idx);
+            } else if own_bounds.1[i] > own_bounds.0[i] {
+                for &j in other {
+                    let j = j as usize;
+                    let overlap = own_bounds.1[i].min(other_bounds.1[j]) - own_bounds.0[i].max(other_bounds.0[j]);
+                    if overlap > T::zero() {
+                        lengths.push(overlap.to_f64().unwrap());
+                    }
+                }
+                own.insert(e.idx);
+            }
+        }
+    }
+
+    if union > 0.0 { intersection / union } else { 0.0 }
+}
+
+/// [`jaccard`] together with statistics on the overlapping pairs, so the
+/// intersection can be characterised without a separate `overlaps` call.
+/// Both come from one sweep. Touching intervals share no base and do not
+/// form a pair.
+pub fn jaccard_with_overlap_stats<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+) -> (f64, OverlapLengthStats) {
+    let mut lengths = Vec::new();
+    let index = jaccard_sweep(chrs, starts, ends, chrs2, starts2, ends2, Some(&mut lengths));
+    lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n_pairs = lengths.len();
+    let (mean, median) = if n_pairs == 0 {
+        (f64::NAN, f64::NAN)
+    } else {
+        let mid = n_pairs / 2;
+        let median = if n_pairs.is_multiple_of(2) { (lengths[mid - 1] + lengths[mid]) / 2.0 } else { lengths[mid] };
+        (lengths.iter().sum::<f64>() / n_pairs as f64, median)
+    };
+
+    (index, OverlapLengthStats { n_pairs, mean, median })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_overlap_precision_recall_f1() {
+        // Prediction 0 covers all of truth 0, prediction 1 only 2 of truth
+        // 1's 10 bases, prediction 2 hits nothing. Truth 2 is missed.
+        let pred_chrs = [0u32, 0, 0];
+        let pred_starts = [0i64, 18, 50];
+        let pred_ends = [10i64, 22, 60];
+        let true_chrs = [0u32, 0, 1];
+        let true_starts = [0i64, 20, 0];
+        let true_ends = [10i64, 30, 10];
+
+        let run = |frac| evaluate_overlap(&pred_chrs, &pred_starts, &pred_ends, &true_chrs, &true_starts, &true_ends, frac);
+
+        let (p, r, f1) = run(0.0);
+        assert_eq!((p, r), (2.0 / 3.0, 2.0 / 3.0));
+        assert!((f1 - 2.0 / 3.0).abs() < 1e-12);
+
+        let (p, r, _) = run(0.5);
+        assert_eq!((p, r), (1.0 / 3.0, 1.0 / 3.0));
+
+        assert_eq!(evaluate_overlap::<u32, i64>(&[], &[], &[], &[], &[], &[], 0.5), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_jaccard_with_overlap_stats() {
+        // Set 1 covers [0, 20) and [30, 40), set 2 [10, 35) and [50, 60):
+        // 15 bases shared out of 50 covered.
+        let chrs = [0u32, 0];
+        let starts = [0i64, 30];
+        let ends = [20i64, 40];
+        let chrs2 = [0u32, 0];
+        let starts2 = [10i64, 50];
+        let ends2 = [35i64, 60];
+
+        let (index, stats) = jaccard_with_overlap_stats(&chrs, &starts, &ends, &chrs2, &starts2, &ends2);
+        assert!((index - 15.0 / 50.0).abs() < 1e-12);
+        assert_eq!(stats, OverlapLengthStats { n_pairs: 2, mean: 7.5, median: 7.5 });
+
+        // Nested and multiply-overlapping intervals, on two chromosomes.
+        let (_, stats) = jaccard_with_overlap_stats(
+            &[0u32, 0, 1], &[0i64, 5, 0], &[100i64, 8, 10],
+            &[0u32, 0, 1], &[2i64, 6, 5], &[7i64, 50, 6],
+        );
+        assert_eq!(stats, OverlapLengthStats { n_pairs: 5, mean: 10.8, median: 2.0 });
+
+        let (index, stats) = jaccard_with_overlap_stats(&[0u32], &[0i64], &[10i64], &[0u32], &[10i64], &[20i64]);
+        assert_eq!((index, stats.n_pairs), (0.0, 0));
+        assert!(stats.mean.is_nan());
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use rustc_hash::FxHashMap;
+
 use crate::ruranges_structs::{GroupType, PositionType};
 
 fn check_ext_options<T: PositionType>(
@@ -16,23 +18,86 @@ fn check_ext_options<T: PositionType>(
     }
 }
 
+/// Resolves the extension options into `(ext_3, ext_5)`: `ext` extends both
+/// ends by the same amount, otherwise the missing one of `ext_3`/`ext_5` is 0.
+fn resolve_ext<T: PositionType>(
+    ext: Option<T>,
+    ext_3: Option<T>,
+    ext_5: Option<T>,
+) -> Result<(T, T), String> {
+    check_ext_options(ext, ext_3, ext_5)?;
+    Ok(match ext {
+        Some(ext) => (ext, ext),
+        None => (ext_3.unwrap_or(T::zero()), ext_5.unwrap_or(T::zero())),
+    })
+}
+
+/// Extends every interval on its own, see [`extend_grp`] for the options.
+///
+/// Returns `(new_starts, new_ends)`.
+pub fn extend<T: PositionType>(
+    starts:           &[T],
+    ends:             &[T],
+    negative_strand:  &[bool],
+    ext:              Option<T>,
+    ext_3:            Option<T>,
+    ext_5:            Option<T>,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    let (ext_3, ext_5) = resolve_ext(ext, ext_3, ext_5)?;
+    extend_rows(starts, ends, negative_strand, ext_3, ext_5)
+}
+
+/// Every per-row column must have one entry per interval.
+fn check_row_lengths<T>(starts: &[T], ends: &[T], negative_strand: &[bool]) -> Result<(), String> {
+    if ends.len() != starts.len() {
+        return Err(format!("ends has {} entries, but starts has {}", ends.len(), starts.len()));
+    }
+    if negative_strand.len() != starts.len() {
+        return Err(format!(
+            "negative_strand has {} entries, but there are {} intervals",
+            negative_strand.len(),
+            starts.len()
+        ));
+    }
+    Ok(())
+}
+
 
 /// Extend each group's intervals by modifying only the row with the minimal start
 /// and the row with the maximal end for that group.
 ///
-/// Returns `(group_ids, new_starts, new_ends)`.
+/// Either `ext` (both ends) or at least one of `ext_3`/`ext_5` must be given.
+/// The 5′ end of a group is its start on the plus strand and its end on the
+/// minus strand, judged by the strand of the row with the minimal start.
+///
+/// When `chrom_lens` is given as `(chrs, lengths)`, with one chromosome per
+/// row and a length per chromosome, the extended coordinates are clipped to
+/// `[0, len]` of the row's chromosome, which must then have a length.
+///
+/// Returns `(new_starts, new_ends)`.
+#[allow(clippy::too_many_arguments)]
 pub fn extend_grp<G: GroupType, T: PositionType>(
     group_ids:        &[G],
     starts:           &[T],
     ends:             &[T],
     negative_strand:  &[bool],
-    ext_3:            T,
-    ext_5:            T,
-) -> (Vec<T>, Vec<T>) {
+    ext:              Option<T>,
+    ext_3:            Option<T>,
+    ext_5:            Option<T>,
+    chrom_lens:       Option<(&[G], &FxHashMap<G, T>)>,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    let (ext_3, ext_5) = resolve_ext(ext, ext_3, ext_5)?;
+
     /* ─── 0. Basic sanity ─────────────────────────────────────────────────── */
-    assert_eq!(group_ids.len(), starts.len());
-    assert_eq!(starts.len(),     ends.len());
-    assert_eq!(ends.len(),       negative_strand.len());
+    if group_ids.len() != starts.len() {
+        return Err(format!("group_ids has {} entries, but starts has {}", group_ids.len(), starts.len()));
+    }
+    check_row_lengths(starts, ends, negative_strand)?;
+    if let Some((chrs, _)) = chrom_lens {
+        if chrs.len() != starts.len() {
+            return Err("chrs must have one entry per interval".to_string());
+        }
+    }
 
     let n = starts.len();
     let mut new_start = starts.to_vec();
@@ -51,7 +116,7 @@ pub fn extend_grp<G: GroupType, T: PositionType>(
             .or_insert((i, i));
     }
 
-    for (_gid, (min_i, max_i)) in extrema {
+    for (_, (min_i, max_i)) in extrema {
         if negative_strand[min_i] {
             new_end  [max_i] = new_end  [max_i] + ext_5;
             new_start[min_i] = new_start[min_i] - ext_3;
@@ -59,7 +124,145 @@ pub fn extend_grp<G: GroupType, T: PositionType>(
             new_start[min_i] = new_start[min_i] - ext_5;
             new_end  [max_i] = new_end  [max_i] + ext_3;
         }
+
+        if let Some((chrs, lens)) = chrom_lens {
+            let chr = chrs[max_i];
+            let len = *lens
+                .get(&chr)
+                .ok_or_else(|| format!("No chromosome length given for chromosome {:?}", chr))?;
+            if new_start[min_i] < T::zero() { new_start[min_i] = T::zero(); }
+            if new_end  [max_i] > len       { new_end  [max_i] = len; }
+        }
     }
 
-    (new_start, new_end)
+    Ok((new_start, new_end))
+}
+
+/// Extends every interval on its own by `ext_5` at its 5′ end and `ext_3` at
+/// its 3′ end. The 5′ end is the start for plus-strand intervals and the end
+/// for minus-strand intervals (`negative_strand[i]`).
+fn extend_rows<T: PositionType>(
+    starts: &[T],
+    ends: &[T],
+    negative_strand: &[bool],
+    ext_3: T,
+    ext_5: T,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    check_row_lengths(starts, ends, negative_strand)?;
+
+    Ok(starts
+        .iter()
+        .zip(ends)
+        .zip(negative_strand)
+        .map(|((&start, &end), &negative)| {
+            if negative {
+                (start - ext_3, end + ext_5)
+            } else {
+                (start - ext_5, end + ext_3)
+            }
+        })
+        .unzip())
+}
+
+/// Extends each interval by `amount` at its 5′ end only: the start on the
+/// plus strand, the end on the minus strand.
+pub fn extend_5prime<T: PositionType>(
+    starts: &[T],
+    ends: &[T],
+    negative_strand: &[bool],
+    amount: T,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    extend_rows(starts, ends, negative_strand, T::zero(), amount)
+}
+
+/// Extends each interval by `amount` at its 3′ end only: the end on the
+/// plus strand, the start on the minus strand.
+pub fn extend_3prime<T: PositionType>(
+    starts: &[T],
+    ends: &[T],
+    negative_strand: &[bool],
+    amount: T,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    extend_rows(starts, ends, negative_strand, amount, T::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extend_5prime_and_3prime_follow_strand() {
+        let starts = [100i64, 100];
+        let ends = [200i64, 200];
+        let negative_strand = [false, true];
+
+        // Plus strand: 5′ is the start. Minus strand: 5′ is the end.
+        let (s, e) = extend_5prime(&starts, &ends, &negative_strand, 10).unwrap();
+        assert_eq!(s, vec![90, 100]);
+        assert_eq!(e, vec![200, 210]);
+
+        let (s, e) = extend_3prime(&starts, &ends, &negative_strand, 10).unwrap();
+        assert_eq!(s, vec![100, 90]);
+        assert_eq!(e, vec![210, 200]);
+    }
+
+    #[test]
+    fn test_extend_rejects_mismatched_lengths() {
+        let starts = [100i64, 100];
+        let ends = [200i64, 200];
+
+        assert!(extend(&starts, &ends, &[false], Some(5), None, None).is_err());
+        assert!(extend_5prime(&starts, &ends[..1], &[false, true], 5).is_err());
+        assert!(extend_grp(&[0u32, 0], &starts, &ends, &[false, true, false], Some(5), None, None, None).is_err());
+        assert!(extend_grp(&[0u32], &starts, &ends, &[false, true], Some(5), None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_extend_grp_clips_to_chrom_lens() {
+        // Two transcripts, 7 and 9, on chromosomes 0 and 1.
+        let groups = [7u32, 7, 9];
+        let chrs = [0u32, 0, 1];
+        let starts = [5i64, 50, 990];
+        let ends = [20i64, 80, 995];
+        let negative_strand = [false, false, false];
+
+        let unclipped =
+            extend_grp(&groups, &starts, &ends, &negative_strand, Some(10), None, None, None).unwrap();
+        assert_eq!(unclipped, (vec![-5, 50, 980], vec![20, 90, 1005]));
+
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 85i64);
+        lens.insert(1u32, 1000i64);
+        let clipped =
+            extend_grp(&groups, &starts, &ends, &negative_strand, Some(10), None, None, Some((&chrs, &lens))).unwrap();
+        assert_eq!(clipped, (vec![0, 50, 980], vec![20, 85, 1000]));
+
+        let short_chrs = [0u32, 0];
+        assert!(extend_grp(&groups, &starts, &ends, &negative_strand, Some(10), None, None, Some((&short_chrs, &lens))).is_err());
+
+        lens.remove(&1);
+        assert!(extend_grp(&groups, &starts, &ends, &negative_strand, Some(10), None, None, Some((&chrs, &lens))).is_err());
+    }
+
+    #[test]
+    fn test_extend_grp_swaps_ends_on_minus_strand() {
+        // Two exons per transcript; transcript 1 is on the minus strand.
+        let groups = [0u32, 0, 1, 1];
+        let starts = [100i64, 300, 100, 300];
+        let ends = [200i64, 400, 200, 400];
+        let negative_strand = [false, false, true, true];
+
+        let (s, e) =
+            extend_grp(&groups, &starts, &ends, &negative_strand, None, Some(5), Some(20), None).unwrap();
+        // Only the outermost rows move; 5′ is the end on the minus strand.
+        assert_eq!(s, vec![80, 300, 95, 300]);
+        assert_eq!(e, vec![200, 405, 200, 420]);
+
+        let (s, e) = extend(&starts, &ends, &negative_strand, None, None, Some(20)).unwrap();
+        assert_eq!(s, vec![80, 280, 100, 300]);
+        assert_eq!(e, vec![200, 400, 220, 420]);
+
+        assert!(extend_grp(&groups, &starts, &ends, &negative_strand, Some(1), Some(5), None, None).is_err());
+        assert!(extend::<i64>(&starts, &ends, &negative_strand, None, None, None).is_err());
+    }
 }
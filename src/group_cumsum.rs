@@ -10,6 +10,24 @@ pub fn sweep_line_cumsum<G, T>(
     strand_flags: &[bool],
     sort: bool,
 ) -> (Vec<u32>, Vec<T>, Vec<T>)
+where
+    G: GroupType,
+    T: PositionType,
+{
+    let (idxs, cumsum_starts, cumsum_ends, _) =
+        sweep_line_cumsum_with_totals(chrs, starts, ends, strand_flags, sort);
+    (idxs, cumsum_starts, cumsum_ends)
+}
+
+/// Same as [`sweep_line_cumsum`], but also returns each group's total length
+/// (the final running total), broadcast to every output row of that group.
+pub fn sweep_line_cumsum_with_totals<G, T>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    strand_flags: &[bool],
+    sort: bool,
+) -> (Vec<u32>, Vec<T>, Vec<T>, Vec<T>)
 where
     G: GroupType,
     T: PositionType,
@@ -18,17 +36,25 @@ where
 
     sort_by_key(&mut ivals, |iv| (iv.chr, iv.start));
 
-    let mut results= Vec::with_capacity(chrs.len());
+    let mut results: Vec<MinInterval<T>> = Vec::with_capacity(chrs.len());
 
     if ivals.is_empty() {
-        return (Vec::with_capacity(chrs.len()),Vec::with_capacity(chrs.len()), Vec::with_capacity(chrs.len()));
+        return (Vec::with_capacity(chrs.len()),Vec::with_capacity(chrs.len()), Vec::with_capacity(chrs.len()), Vec::with_capacity(chrs.len()));
     }
 
+    // Group total per original row, filled in once the group is finished.
+    let mut totals_by_idx = vec![T::zero(); chrs.len()];
+    let mut group_first   = 0;
+
     let mut current_chr   = ivals[0].chr;
     let mut running_total = T::zero();
 
     for iv in ivals {
         if iv.chr != current_chr {
+            for rec in &results[group_first..] {
+                totals_by_idx[rec.idx as usize] = running_total;
+            }
+            group_first   = results.len();
             running_total = T::zero();
             current_chr   = iv.chr;
         }
@@ -41,6 +67,9 @@ where
         results.push(MinInterval {idx: iv.idx, start: s, end: e});
         running_total = e;
     }
+    for rec in &results[group_first..] {
+        totals_by_idx[rec.idx as usize] = running_total;
+    }
 
     if sort {
         sort_by_key(&mut results, |i| i.idx);
@@ -49,12 +78,14 @@ where
     let mut out_idxs    = Vec::with_capacity(results.len());
     let mut out_starts  = Vec::with_capacity(results.len());
     let mut out_ends = Vec::with_capacity(results.len());
+    let mut out_totals = Vec::with_capacity(results.len());
 
     for rec in results {
         out_idxs.push(rec.idx);
         out_starts.push(rec.start);
         out_ends.push(rec.end);
+        out_totals.push(totals_by_idx[rec.idx as usize]);
     }
 
-    (out_idxs, out_starts, out_ends)
+    (out_idxs, out_starts, out_ends, out_totals)
 }
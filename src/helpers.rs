@@ -1,9 +1,11 @@
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::ruranges_structs::OverlapPair;
+use std::str::FromStr;
 
+use crate::ruranges_structs::{CoordinateType, OverlapPair, PositionType, StrandMode};
 
-pub fn keep_last_by_idx(pairs: &mut Vec<OverlapPair>) {
+
+pub fn keep_last_by_idx<T: CoordinateType>(pairs: &mut Vec<OverlapPair<T>>) {
     let mut seen_idx = FxHashSet::default();
     pairs.reverse();
     pairs.retain(|pair| seen_idx.insert(pair.idx));
@@ -11,7 +13,145 @@ pub fn keep_last_by_idx(pairs: &mut Vec<OverlapPair>) {
 }
 
 
-pub fn keep_first_by_idx(pairs: &mut Vec<OverlapPair>) {
+pub fn keep_first_by_idx<T: CoordinateType>(pairs: &mut Vec<OverlapPair<T>>) {
     let mut seen_idx = FxHashSet::default();
     pairs.retain(|pair| seen_idx.insert(pair.idx));
-}
\ No newline at end of file
+}
+
+/// Keeps at most `n` pairs per `idx`, the first ones in the current order.
+/// With `n == 1` this is [`keep_first_by_idx`].
+pub fn keep_first_n_by_idx<T: CoordinateType>(pairs: &mut Vec<OverlapPair<T>>, n: usize) {
+    let mut seen_idx: FxHashMap<u32, usize> = FxHashMap::default();
+    pairs.retain(|pair| {
+        let seen = seen_idx.entry(pair.idx).or_insert(0);
+        *seen += 1;
+        *seen <= n
+    });
+}
+
+/// `a + b` clamped to `[T::min_value(), T::max_value()]`, like
+/// `saturating_add` but also for float coordinates. A negative slack `b`
+/// shrinks `a` and saturates at the bottom instead.
+#[inline]
+pub fn saturating_add_slack<T: CoordinateType>(a: T, b: T) -> T {
+    if b >= T::zero() && a > T::max_value() - b {
+        T::max_value()
+    } else if b < T::zero() && a < T::min_value() - b {
+        T::min_value()
+    } else {
+        a + b
+    }
+}
+
+/// Checks that every interval is non-empty (`start < end`).
+///
+/// Kernels accept zero-length intervals and give them defined behaviour (see
+/// the overlap and tile docs), and reversed ones are skipped or produce
+/// meaningless output, so this check is opt-in for callers that want to
+/// reject both up front.
+pub fn validate_intervals<T: PositionType>(starts: &[T], ends: &[T]) -> Result<(), String> {
+    if starts.len() != ends.len() {
+        return Err(format!(
+            "starts and ends must have the same length ({} != {})",
+            starts.len(),
+            ends.len()
+        ));
+    }
+    match starts.iter().zip(ends).position(|(s, e)| s >= e) {
+        Some(i) => Err(format!(
+            "interval {} is {} (start={}, end={})",
+            i,
+            if starts[i] == ends[i] { "empty" } else { "reversed" },
+            starts[i],
+            ends[i]
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the `chrs`, `starts` and `ends` arrays of one interval set
+/// have the same length. `suffix` is appended to the array names in the
+/// error, e.g. `"2"` for the second set of a binary operation.
+pub fn check_set_lengths(suffix: &str, chrs: usize, starts: usize, ends: usize) -> Result<(), String> {
+    for (name, len) in [("starts", starts), ("ends", ends)] {
+        if len != chrs {
+            return Err(format!(
+                "chrs{suffix} and {name}{suffix} must have the same length \
+                 ({chrs} != {len}, off by {})",
+                chrs.abs_diff(len)
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// [`check_set_lengths`] for both sets of a binary operation.
+pub fn check_pair_lengths<C, T>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+) -> Result<(), String> {
+    check_set_lengths("", chrs.len(), starts.len(), ends.len())?;
+    check_set_lengths("2", chrs2.len(), starts2.len(), ends2.len())
+}
+
+/// Parses `strand_mode` and checks that `strands`, needed by every mode but
+/// `"any"`, has one flag per row of each set.
+pub fn check_strands(
+    strand_mode: &str,
+    strands: Option<(&[bool], &[bool])>,
+    len: usize,
+    len2: usize,
+) -> Result<StrandMode, String> {
+    let mode = StrandMode::from_str(strand_mode)?;
+    match strands {
+        None if mode != StrandMode::Any => {
+            Err(format!("strand_mode \"{strand_mode}\" needs strands and strands2"))
+        }
+        Some((strands, strands2)) if strands.len() != len || strands2.len() != len2 => Err(format!(
+            "strands and strands2 must match the lengths of the interval sets \
+             ({} != {} or {} != {})",
+            strands.len(),
+            len,
+            strands2.len(),
+            len2
+        )),
+        _ => Ok(mode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturating_add_slack_negative_slack() {
+        assert_eq!(saturating_add_slack(10i64, -3), 7);
+        assert_eq!(saturating_add_slack(i64::MAX, -1), i64::MAX - 1);
+        assert_eq!(saturating_add_slack(i16::MIN + 2, -5), i16::MIN);
+        assert_eq!(saturating_add_slack(i32::MAX - 1, 5), i32::MAX);
+    }
+
+    #[test]
+    fn test_validate_intervals() {
+        assert!(validate_intervals(&[0i64, 5], &[1i64, 10]).is_ok());
+        assert_eq!(
+            validate_intervals(&[0i32, 5], &[1i32, 5]).unwrap_err(),
+            "interval 1 is empty (start=5, end=5)"
+        );
+        assert!(validate_intervals(&[3i16], &[2i16]).unwrap_err().contains("reversed"));
+        assert!(validate_intervals(&[0i64], &[]).is_err());
+    }
+
+    #[test]
+    fn test_check_set_lengths() {
+        assert!(check_set_lengths("", 3, 3, 3).is_ok());
+        assert_eq!(
+            check_set_lengths("2", 3, 3, 5).unwrap_err(),
+            "chrs2 and ends2 must have the same length (3 != 5, off by 2)"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! CIGAR-aware splitting of alignments into exonic blocks.
+//!
+//! The BAM decoder itself (`rust-htslib`) is not enabled in `Cargo.toml`, so
+//! this module holds the decoder-independent part of spliced reading: given
+//! each read's reference id, leftmost position and CIGAR string, emit one
+//! interval per aligned block. A BAM reader only has to feed those three
+//! columns into [`extract_spliced_blocks`].
+//!
+//! Writing BAM output (e.g. the reads `overlaps` matched, with the source
+//! header copied and the result indexed) is not provided: unlike the
+//! splitting above, every step of it needs the decoder.
+
+use std::error::Error;
+
+use crate::ruranges_structs::GenomicData;
+
+/// SAM flag bit for reads aligned to the reverse strand.
+pub const FLAG_REVERSE: u16 = 0x10;
+
+/// Read filters for BAM input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BamReadOptions {
+    /// Reads with a lower mapping quality are skipped.
+    pub min_mapq: u8,
+    /// Reads with any of these flag bits set are skipped (e.g. `0x100` for
+    /// secondary alignments).
+    pub exclude_flags: u16,
+}
+
+impl BamReadOptions {
+    /// Whether a read with this mapping quality and flag passes the filters.
+    #[inline]
+    pub fn keeps(&self, mapq: u8, flag: u16) -> bool {
+        mapq >= self.min_mapq && flag & self.exclude_flags == 0
+    }
+}
+
+/// Indices of the reads that pass `options`, and the strand of each kept
+/// read (`true` for forward), as a BAM reader would apply them per record.
+pub fn filter_alignments(
+    mapqs: &[u8],
+    flags: &[u16],
+    options: &BamReadOptions,
+) -> Result<(Vec<u32>, Vec<bool>), String> {
+    if mapqs.len() != flags.len() {
+        return Err("mapqs and flags must have the same length".into());
+    }
+
+    let mut idx = Vec::with_capacity(mapqs.len());
+    let mut strands = Vec::with_capacity(mapqs.len());
+    for (i, (&mapq, &flag)) in mapqs.iter().zip(flags).enumerate() {
+        if options.keeps(mapq, flag) {
+            idx.push(i as u32);
+            strands.push(flag & FLAG_REVERSE == 0);
+        }
+    }
+    Ok((idx, strands))
+}
+
+/// Splits one alignment into its aligned blocks.
+///
+/// `M`, `=` and `X` each emit a block; `N` (intron) and `D` advance the
+/// reference position without emitting anything, so introns become gaps.
+/// `I`, `S`, `H` and `P` do not consume the reference and are skipped.
+pub fn cigar_blocks(start: i32, cigar: &str) -> Result<Vec<(i32, i32)>, String> {
+    let mut blocks = Vec::new();
+    let mut pos = start;
+    let mut len: i32 = 0;
+    let mut have_len = false;
+
+    for c in cigar.chars() {
+        if let Some(d) = c.to_digit(10) {
+            len = len
+                .checked_mul(10)
+                .and_then(|l| l.checked_add(d as i32))
+                .ok_or_else(|| format!("CIGAR operation length overflows in {:?}", cigar))?;
+            have_len = true;
+            continue;
+        }
+
+        if !have_len {
+            return Err(format!("CIGAR operation {:?} has no length in {:?}", c, cigar));
+        }
+
+        match c {
+            'M' | '=' | 'X' => {
+                blocks.push((pos, pos + len));
+                pos += len;
+            }
+            'N' | 'D' => pos += len,
+            'I' | 'S' | 'H' | 'P' => {}
+            _ => return Err(format!("unknown CIGAR operation {:?} in {:?}", c, cigar)),
+        }
+
+        len = 0;
+        have_len = false;
+    }
+
+    if have_len {
+        return Err(format!("CIGAR string {:?} ends with a dangling length", cigar));
+    }
+
+    Ok(blocks)
+}
+
+/// Builds one interval per aligned block for a set of alignments.
+///
+/// `groups` holds the index of the read each block came from, so blocks of
+/// the same spliced read can be stitched back together downstream (e.g. by
+/// `spliced_subsequence`). Unmapped reads (`*` CIGAR) produce no blocks.
+pub fn extract_spliced_blocks(
+    chroms: &[u32],
+    starts: &[i32],
+    cigars: &[&str],
+) -> Result<GenomicData<u32, i32>, Box<dyn Error>> {
+    if chroms.len() != starts.len() || chroms.len() != cigars.len() {
+        return Err("chroms, starts and cigars must have the same length".into());
+    }
+
+    let mut out_chroms = Vec::with_capacity(chroms.len());
+    let mut out_starts = Vec::with_capacity(chroms.len());
+    let mut out_ends = Vec::with_capacity(chroms.len());
+    let mut out_groups = Vec::with_capacity(chroms.len());
+
+    for (read_idx, ((&chrom, &start), &cigar)) in
+        chroms.iter().zip(starts).zip(cigars).enumerate()
+    {
+        if cigar == "*" {
+            continue;
+        }
+        for (block_start, block_end) in cigar_blocks(start, cigar)? {
+            out_chroms.push(chrom);
+            out_starts.push(block_start);
+            out_ends.push(block_end);
+            out_groups.push(read_idx as u32);
+        }
+    }
+
+    let mut data = GenomicData::new(out_chroms, out_starts, out_ends);
+    data.groups = Some(out_groups);
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cigar_blocks_split_on_introns() {
+        let blocks = cigar_blocks(100, "5S10M200N5=3I7X").unwrap();
+        assert_eq!(blocks, vec![(100, 110), (310, 315), (315, 322)]);
+    }
+
+    #[test]
+    fn test_filter_alignments_by_mapq_and_flags() {
+        let options = BamReadOptions { min_mapq: 10, exclude_flags: 0x100 };
+        let (idx, strands) = filter_alignments(&[30, 5, 30, 60], &[0, 0, 0x100, 0x10], &options).unwrap();
+        assert_eq!(idx, vec![0, 3]);
+        assert_eq!(strands, vec![true, false]);
+
+        assert!(filter_alignments(&[30], &[], &options).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Plain-text BED-family readers.
+//!
+//! Chromosome names are turned into dense `u32` codes in order of first
+//! appearance; the names themselves are returned alongside so codes can be
+//! mapped back.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+use super::ChromCodes;
+use crate::ruranges_structs::GenomicData;
+
+/// ENCODE narrowPeak (BED6+4) records.
+///
+/// The BED6 part lives in `data`: `names` and `scores` are always set and
+/// `strands` holds `false` for `-` and `true` otherwise (including `.`).
+pub struct NarrowPeakData {
+    pub data: GenomicData<u32, i32>,
+    /// `chrom_names[code]` is the chromosome behind code `code`.
+    pub chrom_names: Vec<String>,
+    pub signal_value: Vec<f64>,
+    pub p_value: Vec<f64>,
+    pub q_value: Vec<f64>,
+    /// Summit position relative to the start, `-1` if not called.
+    pub peak_offset: Vec<i32>,
+}
+
+pub fn read_narrowpeak_file(f: PathBuf) -> Result<NarrowPeakData, Box<dyn Error>> {
+    read_narrowpeak_stream(BufReader::new(File::open(&f)?), &f.display().to_string())
+}
+
+/// Like [`read_narrowpeak_file`], but from any buffered reader (e.g. stdin).
+/// `source` names the input in error messages.
+pub fn read_narrowpeak_stream<R: BufRead>(reader: R, source: &str) -> Result<NarrowPeakData, Box<dyn Error>> {
+    let mut chrom_codes = ChromCodes::default();
+
+    let mut chroms = Vec::new();
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    let mut strands = Vec::new();
+    let mut names = Vec::new();
+    let mut scores = Vec::new();
+    let mut signal_value = Vec::new();
+    let mut p_value = Vec::new();
+    let mut q_value = Vec::new();
+    let mut peak_offset = Vec::new();
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.is_empty() || line.starts_with('#') || line.starts_with("track") || line.starts_with("browser") {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 10 {
+            return Err(format!(
+                "{}:{}: expected 10 narrowPeak columns, found {}",
+                source,
+                line_no + 1,
+                fields.len()
+            )
+            .into());
+        }
+        let parse_err = |column: &str| format!("{}:{}: invalid {}", source, line_no + 1, column);
+
+        chroms.push(chrom_codes.code(fields[0]));
+        starts.push(fields[1].parse::<i32>().map_err(|_| parse_err("start"))?);
+        ends.push(fields[2].parse::<i32>().map_err(|_| parse_err("end"))?);
+        names.push(fields[3].to_string());
+        scores.push(fields[4].parse::<f64>().map_err(|_| parse_err("score"))?);
+        strands.push(fields[5] != "-");
+        signal_value.push(fields[6].parse::<f64>().map_err(|_| parse_err("signalValue"))?);
+        p_value.push(fields[7].parse::<f64>().map_err(|_| parse_err("pValue"))?);
+        q_value.push(fields[8].parse::<f64>().map_err(|_| parse_err("qValue"))?);
+        peak_offset.push(fields[9].parse::<i32>().map_err(|_| parse_err("peak"))?);
+    }
+
+    let mut data = GenomicData::new(chroms, starts, ends).with_names(names).with_scores(scores);
+    data.strands = Some(strands);
+
+    Ok(NarrowPeakData { data, chrom_names: chrom_codes.into_names(), signal_value, p_value, q_value, peak_offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_narrowpeak_file() {
+        let path = std::env::temp_dir().join(format!("ruranges_narrowpeak_{}.bed", std::process::id()));
+        std::fs::write(
+            &path,
+            "track name=peaks\n\
+             chr2\t100\t200\tp1\t0\t.\t5.5\t10.1\t3.2\t50\n\
+             chr1\t10\t20\tp2\t900\t-\t1.0\t2.0\t-1\t-1\n\
+             chr2\t300\t400\tp3\t10\t+\t2.5\t3.0\t1.5\t20\n",
+        )
+        .unwrap();
+
+        let peaks = read_narrowpeak_file(path.clone()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(peaks.chrom_names, vec!["chr2", "chr1"]);
+        assert_eq!(peaks.data.chroms, vec![0, 1, 0]);
+        assert_eq!(peaks.data.starts, vec![100, 10, 300]);
+        assert_eq!(peaks.data.strands, Some(vec![true, false, true]));
+        assert_eq!(peaks.data.scores, Some(vec![0.0, 900.0, 10.0]));
+        assert_eq!(peaks.signal_value, vec![5.5, 1.0, 2.5]);
+        assert_eq!(peaks.q_value, vec![3.2, -1.0, 1.5]);
+        assert_eq!(peaks.peak_offset, vec![50, -1, 20]);
+    }
+}
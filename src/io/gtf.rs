@@ -0,0 +1,214 @@
+//! GTF/GFF2 reader and writer.
+//!
+//! GTF coordinates are 1-based and inclusive; they are converted to the
+//! 0-based half-open intervals used everywhere else (`start - 1`, `end`),
+//! and back when writing.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+use super::ChromCodes;
+use crate::ruranges_structs::GenomicData;
+
+/// GTF records. `data` carries `strands` (`false` for `-`), `scores` (`NaN`
+/// for `.`) and the raw attribute column in `attributes`.
+pub struct GtfData {
+    pub data: GenomicData<u32, i32>,
+    /// `chrom_names[code]` is the chromosome behind code `code`.
+    pub chrom_names: Vec<String>,
+    /// Column 3 of each kept row (`exon`, `CDS`, ...).
+    pub features: Vec<String>,
+}
+
+pub fn read_gtf_file(f: PathBuf) -> Result<GtfData, Box<dyn Error>> {
+    read_gtf(f, None)
+}
+
+/// Like [`read_gtf_file`], but keeps only rows whose feature column is one
+/// of `feature_types`. Rows are dropped before their coordinates and
+/// attributes are parsed.
+pub fn read_gtf_file_filtered(f: PathBuf, feature_types: &[&str]) -> Result<GtfData, Box<dyn Error>> {
+    read_gtf(f, Some(feature_types))
+}
+
+fn read_gtf(f: PathBuf, feature_types: Option<&[&str]>) -> Result<GtfData, Box<dyn Error>> {
+    read_gtf_stream(BufReader::new(File::open(&f)?), &f.display().to_string(), feature_types)
+}
+
+/// GTF records from any buffered reader (e.g. stdin), optionally keeping
+/// only `feature_types`. `source` names the input in error messages.
+pub fn read_gtf_stream<R: BufRead>(
+    reader: R,
+    source: &str,
+    feature_types: Option<&[&str]>,
+) -> Result<GtfData, Box<dyn Error>> {
+    let mut chrom_codes = ChromCodes::default();
+
+    let mut chroms = Vec::new();
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    let mut strands = Vec::new();
+    let mut scores = Vec::new();
+    let mut attributes = Vec::new();
+    let mut features = Vec::new();
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.splitn(9, '\t').collect();
+        if fields.len() != 9 {
+            return Err(format!(
+                "{}:{}: expected 9 GTF columns, found {}",
+                source,
+                line_no + 1,
+                fields.len()
+            )
+            .into());
+        }
+        if let Some(types) = feature_types {
+            if !types.contains(&fields[2]) {
+                continue;
+            }
+        }
+        let parse_err = |column: &str| format!("{}:{}: invalid {}", source, line_no + 1, column);
+
+        let start = fields[3].parse::<i32>().map_err(|_| parse_err("start"))?;
+        let end = fields[4].parse::<i32>().map_err(|_| parse_err("end"))?;
+        let score = match fields[5] {
+            "." => f64::NAN,
+            s => s.parse::<f64>().map_err(|_| parse_err("score"))?,
+        };
+
+        chroms.push(chrom_codes.code(fields[0]));
+        starts.push(start - 1);
+        ends.push(end);
+        scores.push(score);
+        strands.push(fields[6] != "-");
+        features.push(fields[2].to_string());
+        attributes.push(fields[8].to_string());
+    }
+
+    let mut data = GenomicData::new(chroms, starts, ends).with_scores(scores).with_attributes(attributes);
+    data.strands = Some(strands);
+
+    Ok(GtfData { data, chrom_names: chrom_codes.into_names(), features })
+}
+
+/// Writes intervals as a GTF file, one row per interval.
+///
+/// Chromosome codes are mapped back to names through `chrom_names`
+/// (`chrom_names[code]`), strands are `+` for `true` and `-` for `false`, and
+/// the source, score and frame columns are written as `.`. `attributes` is
+/// copied verbatim into column 9.
+#[allow(clippy::too_many_arguments)]
+pub fn write_gtf_file(
+    f: PathBuf,
+    chrs: &[u32],
+    starts: &[i32],
+    ends: &[i32],
+    strands: &[bool],
+    features: &[&str],
+    attributes: &[&str],
+    chrom_names: &[String],
+) -> Result<(), Box<dyn Error>> {
+    let n = chrs.len();
+    for (column, len) in [
+        ("starts", starts.len()),
+        ("ends", ends.len()),
+        ("strands", strands.len()),
+        ("features", features.len()),
+        ("attributes", attributes.len()),
+    ] {
+        if len != n {
+            return Err(format!("chrs and {} must have the same length ({} != {})", column, n, len).into());
+        }
+    }
+
+    let mut out = BufWriter::new(File::create(&f)?);
+    for i in 0..n {
+        let chrom = chrom_names
+            .get(chrs[i] as usize)
+            .ok_or_else(|| format!("no chromosome name for code {}", chrs[i]))?;
+        writeln!(
+            out,
+            "{}\t.\t{}\t{}\t{}\t.\t{}\t.\t{}",
+            chrom,
+            features[i],
+            starts[i] + 1,
+            ends[i],
+            if strands[i] { '+' } else { '-' },
+            attributes[i],
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_gtf_file_filtered() {
+        let path = std::env::temp_dir().join(format!("ruranges_gtf_{}.gtf", std::process::id()));
+        std::fs::write(
+            &path,
+            "#!genome-build test\n\
+             chr1\tsrc\tgene\t11\t100\t.\t+\t.\tgene_id \"g1\";\n\
+             chr1\tsrc\texon\t11\t20\t.\t+\t.\tgene_id \"g1\"; exon_number \"1\";\n\
+             chr2\tsrc\tCDS\t5\t9\t0.5\t-\t0\tgene_id \"g2\";\n\
+             chr2\tsrc\texon\t1\t9\t.\t-\t.\tgene_id \"g2\";\n",
+        )
+        .unwrap();
+
+        let all = read_gtf_file(path.clone()).unwrap();
+        let exons = read_gtf_file_filtered(path.clone(), &["exon"]).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(all.features, vec!["gene", "exon", "CDS", "exon"]);
+        assert_eq!(all.data.starts, vec![10, 10, 4, 0]);
+        assert_eq!(all.data.ends, vec![100, 20, 9, 9]);
+
+        assert_eq!(exons.chrom_names, vec!["chr1", "chr2"]);
+        assert_eq!(exons.data.chroms, vec![0, 1]);
+        assert_eq!(exons.data.strands, Some(vec![true, false]));
+        assert_eq!(
+            exons.data.attributes.as_deref().unwrap()[0],
+            "gene_id \"g1\"; exon_number \"1\";"
+        );
+    }
+
+    #[test]
+    fn test_write_gtf_file_round_trips() {
+        let path = std::env::temp_dir().join(format!("ruranges_gtf_write_{}.gtf", std::process::id()));
+        let chrom_names = vec!["chr1".to_string(), "chrX".to_string()];
+
+        write_gtf_file(
+            path.clone(),
+            &[1, 0],
+            &[0, 10],
+            &[9, 20],
+            &[false, true],
+            &["exon", "CDS"],
+            &["gene_id \"g2\";", "gene_id \"g1\";"],
+            &chrom_names,
+        )
+        .unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        let gtf = read_gtf_file(path.clone()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(written.lines().next().unwrap(), "chrX\t.\texon\t1\t9\t.\t-\t.\tgene_id \"g2\";");
+        assert_eq!(gtf.chrom_names, vec!["chrX", "chr1"]);
+        assert_eq!(gtf.data.starts, vec![0, 10]);
+        assert_eq!(gtf.data.ends, vec![9, 20]);
+        assert_eq!(gtf.features, vec!["exon", "CDS"]);
+
+        assert!(write_gtf_file(path, &[2], &[0], &[1], &[true], &["exon"], &[""], &chrom_names).is_err());
+    }
+}
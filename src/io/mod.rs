@@ -0,0 +1,99 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::PathBuf;
+
+use rustc_hash::FxHashMap;
+
+use crate::ruranges_structs::GenomicData;
+
+pub mod bam;
+pub mod bed;
+pub mod gtf;
+
+/// Intervals read by [`read_genomics_stream`], with the chromosome names
+/// behind their codes.
+pub struct GenomicRecords {
+    pub data: GenomicData<u32, i32>,
+    /// `chrom_names[code]` is the chromosome behind code `code`.
+    pub chrom_names: Vec<String>,
+}
+
+/// Reads `format` (`"narrowpeak"` or `"gtf"`) from any reader.
+///
+/// Both formats are parsed line by line, so the reader needs neither `Seek`
+/// nor to be a file; pipes and stdin work. Format-specific columns beyond
+/// what [`GenomicData`] holds are dropped.
+pub fn read_genomics_stream<R: Read>(reader: R, format: &str) -> Result<GenomicRecords, Box<dyn Error>> {
+    read_genomics_named(reader, format, "<stream>")
+}
+
+/// [`read_genomics_stream`] on stdin, e.g. at the end of a shell pipeline.
+pub fn read_genomics_stdin(format: &str) -> Result<GenomicRecords, Box<dyn Error>> {
+    read_genomics_named(std::io::stdin().lock(), format, "<stdin>")
+}
+
+/// [`read_genomics_stream`] on a file; errors name the path.
+pub fn read_genomics_file(f: PathBuf, format: &str) -> Result<GenomicRecords, Box<dyn Error>> {
+    let file = File::open(&f)?;
+    read_genomics_named(file, format, &f.display().to_string())
+}
+
+fn read_genomics_named<R: Read>(reader: R, format: &str, source: &str) -> Result<GenomicRecords, Box<dyn Error>> {
+    let reader = BufReader::new(reader);
+    match format.to_ascii_lowercase().as_str() {
+        "narrowpeak" => {
+            let peaks = bed::read_narrowpeak_stream(reader, source)?;
+            Ok(GenomicRecords { data: peaks.data, chrom_names: peaks.chrom_names })
+        }
+        "gtf" => {
+            let gtf = gtf::read_gtf_stream(reader, source, None)?;
+            Ok(GenomicRecords { data: gtf.data, chrom_names: gtf.chrom_names })
+        }
+        other => Err(format!("unsupported format {:?}, expected \"narrowpeak\" or \"gtf\"", other).into()),
+    }
+}
+
+/// Dense `u32` codes for chromosome names, assigned in order of first
+/// appearance.
+#[derive(Default)]
+pub(crate) struct ChromCodes {
+    codes: FxHashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl ChromCodes {
+    pub(crate) fn code(&mut self, name: &str) -> u32 {
+        if let Some(&code) = self.codes.get(name) {
+            return code;
+        }
+        let code = self.names.len() as u32;
+        self.codes.insert(name.to_string(), code);
+        self.names.push(name.to_string());
+        code
+    }
+
+    /// `names[code]` is the chromosome behind `code`.
+    pub(crate) fn into_names(self) -> Vec<String> {
+        self.names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_genomics_stream_from_memory() {
+        let gtf = "chr1\tsrc\texon\t11\t20\t.\t+\t.\tgene_id \"g1\";\n\
+                   chr2\tsrc\texon\t1\t9\t.\t-\t.\tgene_id \"g2\";\n";
+        let records = read_genomics_stream(gtf.as_bytes(), "GTF").unwrap();
+        assert_eq!(records.chrom_names, vec!["chr1", "chr2"]);
+        assert_eq!(records.data.starts, vec![10, 0]);
+        assert_eq!(records.data.ends, vec![20, 9]);
+
+        let err = read_genomics_stream("chr1\t1\n".as_bytes(), "narrowpeak").err().unwrap();
+        assert!(err.to_string().starts_with("<stream>:1:"), "{err}");
+        assert!(read_genomics_stream(&b""[..], "bam").is_err());
+    }
+}
@@ -2,9 +2,13 @@ pub mod boundary;
 pub mod cluster;
 pub mod complement;
 pub mod complement_single;
+pub mod coverage;
+pub mod evaluate;
 pub mod extend;
 pub mod max_disjoint;
 pub mod merge;
+pub mod midpoints;
+pub mod multiprocessing;
 pub mod nearest;
 pub mod outside_bounds;
 pub mod overlaps;
@@ -12,6 +16,7 @@ pub mod overlaps_simple;
 pub mod ruranges_structs;
 pub mod sorts;
 pub mod spliced_subsequence;
+pub mod sample;
 pub mod split;
 pub mod subtract;
 pub mod tile;
@@ -19,6 +24,7 @@ pub mod group_cumsum;
 pub mod map_to_global;
 
 pub mod helpers;
+pub mod io;
 
 pub mod bindings;
 pub mod numpy_bindings;
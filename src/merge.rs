@@ -1,26 +1,153 @@
-use crate::{ruranges_structs::{GroupType, PositionType}, sorts};
+use std::str::FromStr;
 
-pub fn sweep_line_merge<G: GroupType, T: PositionType>(
+use crate::{ruranges_structs::{CoordinateType, GroupType, PositionType}, sorts};
+
+/// Which member interval is reported as the representative of a merged region.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MergeAnchor {
+    /// The member with the smallest start (the first one in the region).
+    First,
+    /// The member with the largest end. This was the only behaviour before
+    /// the anchor could be chosen.
+    Last,
+    /// The widest member.
+    Largest,
+    /// The member with the highest score.
+    Score,
+}
+
+impl FromStr for MergeAnchor {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(MergeAnchor::First),
+            "last" => Ok(MergeAnchor::Last),
+            "largest" => Ok(MergeAnchor::Largest),
+            "score" => Ok(MergeAnchor::Score),
+            _ => Err("Invalid anchor string"),
+        }
+    }
+}
+
+/// Running choice of the representative while a region is being swept.
+struct Representative<'a, T: CoordinateType> {
+    anchor: MergeAnchor,
+    starts: &'a [T],
+    ends: &'a [T],
+    scores: Option<&'a [f64]>,
+}
+
+impl<T: CoordinateType> Representative<'_, T> {
+    /// Whether `candidate` should replace `current`; ties keep `current`,
+    /// except for `Last`, where the later of two equal ends wins.
+    fn replaces(&self, candidate: u32, current: u32) -> bool {
+        let (c, r) = (candidate as usize, current as usize);
+        match self.anchor {
+            MergeAnchor::First => false,
+            MergeAnchor::Last => self.ends[c] >= self.ends[r],
+            MergeAnchor::Largest => {
+                self.ends[c] - self.starts[c] > self.ends[r] - self.starts[r]
+            }
+            MergeAnchor::Score => {
+                let scores = self.scores.expect("anchor \"score\" requires scores");
+                scores[c] > scores[r]
+            }
+        }
+    }
+}
+
+/// Merges overlapping intervals per chromosome.
+///
+/// `slack` only decides *which* intervals are merged: two intervals end up
+/// in the same region when the gap between them is at most `slack` bases
+/// (`next_start <= current_end + slack`). It never widens the output; every
+/// region spans from the smallest original start to the largest original end
+/// of its members. Since it is a plain distance test between neighbours, slack
+/// is symmetric: it is not a one-sided widening of the ends.
+///
+/// Intervals are half-open, so `[0,10)` and `[10,20)` share no base but
+/// touch. At `slack == 0` touching intervals are only joined with
+/// `merge_touching` (off by default in the bindings); otherwise only
+/// intervals that share at least one base are merged. A positive `slack`
+/// always joins a gap of exactly `slack`, with or without the flag.
+///
+/// Zero-length intervals follow the same rule: a point joins any region it
+/// lies in (or touches, when touching intervals are joined), and an isolated
+/// point becomes a zero-length region with a count of 1.
+///
+/// `anchor` picks the index reported for each region: `"first"` (smallest
+/// start, the default in the bindings), `"last"` (largest end, what merge
+/// returned before `anchor` existed), `"largest"` (widest member) or
+/// `"score"` (highest entry in `scores`, which must then be given).
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_line_merge<G: GroupType, T: CoordinateType>(
     chrs: &[G],
     starts: &[T],
     ends: &[T],
     slack: T,
+    presorted: bool,
+    merge_touching: bool,
+    anchor: &str,
+    scores: Option<&[f64]>,
 ) -> (Vec<u32>, Vec<T>, Vec<T>, Vec<u32>) {
+    let anchor = MergeAnchor::from_str(anchor).expect("invalid anchor string");
+    if anchor == MergeAnchor::Score {
+        assert!(scores.is_some(), "anchor \"score\" requires scores");
+    }
+    let rep = Representative { anchor, starts, ends, scores };
+
+    if presorted {
+        return merge_presorted_scan(chrs, starts, ends, slack, merge_touching, &rep);
+    }
+
+    let (idx, m_starts, m_ends, counts, _) = merge_sweep(chrs, starts, ends, slack, merge_touching, &rep, None);
+    (idx, m_starts, m_ends, counts)
+}
+
+/// Per-region sums and maxima of the values folded into [`merge_sweep`].
+type RegionValues = (Vec<f64>, Vec<f64>);
+
+/// The sorting sweep behind [`sweep_line_merge`]. With `values`, their sum
+/// and maximum over each region's members are accumulated in the same pass.
+fn merge_sweep<G: GroupType, T: CoordinateType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+    rep: &Representative<'_, T>,
+    values: Option<&[f64]>,
+) -> (Vec<u32>, Vec<T>, Vec<T>, Vec<u32>, RegionValues) {
     let mut out_indices = Vec::with_capacity(chrs.len());
     let mut out_starts = Vec::with_capacity(chrs.len());
     let mut out_ends = Vec::with_capacity(chrs.len());
     let mut counts = Vec::with_capacity(chrs.len());
+    let mut sums = Vec::new();
+    let mut maxs = Vec::new();
 
     if chrs.is_empty() {
-        return (out_indices, out_starts, out_ends, counts);
+        return (out_indices, out_starts, out_ends, counts, (sums, maxs));
     };
 
-    let events = sorts::build_sorted_events_single_collection(chrs, starts, ends, slack);
+    let value = |idx: u32| values.map_or(0.0, |v| v[idx as usize]);
+
+    // Ends are shifted by `slack`; processing starts before ends at equal
+    // positions makes a gap of exactly `slack` still merge. Processing ends
+    // first closes the region before a touching interval opens.
+    let joins_equal = sorts::joins_gap_of_slack(slack, merge_touching);
+    let events = sorts::build_sorted_events_gap_rule(chrs, starts, ends, slack, merge_touching);
+    // With ends first, a zero-length interval's end event precedes its own
+    // start, so such points are handled entirely at their start event.
+    let is_point = |idx: u32| !joins_equal && starts[idx as usize] == ends[idx as usize];
 
     let mut current_chr = events.first().unwrap().chr;
     let mut current_start: T = T::zero();
+    let mut current_rep = 0u32;
     let mut active_count = 0;
     let mut current_cluster_count = 0;
+    let mut current_sum = 0.0;
+    let mut current_max = f64::NEG_INFINITY;
 
     for e in events {
         if e.chr != current_chr {
@@ -32,21 +159,482 @@ pub fn sweep_line_merge<G: GroupType, T: PositionType>(
         if active_count == 0 {
             current_start = e.pos;
             current_cluster_count = 0;
+            current_sum = 0.0;
+            current_max = f64::NEG_INFINITY;
+        }
+
+        if is_point(e.idx) {
+            if !e.is_start {
+                continue;
+            }
+            if active_count == 0 {
+                out_indices.push(e.idx);
+                out_starts.push(e.pos);
+                out_ends.push(e.pos);
+                counts.push(1);
+                if values.is_some() {
+                    sums.push(value(e.idx));
+                    maxs.push(value(e.idx));
+                }
+            } else {
+                if rep.replaces(e.idx, current_rep) {
+                    current_rep = e.idx;
+                }
+                current_cluster_count += 1;
+                current_sum += value(e.idx);
+                current_max = current_max.max(value(e.idx));
+            }
+            continue;
         }
 
         if e.is_start {
+            if active_count == 0 || rep.replaces(e.idx, current_rep) {
+                current_rep = e.idx;
+            }
             active_count += 1;
             current_cluster_count += 1;
+            current_sum += value(e.idx);
+            current_max = current_max.max(value(e.idx));
         } else {
             active_count -= 1;
             if active_count == 0 {
-                out_indices.push(e.idx);
+                // The closing end event is the member with the largest end.
+                if rep.anchor == MergeAnchor::Last {
+                    current_rep = e.idx;
+                }
+                out_indices.push(current_rep);
                 out_starts.push(current_start);
                 out_ends.push(e.pos - slack);
                 counts.push(current_cluster_count);
+                if values.is_some() {
+                    sums.push(current_sum);
+                    maxs.push(current_max);
+                }
+            }
+        }
+    }
+
+    (out_indices, out_starts, out_ends, counts, (sums, maxs))
+}
+
+/// Per-region `(idx, start, end, count, sum, max, mean)`: the output of
+/// [`sweep_line_merge`] plus the sum, maximum and mean of `values` over the
+/// members of each region.
+pub type MergedValues<T> = (Vec<u32>, Vec<T>, Vec<T>, Vec<u32>, Vec<f64>, Vec<f64>, Vec<f64>);
+
+/// [`sweep_line_merge`] that also reduces one value per input row over each
+/// merged region, e.g. summing or taking the peak of a signal column. The
+/// values are accumulated in the merge sweep itself, so regions and
+/// representatives are the same as without `values`.
+///
+/// Fails when `values` does not have one entry per interval.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_line_merge_values<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+    anchor: &str,
+    scores: Option<&[f64]>,
+    values: &[f64],
+) -> Result<MergedValues<T>, String> {
+    if values.len() != chrs.len() {
+        return Err(format!(
+            "values must have one entry per interval: got {}, expected {}",
+            values.len(),
+            chrs.len()
+        ));
+    }
+    let anchor = MergeAnchor::from_str(anchor).map_err(str::to_string)?;
+    if anchor == MergeAnchor::Score && scores.is_none() {
+        return Err("anchor \"score\" requires scores".to_string());
+    }
+    let rep = Representative { anchor, starts, ends, scores };
+
+    let (idx, m_starts, m_ends, counts, (sums, maxs)) =
+        merge_sweep(chrs, starts, ends, slack, merge_touching, &rep, Some(values));
+    let means = sums.iter().zip(&counts).map(|(&s, &c)| s / c as f64).collect();
+
+    Ok((idx, m_starts, m_ends, counts, sums, maxs, means))
+}
+
+/// For every input row, the id of the merged region it belongs to: its
+/// position in the output of [`sweep_line_merge`] called with the same
+/// `slack` and `merge_touching`. Computed in the same sweep, by recording the
+/// current region at each start event.
+pub fn sweep_line_merge_ids<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+) -> Vec<u32> {
+    let mut merge_ids = vec![0; chrs.len()];
+
+    if chrs.is_empty() {
+        return merge_ids;
+    };
+
+    let joins_equal = sorts::joins_gap_of_slack(slack, merge_touching);
+    let events = sorts::build_sorted_events_gap_rule(chrs, starts, ends, slack, merge_touching);
+    let is_point = |idx: u32| !joins_equal && starts[idx as usize] == ends[idx as usize];
+
+    let mut current_chr = events.first().unwrap().chr;
+    let mut current_region = 0u32;
+    let mut active_count = 0;
+
+    for e in events {
+        if e.chr != current_chr {
+            active_count = 0;
+            current_chr = e.chr;
+        }
+
+        if is_point(e.idx) {
+            if !e.is_start {
+                continue;
+            }
+            merge_ids[e.idx as usize] = current_region;
+            // An isolated point is a region of its own.
+            if active_count == 0 {
+                current_region += 1;
+            }
+            continue;
+        }
+
+        if e.is_start {
+            merge_ids[e.idx as usize] = current_region;
+            active_count += 1;
+        } else {
+            active_count -= 1;
+            if active_count == 0 {
+                current_region += 1;
             }
         }
     }
 
+    merge_ids
+}
+
+/// [`sweep_line_merge`] for input already sorted by chromosome and start,
+/// with the defaults of the bindings: no slack, touching intervals kept
+/// apart and the first member as representative. Runs in O(n) without
+/// sorting.
+pub fn sweep_line_merge_presorted<G: GroupType, T: CoordinateType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+) -> (Vec<u32>, Vec<T>, Vec<T>, Vec<u32>) {
+    let rep = Representative { anchor: MergeAnchor::First, starts, ends, scores: None };
+    merge_presorted_scan(chrs, starts, ends, T::zero(), false, &rep)
+}
+
+/// Linear scan over `(chr, start, end)` triples that are already sorted by
+/// chromosome and start. Produces the same output as the event sweep without
+/// building or sorting any events.
+fn merge_presorted_scan<G: GroupType, T: CoordinateType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+    rep: &Representative<'_, T>,
+) -> (Vec<u32>, Vec<T>, Vec<T>, Vec<u32>) {
+    let mut out_indices = Vec::with_capacity(chrs.len());
+    let mut out_starts = Vec::with_capacity(chrs.len());
+    let mut out_ends = Vec::with_capacity(chrs.len());
+    let mut counts = Vec::with_capacity(chrs.len());
+
+    if chrs.is_empty() {
+        return (out_indices, out_starts, out_ends, counts);
+    };
+
+    let mut current_chr = chrs[0];
+    let mut current_start = starts[0];
+    let mut current_end = ends[0];
+    let mut current_idx = 0u32;
+    let mut current_cluster_count = 1;
+
+    for i in 1..chrs.len() {
+        // Same rule as the sweep: a gap of at most `slack` merges, except
+        // that touching intervals are kept apart at `slack == 0` without
+        // `merge_touching`.
+        let joins = if sorts::joins_gap_of_slack(slack, merge_touching) {
+            starts[i] <= current_end + slack
+        } else {
+            starts[i] < current_end + slack
+        };
+        if chrs[i] == current_chr && joins {
+            if rep.replaces(i as u32, current_idx) {
+                current_idx = i as u32;
+            }
+            if ends[i] > current_end {
+                current_end = ends[i];
+            }
+            current_cluster_count += 1;
+            continue;
+        }
+
+        out_indices.push(current_idx);
+        out_starts.push(current_start);
+        out_ends.push(current_end);
+        counts.push(current_cluster_count);
+
+        current_chr = chrs[i];
+        current_start = starts[i];
+        current_end = ends[i];
+        current_idx = i as u32;
+        current_cluster_count = 1;
+    }
+
+    out_indices.push(current_idx);
+    out_starts.push(current_start);
+    out_ends.push(current_end);
+    counts.push(current_cluster_count);
+
     (out_indices, out_starts, out_ends, counts)
 }
+
+/// Overall extent of every chromosome: one row per distinct `chr`, in
+/// ascending order, with the smallest start, the largest end and the number
+/// of intervals. Unlike [`sweep_line_merge`], intervals do not need to
+/// overlap to count towards the span, so exons give their gene boundaries.
+pub fn sweep_line_span<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+) -> (Vec<G>, Vec<T>, Vec<T>, Vec<u32>) {
+    let mut out_chrs = Vec::new();
+    let mut out_starts: Vec<T> = Vec::new();
+    let mut out_ends: Vec<T> = Vec::new();
+    let mut counts = Vec::new();
+
+    for i in sorts::build_sorted_groups(chrs) {
+        let i = i as usize;
+        if out_chrs.last() == Some(&chrs[i]) {
+            let last = out_chrs.len() - 1;
+            out_starts[last] = out_starts[last].min(starts[i]);
+            out_ends[last] = out_ends[last].max(ends[i]);
+            counts[last] += 1;
+        } else {
+            out_chrs.push(chrs[i]);
+            out_starts.push(starts[i]);
+            out_ends.push(ends[i]);
+            counts.push(1);
+        }
+    }
+
+    (out_chrs, out_starts, out_ends, counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_gap_equal_to_slack_is_joined() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 15];
+        let ends = [10i64, 20];
+
+        for presorted in [false, true] {
+            let (_, m_starts, m_ends, counts) = sweep_line_merge(&chrs, &starts, &ends, 5, presorted, true, "first", None);
+            assert_eq!(m_starts, vec![0]);
+            assert_eq!(m_ends, vec![20]);
+            assert_eq!(counts, vec![2]);
+
+            let (_, m_starts, m_ends, counts) = sweep_line_merge(&chrs, &starts, &ends, 4, presorted, true, "first", None);
+            assert_eq!(m_starts, vec![0, 15]);
+            assert_eq!(m_ends, vec![10, 20]);
+            assert_eq!(counts, vec![1, 1]);
+        }
+    }
+
+    #[test]
+    fn test_merge_default_keeps_touching_intervals_apart() {
+        // The bindings' default: no slack, merge_touching off. Bookended
+        // intervals stay separate, overlapping ones merge.
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 10, 15];
+        let ends = [10i64, 20, 30];
+
+        for presorted in [false, true] {
+            let (idx, m_starts, m_ends, counts) =
+                sweep_line_merge(&chrs, &starts, &ends, 0, presorted, false, "first", None);
+            assert_eq!(idx, vec![0, 1]);
+            assert_eq!(m_starts, vec![0, 10]);
+            assert_eq!(m_ends, vec![10, 30]);
+            assert_eq!(counts, vec![1, 2]);
+        }
+        assert_eq!(sweep_line_merge_ids(&chrs, &starts, &ends, 0, false), vec![0, 1, 1]);
+
+        // With slack the gap rule is `<=`, with or without the flag.
+        let (_, m_starts, _, counts) =
+            sweep_line_merge(&chrs[..2], &[0i64, 15], &[10i64, 20], 5, false, false, "first", None);
+        assert_eq!((m_starts, counts), (vec![0], vec![2]));
+        assert_eq!(sweep_line_merge_ids(&chrs[..2], &[0i64, 15], &[10i64, 20], 5, false), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_merge_ids_index_merged_regions() {
+        let chrs = [0u32, 0, 0, 1, 0, 0];
+        let starts = [20i64, 0, 5, 0, 30, 40];
+        let ends = [25i64, 10, 12, 10, 30, 45];
+
+        for merge_touching in [true, false] {
+            let (_, m_starts, m_ends, counts) =
+                sweep_line_merge(&chrs, &starts, &ends, 0, false, merge_touching, "first", None);
+            let ids = sweep_line_merge_ids(&chrs, &starts, &ends, 0, merge_touching);
+
+            assert_eq!(ids, vec![1, 0, 0, 4, 2, 3]);
+            for (i, &id) in ids.iter().enumerate() {
+                assert!(m_starts[id as usize] <= starts[i] && ends[i] <= m_ends[id as usize]);
+            }
+            for (region, &count) in counts.iter().enumerate() {
+                assert_eq!(ids.iter().filter(|&&id| id as usize == region).count(), count as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn test_span_covers_non_overlapping_members() {
+        let chrs = [3u32, 1, 3, 1, 3];
+        let starts = [50i64, 0, 10, 100, 30];
+        let ends = [60i64, 10, 20, 110, 70];
+
+        let (s_chrs, s_starts, s_ends, counts) = sweep_line_span(&chrs, &starts, &ends);
+
+        assert_eq!(s_chrs, vec![1, 3]);
+        assert_eq!(s_starts, vec![0, 10]);
+        assert_eq!(s_ends, vec![110, 70]);
+        assert_eq!(counts, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_merge_float_coordinates() {
+        let chrs = [0u32, 0, 0];
+        let starts = [0.1f32, 0.15, 0.5];
+        let ends = [0.2f32, 0.3, 0.6];
+
+        for presorted in [false, true] {
+            let (_, m_starts, m_ends, counts) =
+                sweep_line_merge(&chrs, &starts, &ends, 0.0, presorted, true, "first", None);
+            assert_eq!(m_starts, vec![0.1, 0.5]);
+            assert_eq!(m_ends, vec![0.3, 0.6]);
+            assert_eq!(counts, vec![2, 1]);
+        }
+    }
+
+    #[test]
+    fn test_merge_presorted_matches_sweep() {
+        let chrs = [0u32, 0, 0, 1, 1];
+        let starts = [0i64, 5, 20, 0, 10];
+        let ends = [10i64, 12, 30, 10, 15];
+
+        assert_eq!(
+            sweep_line_merge_presorted(&chrs, &starts, &ends),
+            sweep_line_merge(&chrs, &starts, &ends, 0, false, false, "first", None)
+        );
+    }
+
+    #[test]
+    fn test_merge_anchor_strategies() {
+        // One region: [0,10) first, [2,30) widest, [5,32) furthest right.
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 2, 5];
+        let ends = [10i64, 30, 32];
+        let scores = [0.5, 0.1, 0.9];
+
+        for presorted in [false, true] {
+            for (anchor, expected) in [("first", 0), ("last", 2), ("largest", 1), ("score", 2)] {
+                let (idx, _, _, _) =
+                    sweep_line_merge(&chrs, &starts, &ends, 0, presorted, true, anchor, Some(&scores));
+                assert_eq!(idx, vec![expected], "anchor {anchor}, presorted {presorted}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_zero_length_intervals() {
+        // A point inside [0,10), one touching its end, and an isolated point.
+        let chrs = [0u32, 0, 0, 0];
+        let starts = [0i64, 5, 10, 20];
+        let ends = [10i64, 5, 10, 20];
+
+        for presorted in [false, true] {
+            let (_, m_starts, m_ends, counts) = sweep_line_merge(&chrs, &starts, &ends, 0, presorted, true, "first", None);
+            assert_eq!(m_starts, vec![0, 20]);
+            assert_eq!(m_ends, vec![10, 20]);
+            assert_eq!(counts, vec![3, 1]);
+        }
+    }
+
+    #[test]
+    fn test_merge_touching_flag() {
+        // [0,10) and [10,20) touch; [25,25) is an isolated point and
+        // [30,40) contains the point [35,35).
+        let chrs = [0u32, 0, 0, 0, 0];
+        let starts = [0i64, 10, 25, 30, 35];
+        let ends = [10i64, 20, 25, 40, 35];
+
+        for presorted in [false, true] {
+            let (_, m_starts, m_ends, counts) =
+                sweep_line_merge(&chrs, &starts, &ends, 0, presorted, true, "first", None);
+            assert_eq!(m_starts, vec![0, 25, 30]);
+            assert_eq!(m_ends, vec![20, 25, 40]);
+            assert_eq!(counts, vec![2, 1, 2]);
+
+            let (idx, m_starts, m_ends, counts) =
+                sweep_line_merge(&chrs, &starts, &ends, 0, presorted, false, "first", None);
+            assert_eq!(idx, vec![0, 1, 2, 3]);
+            assert_eq!(m_starts, vec![0, 10, 25, 30]);
+            assert_eq!(m_ends, vec![10, 20, 25, 40]);
+            assert_eq!(counts, vec![1, 1, 1, 2]);
+
+            // A positive slack joins a gap of exactly `slack` either way.
+            for merge_touching in [false, true] {
+                let (_, m_starts, m_ends, _) = sweep_line_merge(
+                    &chrs[..2], &[0i64, 15], &[10i64, 20], 5, presorted, merge_touching, "first", None,
+                );
+                assert_eq!((m_starts, m_ends), (vec![0], vec![20]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge_values_sum_max_mean() {
+        // Region [0,20) holds rows 0, 2 and 3, region [30,40) only row 1.
+        let chrs = [0u32, 0, 0, 0];
+        let starts = [0i64, 30, 5, 15];
+        let ends = [10i64, 40, 15, 20];
+        let values = [1.0, 7.0, 4.0, 2.5];
+
+        let (idx, s, e, counts, sums, maxs, means) =
+            sweep_line_merge_values(&chrs, &starts, &ends, 0, true, "first", None, &values).unwrap();
+        assert_eq!((idx, s, e, counts), (vec![0, 1], vec![0, 30], vec![20, 40], vec![3, 1]));
+        assert_eq!(sums, vec![7.5, 7.0]);
+        assert_eq!(maxs, vec![4.0, 7.0]);
+        assert_eq!(means, vec![2.5, 7.0]);
+
+        assert!(sweep_line_merge_values(&chrs, &starts, &ends, 0, true, "first", None, &values[..3]).is_err());
+        assert!(sweep_line_merge_values(&chrs, &starts, &ends, 0, true, "score", None, &values).is_err());
+    }
+
+    #[test]
+    fn test_merge_values_isolated_point_and_touching() {
+        // [0,10) and [10,20) touch; the point at 25 stands alone.
+        let chrs = [0u32, 0, 0];
+        let starts = [10i64, 0, 25];
+        let ends = [20i64, 10, 25];
+        let values = [2.0, 3.0, 9.0];
+
+        let (idx, _, _, counts, sums, maxs, _) =
+            sweep_line_merge_values(&chrs, &starts, &ends, 0, false, "first", None, &values).unwrap();
+        assert_eq!((idx, counts), (vec![1, 0, 2], vec![1, 1, 1]));
+        assert_eq!((sums, maxs), (vec![3.0, 2.0, 9.0], vec![3.0, 2.0, 9.0]));
+
+        let (_, _, _, counts, sums, _, _) =
+            sweep_line_merge_values(&chrs, &starts, &ends, 0, true, "first", None, &values).unwrap();
+        assert_eq!((counts, sums), (vec![2, 1], vec![5.0, 9.0]));
+    }
+}
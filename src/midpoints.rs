@@ -0,0 +1,41 @@
+use crate::ruranges_structs::PositionType;
+
+/// Midpoint of `[start, end)`, rounded down: `start + (end - start) / 2`,
+/// which equals `floor((start + end) / 2)` without overflowing.
+#[inline]
+pub fn midpoint<T: PositionType>(start: T, end: T) -> T {
+    start + (end - start) / (T::one() + T::one())
+}
+
+/// The midpoint of every interval, rounded down for odd lengths.
+pub fn midpoints<T: PositionType>(starts: &[T], ends: &[T]) -> Vec<T> {
+    assert_eq!(starts.len(), ends.len());
+    starts.iter().zip(ends).map(|(&s, &e)| midpoint(s, e)).collect()
+}
+
+/// Unit-length intervals `[mid, mid + 1)` at every interval's midpoint, e.g.
+/// to turn peak summits into point features before a nearest search.
+///
+/// Returns `(new_starts, new_ends)`.
+pub fn midpoint_intervals<T: PositionType>(starts: &[T], ends: &[T]) -> (Vec<T>, Vec<T>) {
+    let mids = midpoints(starts, ends);
+    let ends = mids.iter().map(|&m| m + T::one()).collect();
+    (mids, ends)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_midpoints_round_down() {
+        // Even length, odd length, length 1 and negative coordinates.
+        let starts = [0i64, 10, 7, -5];
+        let ends = [10i64, 15, 8, 0];
+
+        assert_eq!(midpoints(&starts, &ends), vec![5, 12, 7, -3]);
+        let (s, e) = midpoint_intervals(&starts, &ends);
+        assert_eq!(s, vec![5, 12, 7, -3]);
+        assert_eq!(e, vec![6, 13, 8, -2]);
+    }
+}
@@ -1,9 +1,13 @@
 use std::fmt;
 
-use crate::ruranges_structs::MinEvent;
+use crate::ruranges_structs::{GroupType, MinEvent, PositionType};
+use crate::sorts::build_sorted_events_single_collection_separate_outputs;
 
 
-pub fn find_chr_boundaries_minevents(data: &[MinEvent]) -> Vec<usize> {
+/// Boundary indices `[0, ..., data.len()]` at every chromosome change in
+/// events sorted by chromosome, so `boundaries[k]..boundaries[k + 1]` is the
+/// `k`th chromosome block.
+pub fn find_chr_boundaries_minevents<C: GroupType, T: PositionType>(data: &[MinEvent<C, T>]) -> Vec<usize> {
     let mut boundaries = Vec::new();
 
     // Start boundary (beginning of first chromosome group)
@@ -22,20 +26,38 @@ pub fn find_chr_boundaries_minevents(data: &[MinEvent]) -> Vec<usize> {
     boundaries
 }
 
+/// Sort order of the intervals by chromosome and start, plus the offsets of
+/// each chromosome block in that order, ending with the number of rows.
+///
+/// Rows `order[offsets[k]..offsets[k + 1]]` are the `k`th chromosome, so
+/// callers can slice their arrays per chromosome and dispatch the blocks
+/// themselves.
+pub fn chrom_boundaries<C: GroupType, T: PositionType>(chrs: &[C], starts: &[T]) -> (Vec<u32>, Vec<u32>) {
+    let events = build_sorted_events_single_collection_separate_outputs(chrs, starts, T::zero());
+
+    let order = events.iter().map(|e| e.idx).collect();
+    let offsets = find_chr_boundaries_minevents(&events)
+        .into_iter()
+        .map(|b| b as u32)
+        .collect();
+
+    (order, offsets)
+}
+
 /// Holds combined boundaries for a single chromosome across two vectors.
 #[derive(Debug, Clone)]
-pub struct ChrBound {
-    pub chr: i64,
+pub struct ChrBound<C: GroupType> {
+    pub chr: C,
     pub start1: usize,
     pub end1: usize,
     pub start2: usize,
     pub end2: usize,
 }
 
-impl fmt::Display for ChrBound {
+impl<C: GroupType> fmt::Display for ChrBound<C> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Customize the output format as desired.
-        write!(f, "ChrBound {{ chr: {}, start1: {}, end1: {}, start2: {}, end2: {}, len1: {}, len2: {}, }}",
+        write!(f, "ChrBound {{ chr: {:?}, start1: {}, end1: {}, start2: {}, end2: {}, len1: {}, len2: {}, }}",
             self.chr, self.start1, self.end1, self.start2, self.end2, self.end1 - self.start1, self.end2 - self.start2)
     }
 }
@@ -43,7 +65,7 @@ impl fmt::Display for ChrBound {
 /// Returns boundary indices [0, ..., data.len()] whenever `chr` changes.
 /// E.g. if `data` has chr=1 for indices [0..2), chr=2 for [2..5), etc.,
 /// then you might get [0, 2, 5] (and finally data.len()).
-fn find_chr_boundaries(data: &[MinEvent]) -> Vec<usize> {
+pub fn find_chr_boundaries<C: GroupType, T: PositionType>(data: &[MinEvent<C, T>]) -> Vec<usize> {
     let mut boundaries = Vec::new();
     if data.is_empty() {
         return boundaries;
@@ -66,7 +88,7 @@ fn find_chr_boundaries(data: &[MinEvent]) -> Vec<usize> {
 
 /// Converts boundary indices into a list of (chr, start_index, end_index) blocks.
 /// Each block covers all MinEvents for a single chromosome in `data`.
-fn build_chr_blocks(data: &[MinEvent], boundaries: &[usize]) -> Vec<(i64, usize, usize)> {
+pub fn build_chr_blocks<C: GroupType, T: PositionType>(data: &[MinEvent<C, T>], boundaries: &[usize]) -> Vec<(C, usize, usize)> {
     let mut blocks = Vec::new();
     for w in boundaries.windows(2) {
         let start = w[0];
@@ -95,15 +117,15 @@ pub struct PartitionIndex {
 /// A helper struct to store the range of indices for a contiguous
 /// set of events on a single chromosome.
 #[derive(Debug)]
-struct ChromRange {
-    chr: i64,
-    start_idx: usize,
-    end_idx: usize, // end_idx is exclusive
+pub struct ChromRange<C: GroupType> {
+    pub chr: C,
+    pub start_idx: usize,
+    pub end_idx: usize, // end_idx is exclusive
 }
 
 /// Given a sorted slice of MinEvents, group them by chromosome
 /// and return a Vec of (chr, start_idx, end_idx).
-fn group_by_chromosome(events: &[MinEvent]) -> Vec<ChromRange> {
+pub fn group_by_chromosome<C: GroupType, T: PositionType>(events: &[MinEvent<C, T>]) -> Vec<ChromRange<C>> {
     if events.is_empty() {
         return vec![];
     }
@@ -113,8 +135,8 @@ fn group_by_chromosome(events: &[MinEvent]) -> Vec<ChromRange> {
     let mut current_chr = events[0].chr;
     let mut current_start = 0usize;
 
-    for i in 1..events.len() {
-        if events[i].chr != current_chr {
+    for (i, event) in events.iter().enumerate().skip(1) {
+        if event.chr != current_chr {
             // We've hit a new chromosome, close out the old range
             ranges.push(ChromRange {
                 chr: current_chr,
@@ -122,7 +144,7 @@ fn group_by_chromosome(events: &[MinEvent]) -> Vec<ChromRange> {
                 end_idx: i,
             });
             // start a new range
-            current_chr = events[i].chr;
+            current_chr = event.chr;
             current_start = i;
         }
     }
@@ -138,8 +160,8 @@ fn group_by_chromosome(events: &[MinEvent]) -> Vec<ChromRange> {
 
 /// Partition a single sorted slice (grouped by chromosome) into N partitions.
 /// Each partition is represented as (start_index, end_index) into the original slice.
-fn partition_chrom_ranges(
-    events: &[MinEvent],
+pub fn partition_chrom_ranges<C: GroupType, T: PositionType>(
+    events: &[MinEvent<C, T>],
     num_partitions: usize,
 ) -> Vec<(usize, usize)> {
     if events.is_empty() {
@@ -219,9 +241,9 @@ fn partition_chrom_ranges(
 /// Create `num_partitions` partitions for *both* slices, ensuring no chromosome boundaries
 /// are crossed in either slice. Each returned element describes the start/end in slice1
 /// and the start/end in slice2.
-pub fn partition_two_arrays(
-    sorted_starts: &[MinEvent],
-    sorted_starts2: &[MinEvent],
+pub fn partition_two_arrays<C: GroupType, T: PositionType>(
+    sorted_starts: &[MinEvent<C, T>],
+    sorted_starts2: &[MinEvent<C, T>],
     num_partitions: usize,
 ) -> Vec<PartitionIndex> {
     let parts1 = partition_chrom_ranges(sorted_starts, num_partitions);
@@ -230,7 +252,7 @@ pub fn partition_two_arrays(
     // Zip them into a single vector of PartitionIndex
     parts1
         .into_iter()
-        .zip(parts2.into_iter())
+        .zip(parts2)
         .map(|((start1, end1), (start2, end2))| PartitionIndex {
             start1,
             end1,
@@ -244,6 +266,38 @@ pub fn partition_two_arrays(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_chrom_boundaries_slice_sorted_blocks() {
+        let chrs = [2u32, 0, 2, 1, 0];
+        let starts = [5i64, 30, 1, 7, 10];
+
+        let (order, offsets) = chrom_boundaries(&chrs, &starts);
+
+        assert_eq!(order, vec![4, 1, 3, 2, 0]);
+        assert_eq!(offsets, vec![0, 2, 3, 5]);
+
+        let (order, offsets) = chrom_boundaries::<u32, i64>(&[], &[]);
+        assert!(order.is_empty());
+        assert_eq!(offsets, vec![0, 0]);
+    }
+
+    #[test]
+    fn test_partition_sorted_event_arrays_with_u32_codes() {
+        // Events as the overlap kernels build them from the bindings' u32 codes.
+        let chrs = [7u32, 2, 7, 2, 9, 7];
+        let starts = [5i32, 1, 3, 8, 0, 9];
+        let events = build_sorted_events_single_collection_separate_outputs(&chrs, &starts, 0);
+
+        let ranges = group_by_chromosome(&events);
+        let ranges: Vec<(u32, usize, usize)> = ranges.iter().map(|r| (r.chr, r.start_idx, r.end_idx)).collect();
+        assert_eq!(ranges, vec![(2, 0, 2), (7, 2, 5), (9, 5, 6)]);
+        assert_eq!(build_chr_blocks(&events, &find_chr_boundaries(&events)), ranges);
+
+        // Partitions never split a chromosome.
+        let parts = partition_chrom_ranges(&events, 2);
+        assert_eq!(parts, vec![(0, 2), (2, 6)]);
+    }
+
     #[test]
     fn test_partition_two_arrays() {
         // A small mock dataset with two chromosomes, 5 events on chr1,
@@ -3,19 +3,31 @@ use std::{str::FromStr, time::Instant};
 use radsort::sort_by_key;
 
 use crate::{
-    overlaps::{self, sweep_line_overlaps, sweep_line_overlaps_overlap_pair},
+    midpoints::midpoint,
+    overlaps::{self, sweep_line_overlaps, sweep_line_overlaps_overlap_pair_measured},
     ruranges_structs::{GroupType, MinEvent, Nearest, OverlapPair, PositionType},
     sorts::build_sorted_events_single_collection_separate_outputs,
 };
 
+/// Whether the query `idx` and subject `idx2` carry the same exclusion id.
+#[inline]
+fn same_group(exclude_group_ids: Option<(&[u32], &[u32])>, idx: u32, idx2: u32) -> bool {
+    exclude_group_ids.is_some_and(|(ids, ids2)| ids[idx as usize] == ids2[idx2 as usize])
+}
+
 /// For each MinEvent in `sorted_ends`, find up to `k` *unique positions*
 /// in `sorted_starts2` that lie to the right (including equal position on the
 /// same chromosome). If multiple entries in `sorted_starts2` share the same
 /// position, they all get reported, but they count as one unique position.
+///
+/// Starts closer than `min_distance`, and with `exclude_group_ids` those
+/// sharing the query's id, are skipped and do not count towards `k`.
 pub fn nearest_intervals_to_the_right<C: GroupType, T: PositionType>(
-    sorted_ends: Vec<MinEvent<C, T>>,
-    sorted_starts2: Vec<MinEvent<C, T>>,
+    sorted_ends: &[MinEvent<C, T>],
+    sorted_starts2: &[MinEvent<C, T>],
     k: usize,
+    min_distance: T,
+    exclude_group_ids: Option<(&[u32], &[u32])>,
 ) -> Vec<Nearest<T>> {
     // We might need more than `sorted_ends.len()` because each end could
     // contribute up to `k` *unique positions* (potentially multiplied by the
@@ -30,7 +42,7 @@ pub fn nearest_intervals_to_the_right<C: GroupType, T: PositionType>(
     let mut j = 0usize;
 
     // Iterate over each 'end' event
-    for end in &sorted_ends {
+    for end in sorted_ends {
         let end_chr = end.chr;
         let end_pos = end.pos;
 
@@ -70,6 +82,11 @@ pub fn nearest_intervals_to_the_right<C: GroupType, T: PositionType>(
                 break;
             }
 
+            if start.pos - end_pos < min_distance || same_group(exclude_group_ids, end.idx, start.idx) {
+                local_idx += 1;
+                continue;
+            }
+
             // Check if we're at a new unique position
             if last_pos.map_or(true, |lp| start.pos != lp) {
                 unique_count += 1;
@@ -80,8 +97,9 @@ pub fn nearest_intervals_to_the_right<C: GroupType, T: PositionType>(
                 last_pos = Some(start.pos);
             }
 
-            // This start is included in the results
-            let distance = start.pos - end_pos + T::one(); // can be 0 or positive
+            // This start is included in the results. The distance is the
+            // gap in bases, so a directly adjacent interval is at distance 0.
+            let distance = start.pos - end_pos; // can be 0 or positive
             output.push(Nearest {
                 distance,
                 idx: end.idx,
@@ -100,10 +118,15 @@ pub fn nearest_intervals_to_the_right<C: GroupType, T: PositionType>(
 /// the same chromosome). If multiple entries in `sorted_starts2` share
 /// the same position, they all get reported, but they count as one
 /// unique position in the limit `k`.
+///
+/// Ends closer than `min_distance`, and with `exclude_group_ids` those
+/// sharing the query's id, are skipped and do not count towards `k`.
 pub fn nearest_intervals_to_the_left<C: GroupType, T: PositionType>(
-    sorted_ends: Vec<MinEvent<C, T>>,
-    sorted_starts2: Vec<MinEvent<C, T>>,
+    sorted_ends: &[MinEvent<C, T>],
+    sorted_starts2: &[MinEvent<C, T>],
     k: usize,
+    min_distance: T,
+    exclude_group_ids: Option<(&[u32], &[u32])>,
 ) -> Vec<Nearest<T>> {
     // The max possible size is (number of ends) * (k + duplicates at each of those k positions).
     // We reserve a rough upper bound for efficiency.
@@ -112,7 +135,7 @@ pub fn nearest_intervals_to_the_left<C: GroupType, T: PositionType>(
     let n_starts = sorted_starts2.len();
     let mut j = 0_usize; // Points into sorted_starts2
 
-    for end in &sorted_ends {
+    for end in sorted_ends {
         let end_chr = end.chr;
         let end_pos = end.pos;
 
@@ -156,6 +179,14 @@ pub fn nearest_intervals_to_the_left<C: GroupType, T: PositionType>(
                 break;
             }
 
+            if end_pos - start.pos < min_distance || same_group(exclude_group_ids, end.idx, start.idx) {
+                if local_idx == 0 {
+                    break;
+                }
+                local_idx -= 1;
+                continue;
+            }
+
             // Check if we have a new (unique) position
             if last_pos.map_or(true, |lp| start.pos != lp) {
                 unique_count += 1;
@@ -165,9 +196,9 @@ pub fn nearest_intervals_to_the_left<C: GroupType, T: PositionType>(
                 last_pos = Some(start.pos);
             }
 
-            // Calculate the distance (end.pos - start.pos)
-            // Here, start.pos < end.pos by definition if we get here.
-            let distance = end_pos - start.pos + T::one();
+            // Calculate the gap (end.pos - start.pos); 0 means adjacent.
+            // Here, start.pos <= end.pos by definition if we get here.
+            let distance = end_pos - start.pos;
             output.push(Nearest {
                 distance,
                 idx: end.idx,    // the 'end' event's idx
@@ -205,6 +236,59 @@ impl FromStr for Direction {
     }
 }
 
+/// Order of the rows returned by [`nearest`]: grouped by query (`"idx"`,
+/// then by distance and subject), or globally nearest first (`"distance"`,
+/// ties by query, then subject).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NearestSortBy {
+    Idx,
+    Distance,
+}
+
+impl FromStr for NearestSortBy {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "idx" => Ok(NearestSortBy::Idx),
+            "distance" => Ok(NearestSortBy::Distance),
+            _ => Err("Invalid sort_by string"),
+        }
+    }
+}
+
+/// How [`nearest`] measures the distance between two intervals: the gap
+/// between their closest edges (`"edge"`, 0 when they overlap or touch), or
+/// between their midpoints (`"midpoint"`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DistanceMetric {
+    Edge,
+    Midpoint,
+}
+
+impl FromStr for DistanceMetric {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "edge" => Ok(DistanceMetric::Edge),
+            "midpoint" => Ok(DistanceMetric::Midpoint),
+            _ => Err("invalid distance metric: expected one of edge/midpoint"),
+        }
+    }
+}
+
+/// `min_distance` skips subjects closer than that many bases (and, when
+/// positive, all overlaps) before the `k` nearest distances are picked, e.g.
+/// to find features outside a promoter window.
+///
+/// With `distance_metric = "midpoint"` distances are taken between the
+/// midpoints `start + (end - start) / 2` (rounded down), so a large subject
+/// overlapping the query can lose to a small one nearby. `"forward"` then
+/// means a midpoint at or after the query's, `"backward"` one before it, and
+/// `include_overlaps = false` drops overlapping subjects. Slack only matters
+/// for that overlap test.
+#[allow(clippy::too_many_arguments)]
 pub fn nearest<C: GroupType, T: PositionType>(
     chrs:     &[C],
     starts:   &[T],
@@ -216,58 +300,320 @@ pub fn nearest<C: GroupType, T: PositionType>(
     k:        usize,
     include_overlaps: bool,
     direction: &str,
+    exclude_group_ids: Option<(&[u32], &[u32])>,
+    sort_by: &str,
+    min_distance: T,
+    distance_metric: &str,
 ) -> (Vec<u32>, Vec<u32>, Vec<T>) {
+    let (idxs, idxs2, distances, _) = nearest_with_ties(
+        chrs, starts, ends,
+        chrs2, starts2, ends2,
+        slack, k, include_overlaps, direction, exclude_group_ids, sort_by, min_distance,
+        distance_metric,
+    );
+    (idxs, idxs2, distances)
+}
+
+/// Same as [`nearest`], plus a fourth column with, for every output row,
+/// the number of subjects sharing its query's minimal distance. A value
+/// above 1 flags a query whose nearest assignment is ambiguous.
+#[allow(clippy::too_many_arguments)]
+pub fn nearest_with_ties<C: GroupType, T: PositionType>(
+    chrs:     &[C],
+    starts:   &[T],
+    ends:     &[T],
+    chrs2:    &[C],
+    starts2:  &[T],
+    ends2:    &[T],
+    slack:    T,
+    k:        usize,
+    include_overlaps: bool,
+    direction: &str,
+    exclude_group_ids: Option<(&[u32], &[u32])>,
+    sort_by: &str,
+    min_distance: T,
+    distance_metric: &str,
+) -> (Vec<u32>, Vec<u32>, Vec<T>, Vec<u32>) {
+    let sort_by = NearestSortBy::from_str(sort_by).expect("invalid sort_by string");
+    let metric = DistanceMetric::from_str(distance_metric).expect("invalid distance_metric string");
+
+    let result = match metric {
+        DistanceMetric::Edge => {
+            let sorted_starts2 = build_sorted_events_single_collection_separate_outputs(chrs2, starts2, T::zero());
+            let sorted_ends2 = build_sorted_events_single_collection_separate_outputs(chrs2, ends2, T::zero());
+            nearest_presorted_database(
+                chrs, starts, ends,
+                &sorted_starts2, &sorted_ends2, (starts2, ends2),
+                slack, k, include_overlaps, direction, exclude_group_ids, min_distance,
+            )
+        }
+        DistanceMetric::Midpoint => nearest_by_midpoint(
+            chrs, starts, ends,
+            chrs2, starts2, ends2,
+            slack, k, include_overlaps, direction, exclude_group_ids, min_distance,
+        ),
+    };
+    match sort_by {
+        NearestSortBy::Idx => result,
+        NearestSortBy::Distance => sort_nearest_by_distance(result),
+    }
+}
+
+/// The midpoint variant of [`nearest_presorted_database`]: subject midpoints
+/// are sorted once and every query scans outward from its own midpoint,
+/// collecting up to `k` distinct distances on each side it looks at.
+#[allow(clippy::too_many_arguments)]
+fn nearest_by_midpoint<C: GroupType, T: PositionType>(
+    chrs:     &[C],
+    starts:   &[T],
+    ends:     &[T],
+    chrs2:    &[C],
+    starts2:  &[T],
+    ends2:    &[T],
+    slack:    T,
+    k:        usize,
+    include_overlaps: bool,
+    direction: &str,
+    exclude_group_ids: Option<(&[u32], &[u32])>,
+    min_distance: T,
+) -> (Vec<u32>, Vec<u32>, Vec<T>, Vec<u32>) {
+    let dir = Direction::from_str(direction).unwrap();
+
+    let mut mids2: Vec<MinEvent<C, T>> = (0..chrs2.len())
+        .map(|j| MinEvent { chr: chrs2[j], pos: midpoint(starts2[j], ends2[j]), idx: j as u32 })
+        .collect();
+    sort_by_key(&mut mids2, |e| (e.chr, e.pos));
+
+    // Whether subject `j` is a candidate for query `i` at `distance`.
+    let keep = |i: usize, j: usize, distance: T| {
+        distance >= min_distance
+            && !same_group(exclude_group_ids, i as u32, j as u32)
+            && (include_overlaps || starts[i] - slack >= ends2[j] || starts2[j] >= ends[i] + slack)
+    };
+
+    let mut nearest_left = Vec::new();
+    let mut nearest_right = Vec::new();
+
+    for i in 0..chrs.len() {
+        let (chr, mid) = (chrs[i], midpoint(starts[i], ends[i]));
+        let first_right = mids2.partition_point(|e| (e.chr, e.pos) < (chr, mid));
+
+        if dir == Direction::Forward || dir == Direction::Any {
+            let mut unique = 0;
+            let mut last_pos = None;
+            for e in mids2[first_right..].iter().take_while(|e| e.chr == chr) {
+                let distance = e.pos - mid;
+                if !keep(i, e.idx as usize, distance) {
+                    continue;
+                }
+                if last_pos != Some(e.pos) {
+                    unique += 1;
+                    if unique > k {
+                        break;
+                    }
+                    last_pos = Some(e.pos);
+                }
+                nearest_right.push(Nearest { distance, idx: i as u32, idx2: e.idx });
+            }
+        }
+
+        if dir == Direction::Backward || dir == Direction::Any {
+            let mut unique = 0;
+            let mut last_pos = None;
+            for e in mids2[..first_right].iter().rev().take_while(|e| e.chr == chr) {
+                let distance = mid - e.pos;
+                if !keep(i, e.idx as usize, distance) {
+                    continue;
+                }
+                if last_pos != Some(e.pos) {
+                    unique += 1;
+                    if unique > k {
+                        break;
+                    }
+                    last_pos = Some(e.pos);
+                }
+                nearest_left.push(Nearest { distance, idx: i as u32, idx2: e.idx });
+            }
+        }
+    }
+
+    // Already ordered by query, and by distance within each query and side.
+    merge_three_way_by_index_distance(&[], &nearest_left, &nearest_right, k, exclude_group_ids)
+}
+
+/// Reorders the query-ordered output of [`nearest_with_ties`] by
+/// `(distance, idx, idx2)`, carrying the tie counts along.
+fn sort_nearest_by_distance<T: PositionType>(
+    (idxs, idxs2, distances, n_ties): (Vec<u32>, Vec<u32>, Vec<T>, Vec<u32>),
+) -> (Vec<u32>, Vec<u32>, Vec<T>, Vec<u32>) {
+    // The input is already ordered by (idx, distance, idx2), so a stable
+    // sort on distance keeps the (idx, idx2) order among equal distances.
+    let mut order: Vec<u32> = (0..idxs.len() as u32).collect();
+    sort_by_key(&mut order, |&i| distances[i as usize]);
+
+    let mut out = (
+        Vec::with_capacity(order.len()),
+        Vec::with_capacity(order.len()),
+        Vec::with_capacity(order.len()),
+        Vec::with_capacity(order.len()),
+    );
+    for i in order {
+        let i = i as usize;
+        out.0.push(idxs[i]);
+        out.1.push(idxs2[i]);
+        out.2.push(distances[i]);
+        out.3.push(n_ties[i]);
+    }
+    out
+}
+
+/// A nearest-search database whose sorted start/end events are built once
+/// and reused for every query batch, instead of being rebuilt per call.
+pub struct NearestIndex<C: GroupType, T: PositionType> {
+    starts2: Vec<T>,
+    ends2: Vec<T>,
+    sorted_starts2: Vec<MinEvent<C, T>>,
+    sorted_ends2: Vec<MinEvent<C, T>>,
+}
+
+impl<C: GroupType, T: PositionType> NearestIndex<C, T> {
+    /// Builds the index from the database intervals. Slack only widens the
+    /// query intervals (as in [`nearest`]), so it is passed to `query`.
+    pub fn new(chrs2: &[C], starts2: &[T], ends2: &[T]) -> Self {
+        NearestIndex {
+            starts2: starts2.to_vec(),
+            ends2: ends2.to_vec(),
+            sorted_starts2: build_sorted_events_single_collection_separate_outputs(chrs2, starts2, T::zero()),
+            sorted_ends2: build_sorted_events_single_collection_separate_outputs(chrs2, ends2, T::zero()),
+        }
+    }
+
+    /// Same as [`nearest`] with this index as the second interval set.
+    #[allow(clippy::too_many_arguments)]
+    pub fn query(
+        &self,
+        chrs:     &[C],
+        starts:   &[T],
+        ends:     &[T],
+        slack:    T,
+        k:        usize,
+        include_overlaps: bool,
+        direction: &str,
+    ) -> (Vec<u32>, Vec<u32>, Vec<T>) {
+        let (idxs, idxs2, distances, _) = nearest_presorted_database(
+            chrs, starts, ends,
+            &self.sorted_starts2, &self.sorted_ends2, (&self.starts2, &self.ends2),
+            slack, k, include_overlaps, direction, None, T::zero(),
+        );
+        (idxs, idxs2, distances)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn nearest_presorted_database<C: GroupType, T: PositionType>(
+    chrs:     &[C],
+    starts:   &[T],
+    ends:     &[T],
+    sorted_starts2: &[MinEvent<C, T>],
+    sorted_ends2:   &[MinEvent<C, T>],
+    coords2:  (&[T], &[T]),
+    slack:    T,
+    k:        usize,
+    include_overlaps: bool,
+    direction: &str,
+    exclude_group_ids: Option<(&[u32], &[u32])>,
+    min_distance: T,
+) -> (Vec<u32>, Vec<u32>, Vec<T>, Vec<u32>) {
     let dir = Direction::from_str(direction).unwrap();
 
     let sorted_starts = build_sorted_events_single_collection_separate_outputs(chrs, starts, slack);
     let sorted_ends = build_sorted_events_single_collection_separate_outputs(chrs, ends, slack);
 
-    let sorted_starts2 = build_sorted_events_single_collection_separate_outputs(chrs2, starts2, T::zero());
-    let sorted_ends2 = build_sorted_events_single_collection_separate_outputs(chrs2, ends2, T::zero());
-
-    let overlaps = if include_overlaps {
-        sweep_line_overlaps_overlap_pair(
+    // Overlaps sit at distance 0, so any positive minimum rules them out.
+    let overlaps = if include_overlaps && min_distance <= T::zero() {
+        sweep_line_overlaps_overlap_pair_measured(
             &sorted_starts,
             &sorted_ends,
-            &sorted_starts2,
-            &sorted_ends2,
+            sorted_starts2,
+            sorted_ends2,
+            (starts, ends),
+            coords2,
         )
     } else {
         Vec::new()
     };
     let nearest_left = if dir == Direction::Backward || dir == Direction::Any {
-        let mut tmp = nearest_intervals_to_the_left(sorted_starts, sorted_ends2, k);
+        let mut tmp = nearest_intervals_to_the_left(&sorted_starts, sorted_ends2, k, min_distance, exclude_group_ids);
         radsort::sort_by_key(&mut tmp, |n| (n.idx, n.distance));
         tmp
     } else {
         Vec::new()
     };
     let nearest_right = if dir == Direction::Forward || dir == Direction::Any {
-        let mut tmp = nearest_intervals_to_the_right(sorted_ends, sorted_starts2, k);
+        let mut tmp = nearest_intervals_to_the_right(&sorted_ends, sorted_starts2, k, min_distance, exclude_group_ids);
         radsort::sort_by_key(&mut tmp, |n| (n.idx, n.distance));
         tmp
     } else {
         Vec::new()
     };
 
-    let merged = merge_three_way_by_index_distance(&overlaps, &nearest_left, &nearest_right, k);
-    merged
+    merge_three_way_by_index_distance(
+        &overlaps,
+        &nearest_left,
+        &nearest_right,
+        k,
+        exclude_group_ids,
+    )
 }
 
 /// Merges three sources of intervals, grouped by `idx` (i.e. `idx1` in overlaps).
 /// For each unique `idx`, it returns up to `k` *distinct* distances (including
-/// all intervals at those distances). Overlaps are treated as distance=0 (or 1).
+/// all intervals at those distances). Overlaps are treated as distance=0, the
+/// same as directly adjacent intervals.
 ///
 /// The data is assumed to be sorted in ascending order by `(idx, distance)`.
+///
+/// With `exclude_group_ids = Some((ids, ids2))`, a pair is dropped before
+/// the `k` distances are picked when `ids[idx] == ids2[idx2]`, so e.g. a
+/// gene never reports itself (or a transcript of itself) as its nearest.
+///
+/// The fourth output holds, per row, how many subjects of that row's query
+/// share the query's smallest distance.
 pub fn merge_three_way_by_index_distance<T: PositionType>(
-    overlaps: &[OverlapPair],     // sorted by idx1
+    overlaps: &[OverlapPair<T>],  // sorted by idx1
     nearest_left: &[Nearest<T>],  // sorted by (idx, distance)
     nearest_right: &[Nearest<T>], // sorted by (idx, distance)
     k: usize,
-) -> (Vec<u32>, Vec<u32>, Vec<T>) {
+    exclude_group_ids: Option<(&[u32], &[u32])>,
+) -> (Vec<u32>, Vec<u32>, Vec<T>, Vec<u32>) {
+    let (overlaps_kept, left_kept, right_kept);
+    let (overlaps, nearest_left, nearest_right) = match exclude_group_ids {
+        Some((ids, ids2)) => {
+            let keep = |idx: u32, idx2: u32| ids[idx as usize] != ids2[idx2 as usize];
+            overlaps_kept = overlaps
+                .iter()
+                .filter(|o| keep(o.idx, o.idx2))
+                .cloned()
+                .collect::<Vec<_>>();
+            left_kept = nearest_left
+                .iter()
+                .filter(|n| keep(n.idx, n.idx2))
+                .copied()
+                .collect::<Vec<_>>();
+            right_kept = nearest_right
+                .iter()
+                .filter(|n| keep(n.idx, n.idx2))
+                .copied()
+                .collect::<Vec<_>>();
+            (&overlaps_kept[..], &left_kept[..], &right_kept[..])
+        }
+        None => (overlaps, nearest_left, nearest_right),
+    };
+
     // We'll return tuples: (idx, idx2, distance).
     // You can adapt if you want a custom struct instead.
     let mut results = Vec::new();
+    let mut n_ties: Vec<u32> = Vec::new();
 
     // Pointers over each input
     let (mut i, mut j, mut r) = (0_usize, 0_usize, 0_usize);
@@ -321,6 +667,7 @@ pub fn merge_three_way_by_index_distance<T: PositionType>(
         // If you store overlap distances in OverlapPair, you can read them;
         // otherwise, assume overlap distance=0.
 
+        let block_start = results.len();
         let mut used_distances = std::collections::HashSet::new();
         let mut distinct_count = 0;
 
@@ -375,7 +722,7 @@ pub fn merge_three_way_by_index_distance<T: PositionType>(
                         used_distances.insert(dcur);
                     }
                     // Add to result
-                    let OverlapPair { idx, idx2 } = overlaps_slice[oi];
+                    let OverlapPair { idx, idx2, .. } = overlaps_slice[oi];
                     results.push(Nearest { idx: idx, idx2: idx2, distance: T::zero() });
                     oi += 1;
                 } else {
@@ -429,8 +776,20 @@ pub fn merge_three_way_by_index_distance<T: PositionType>(
             }
         }
         // done collecting up to k distinct distances for this index
+
+        // The block was filled in ascending distance, so its first row holds
+        // the minimal distance.
+        let block = &results[block_start..];
+        let ties = block
+            .iter()
+            .take_while(|n: &&Nearest<T>| n.distance == block[0].distance)
+            .count() as u32;
+        n_ties.resize(results.len(), ties);
     }
 
+    // Rows of one query stay in one contiguous block under this sort, and
+    // every row of a block carries the same tie count, so `n_ties` stays
+    // aligned without being permuted.
     sort_by_key(&mut results, |n| (n.idx, n.distance, n.idx2));
 
     let mut out_idxs    = Vec::with_capacity(results.len());
@@ -443,5 +802,199 @@ pub fn merge_three_way_by_index_distance<T: PositionType>(
         out_distances.push(rec.distance);
     }
 
-    (out_idxs, out_idxs2, out_distances)
+    (out_idxs, out_idxs2, out_distances, n_ties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_excludes_same_group() {
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 20, 50];
+        let ends = [10i64, 30, 60];
+        let genes = [0u32, 1, 2];
+
+        let (idx, idx2, dist) = nearest(
+            &chrs, &starts, &ends, &chrs, &starts, &ends,
+            0, 1, true, "any", Some((&genes, &genes)), "idx", 0, "edge",
+        );
+
+        assert_eq!(idx, vec![0, 1, 2]);
+        assert_eq!(idx2, vec![1, 0, 1]);
+        assert_eq!(dist, vec![10, 10, 20]);
+    }
+
+    #[test]
+    fn test_nearest_excluded_subject_does_not_take_the_slot() {
+        // Subjects 0 and 1 share the query's id; subject 1 is the closest
+        // downstream one and must not use up k before subject 2 is seen.
+        let chrs = [0u32];
+        let starts = [0i64];
+        let ends = [10i64];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [0i64, 12, 20];
+        let ends2 = [10i64, 14, 30];
+
+        for metric in ["edge", "midpoint"] {
+            let (idx, idx2, dist) = nearest(
+                &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+                0, 1, true, "forward", Some((&[0], &[0, 0, 1])), "idx", 0, metric,
+            );
+            assert_eq!((idx, idx2), (vec![0], vec![2]), "metric {metric}");
+            if metric == "edge" {
+                assert_eq!(dist, vec![10]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_bookended_intervals_are_at_distance_zero() {
+        // Subjects [0,10) and [20,30) touch the query [10,20) on either side
+        // without overlapping it.
+        let chrs = [0u32];
+        let starts = [10i64];
+        let ends = [20i64];
+        let chrs2 = [0u32, 0];
+        let starts2 = [0i64, 20];
+        let ends2 = [10i64, 30];
+
+        for (direction, expected) in [("forward", 1), ("backward", 0)] {
+            let (idx, idx2, dist) = nearest(
+                &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+                0, 1, false, direction, None, "idx", 0, "edge",
+            );
+            assert_eq!((idx, idx2, dist), (vec![0], vec![expected], vec![0]), "direction {direction}");
+        }
+    }
+
+    #[test]
+    fn test_nearest_with_ties_counts_subjects_at_min_distance() {
+        let chrs = [0u32, 0];
+        let starts = [20i64, 100];
+        let ends = [30i64, 110];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [0i64, 40, 115];
+        let ends2 = [10i64, 50, 120];
+
+        let (idx, idx2, dist, n_ties) = nearest_with_ties(
+            &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+            0, 1, true, "any", None, "idx", 0, "edge",
+        );
+
+        assert_eq!(idx, vec![0, 0, 1]);
+        assert_eq!(idx2, vec![0, 1, 2]);
+        assert_eq!(dist, vec![10, 10, 5]);
+        assert_eq!(n_ties, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn test_nearest_never_crosses_chromosomes() {
+        // The query sits at the end of chr 0; subjects 1 and 2 are on chr 1
+        // at coordinates that overlap or touch it, subject 0 is far away on
+        // chr 0 and is the only valid neighbour.
+        let chrs = [0u32];
+        let starts = [990i64];
+        let ends = [1000i64];
+        let chrs2 = [0u32, 1, 1];
+        let starts2 = [0i64, 995, 1000];
+        let ends2 = [10i64, 996, 1010];
+
+        for direction in ["any", "forward", "backward"] {
+            for k in [1, 3] {
+                let (idx, idx2, _) = nearest(
+                    &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+                    0, k, true, direction, None, "idx", 0, "edge",
+                );
+                assert!(idx2.iter().all(|&j| chrs2[j as usize] == 0), "{direction} k={k}: {idx2:?}");
+                let expected = if direction == "forward" { vec![] } else { vec![0] };
+                assert_eq!(idx2, expected, "{direction} k={k}");
+                assert_eq!(idx.len(), idx2.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_index_matches_nearest() {
+        let chrs2 = [0u32, 0, 1];
+        let starts2 = [5i64, 40, 10];
+        let ends2 = [10i64, 50, 20];
+        let index = NearestIndex::new(&chrs2, &starts2, &ends2);
+
+        for (chrs, starts, ends) in [
+            (vec![0u32, 1], vec![20i64, 0], vec![30i64, 5]),
+            (vec![0u32], vec![8i64], vec![45i64]),
+        ] {
+            assert_eq!(
+                index.query(&chrs, &starts, &ends, 0, 1, true, "any"),
+                nearest(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, 1, true, "any", None, "idx", 0, "edge"),
+            );
+        }
+    }
+
+    #[test]
+    fn test_nearest_sort_by_distance() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 100];
+        let ends = [10i64, 110];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [30i64, 112, 60];
+        let ends2 = [40i64, 120, 70];
+
+        let (idx, idx2, dist, n_ties) = nearest_with_ties(
+            &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+            0, 2, true, "any", None, "distance", 0, "edge",
+        );
+
+        assert_eq!(dist, vec![2, 20, 30, 50]);
+        assert_eq!(idx, vec![1, 0, 1, 0]);
+        assert_eq!(idx2, vec![1, 0, 2, 2]);
+        assert_eq!(n_ties, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_nearest_min_distance_skips_close_subjects() {
+        let chrs = [0u32];
+        let starts = [100i64];
+        let ends = [200i64];
+        let chrs2 = [0u32, 0, 0, 0];
+        let starts2 = [150i64, 205, 260, 20];
+        let ends2 = [160i64, 215, 270, 30];
+
+        let (_, idx2, dist) = nearest(
+            &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+            0, 2, true, "any", None, "idx", 10, "edge",
+        );
+        // The overlap and the subject 5 bases downstream are too close.
+        assert_eq!(idx2, vec![2, 3]);
+        assert_eq!(dist, vec![60, 70]);
+    }
+
+    #[test]
+    fn test_nearest_by_midpoint() {
+        // A long subject overlapping the query and a short one just downstream.
+        let chrs = [0u32];
+        let starts = [100i64];
+        let ends = [110i64];
+        let chrs2 = [0u32, 0];
+        let starts2 = [0i64, 112];
+        let ends2 = [1000i64, 114];
+
+        let run = |metric, include_overlaps| {
+            nearest(
+                &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+                0, 1, include_overlaps, "any", None, "idx", 0, metric,
+            )
+        };
+        assert_eq!(run("edge", true), (vec![0], vec![0], vec![0]));
+        // Midpoints: query 105, subjects 500 and 113.
+        assert_eq!(run("midpoint", true), (vec![0], vec![1], vec![8]));
+
+        let (_, idx2, dist) = nearest(
+            &chrs, &starts, &ends, &chrs2, &starts2, &ends2,
+            0, 2, true, "backward", None, "idx", 0, "midpoint",
+        );
+        assert!(idx2.is_empty() && dist.is_empty());
+    }
 }
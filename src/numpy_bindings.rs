@@ -26,6 +26,13 @@ use bindings::numpy_bindings::split_numpy::*;
 use bindings::numpy_bindings::genome_bounds_numpy::*;
 use bindings::numpy_bindings::group_cumsum_numpy::*;
 use bindings::numpy_bindings::map_to_global_numpy::*;
+use bindings::numpy_bindings::io_numpy::{read_gtf_numpy, read_narrowpeak_numpy, write_gtf_numpy};
+use bindings::numpy_bindings::validate_numpy::*;
+use bindings::numpy_bindings::multiprocessing_numpy::*;
+use bindings::numpy_bindings::coverage_numpy::*;
+use bindings::numpy_bindings::sample_numpy::*;
+use bindings::numpy_bindings::evaluate_numpy::*;
+use bindings::numpy_bindings::midpoints_numpy::*;
 
 use crate::bindings;
 
@@ -75,6 +82,9 @@ fn ruranges(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(chromsweep_numpy_u8_i64, m)?)?;
     m.add_function(wrap_pyfunction!(chromsweep_numpy_u8_i32, m)?)?;
     m.add_function(wrap_pyfunction!(chromsweep_numpy_u8_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_numpy_u32_f64, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_numpy_u32_f32, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_numpy_u64_f64, m)?)?;
 
     m.add_function(wrap_pyfunction!(sweepline_numpy_u64_i64, m)?)?;
     m.add_function(wrap_pyfunction!(sweepline_numpy_u32_i64, m)?)?;
@@ -152,6 +162,9 @@ fn ruranges(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(cluster_numpy_u8_i64, m)?)?;
     m.add_function(wrap_pyfunction!(cluster_numpy_u8_i32, m)?)?;
     m.add_function(wrap_pyfunction!(cluster_numpy_u8_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_numpy_u32_f64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_numpy_u32_f32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_numpy_u64_f64, m)?)?;
 
     m.add_function(wrap_pyfunction!(merge_numpy_u64_i64, m)?)?;
     m.add_function(wrap_pyfunction!(merge_numpy_u32_i64, m)?)?;
@@ -163,6 +176,9 @@ fn ruranges(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(merge_numpy_u8_i64, m)?)?;
     m.add_function(wrap_pyfunction!(merge_numpy_u8_i32, m)?)?;
     m.add_function(wrap_pyfunction!(merge_numpy_u8_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_numpy_u32_f64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_numpy_u32_f32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_numpy_u64_f64, m)?)?;
 
     m.add_function(wrap_pyfunction!(max_disjoint_numpy_u64_i64, m)?)?;
     m.add_function(wrap_pyfunction!(max_disjoint_numpy_u32_i64, m)?)?;
@@ -202,6 +218,10 @@ fn ruranges(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(tile_numpy_i32, m)?)?;
     m.add_function(wrap_pyfunction!(tile_numpy_i16, m)?)?;
 
+    m.add_function(wrap_pyfunction!(tile_n_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_n_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_n_numpy_i16, m)?)?;
+
     m.add_function(wrap_pyfunction!(boundary_numpy_u64_i64, m)?)?;
     m.add_function(wrap_pyfunction!(boundary_numpy_u32_i64, m)?)?;
     m.add_function(wrap_pyfunction!(boundary_numpy_u32_i32, m)?)?;
@@ -245,6 +265,21 @@ fn ruranges(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(extend_numpy_u8_i64, m)?)?;
     m.add_function(wrap_pyfunction!(extend_numpy_u8_i32, m)?)?;
     m.add_function(wrap_pyfunction!(extend_numpy_u8_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_rows_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_rows_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_rows_numpy_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(midpoints_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(midpoints_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(midpoints_numpy_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(midpoint_intervals_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(midpoint_intervals_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(midpoint_intervals_numpy_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_5prime_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_5prime_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_5prime_numpy_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_3prime_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_3prime_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(extend_3prime_numpy_i16, m)?)?;
 
     m.add_function(wrap_pyfunction!(split_numpy_u64_i64, m)?)?;
     m.add_function(wrap_pyfunction!(split_numpy_u32_i64, m)?)?;
@@ -279,5 +314,493 @@ fn ruranges(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(group_cumsum_numpy_u8_i32, m)?)?;
     m.add_function(wrap_pyfunction!(group_cumsum_numpy_u8_i16, m)?)?;
 
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_min_coverage_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(no_overlap_mask_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(overlap_matrix_dense_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(pairs_within_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_aggregate_count_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(group_cumsum_totals_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(outside_bounds_circular_numpy_u8_i16, m)?)?;
+
+    m.add_class::<NearestIndex_u64_i64>()?;
+    m.add_class::<NearestIndex_u32_i64>()?;
+    m.add_class::<NearestIndex_u32_i32>()?;
+    m.add_class::<NearestIndex_u32_i16>()?;
+    m.add_class::<NearestIndex_u16_i64>()?;
+    m.add_class::<NearestIndex_u16_i32>()?;
+    m.add_class::<NearestIndex_u16_i16>()?;
+    m.add_class::<NearestIndex_u8_i64>()?;
+    m.add_class::<NearestIndex_u8_i32>()?;
+    m.add_class::<NearestIndex_u8_i16>()?;
+
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(count_overlaps_both_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(split_max_length_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(sort_intervals_apply_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(point_overlaps_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(genome_bounds_flags_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(read_narrowpeak_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(read_gtf_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(write_gtf_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(save_sorted_index_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(load_sorted_index_numpy, m)?)?;
+
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(dump_events_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(validate_intervals_numpy_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_intervals_numpy_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_intervals_numpy_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(chrom_boundaries_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(span_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(span_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_transitions_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(subtract_min_fraction_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_grouped_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_ids_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(tile_scores_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(partition_into_layers_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpm_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_normalize_rpkm_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(chromsweep_within_group_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(complement_set2_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(anchor_profile_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(query_single_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(query_single_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(clip_to_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(contained_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(contained_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(coverage_rle_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(cluster_stats_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(intersection_size_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(intersection_size_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(intersection_size_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(intersection_size_numpy_u8_i64, m)?)?;
+
+    m.add_function(wrap_pyfunction!(gaps_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(gaps_numpy_u8_i16, m)?)?;
+
+    m.add_class::<OverlapChunks_u64_i64>()?;
+    m.add_class::<OverlapChunks_u32_i64>()?;
+    m.add_class::<OverlapChunks_u32_i32>()?;
+    m.add_class::<OverlapChunks_u32_i16>()?;
+    m.add_class::<OverlapChunks_u16_i64>()?;
+    m.add_class::<OverlapChunks_u16_i32>()?;
+    m.add_class::<OverlapChunks_u16_i16>()?;
+    m.add_class::<OverlapChunks_u8_i64>()?;
+    m.add_class::<OverlapChunks_u8_i32>()?;
+    m.add_class::<OverlapChunks_u8_i16>()?;
+
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(any_overlap_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(sample_random_intervals_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(permute_starts_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(evaluate_overlap_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(merge_values_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(annotate_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(annotate_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(jaccard_numpy_u8_i16, m)?)?;
+
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u64_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u32_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u32_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u32_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u16_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u16_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u16_i16, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u8_i64, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u8_i32, m)?)?;
+    m.add_function(wrap_pyfunction!(spliced_length_numpy_u8_i16, m)?)?;
+
     Ok(())
 }
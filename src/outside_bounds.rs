@@ -1,15 +1,80 @@
-use std::collections::HashMap;
+use rustc_hash::FxHashMap;
 
 use crate::ruranges_structs::{GroupType, PositionType};
 
+/// `(indices, starts, ends)`
+pub type BoundsOutput<T> = (Vec<u32>, Vec<T>, Vec<T>);
+
+/// Removes or clips intervals outside their chromosome, looking up each
+/// row's chromosome length in `chrom_lens_map`.
+///
+/// A group missing from the map is an error rather than being silently
+/// treated as in bounds.
 pub fn outside_bounds<G: GroupType, T: PositionType>(
+    groups:          &[G],
+    starts:          &[T],
+    ends:            &[T],
+    chrom_lens_map:  &FxHashMap<G, T>,
+    clip:            bool,
+    only_right:      bool,
+) -> Result<BoundsOutput<T>, String> {
+    let chrom_lens = chrom_lens_per_row(groups, chrom_lens_map)?;
+    outside_bounds_parallel(groups, starts, ends, &chrom_lens, clip, only_right)
+}
+
+/// Expands a chromosome-length map into one length per row, as expected by
+/// [`outside_bounds_parallel`] and [`outside_bounds_with_flags`].
+pub fn chrom_lens_per_row<G: GroupType, T: PositionType>(
+    groups:          &[G],
+    chrom_lens_map:  &FxHashMap<G, T>,
+) -> Result<Vec<T>, String> {
+    groups
+        .iter()
+        .map(|g| {
+            chrom_lens_map
+                .get(g)
+                .copied()
+                .ok_or_else(|| format!("No chromosome length given for group {:?}", g))
+        })
+        .collect()
+}
+
+/// Removes or clips intervals outside their chromosome, with the chromosome
+/// length given per row: `chrom_lens[i]` is the length for interval `i`, so
+/// no lookup table is built on the hot path.
+pub fn outside_bounds_parallel<G: GroupType, T: PositionType>(
+    groups:      &[G],
+    starts:      &[T],
+    ends:        &[T],
+    chrom_lens:  &[T],
+    clip:        bool,
+    only_right:  bool,
+) -> Result<BoundsOutput<T>, String> {
+    let (idx, out_starts, out_ends, _) =
+        outside_bounds_with_flags(groups, starts, ends, chrom_lens, clip, only_right)?;
+    Ok((idx, out_starts, out_ends))
+}
+
+/// `(indices, starts, ends, clip_flags)`
+pub type FlaggedBoundsOutput<T> = (Vec<u32>, Vec<T>, Vec<T>, Vec<u8>);
+
+/// Flag bit set when the start of a kept interval was raised to 0.
+pub const CLIPPED_LEFT: u8 = 1;
+/// Flag bit set when the end of a kept interval was lowered to the
+/// chromosome length.
+pub const CLIPPED_RIGHT: u8 = 2;
+
+/// Like [`outside_bounds_parallel`], but also returns one flag per kept row:
+/// `0` if it is unchanged, otherwise a combination of [`CLIPPED_LEFT`] and
+/// [`CLIPPED_RIGHT`] (`3` for both). Without `clip` every flag is `0`.
+pub fn outside_bounds_with_flags<G: GroupType, T: PositionType>(
     groups:      &[G],
     starts:      &[T],
     ends:        &[T],
     chrom_lens:  &[T],
     clip:        bool,
     only_right:  bool,
-) -> Result<(Vec<u32>, Vec<T>, Vec<T>), String> {
+) -> Result<FlaggedBoundsOutput<T>, String> {
 
     if starts.len() != ends.len()
         || groups.len() != starts.len()
@@ -22,6 +87,7 @@ pub fn outside_bounds<G: GroupType, T: PositionType>(
     let mut idx        = Vec::with_capacity(n);
     let mut out_starts = Vec::with_capacity(n);
     let mut out_ends   = Vec::with_capacity(n);
+    let mut flags      = Vec::with_capacity(n);
 
     for i in 0..n {
         let size        = chrom_lens[i];
@@ -40,6 +106,7 @@ pub fn outside_bounds<G: GroupType, T: PositionType>(
             idx.push(i);
             out_starts.push(orig_start);
             out_ends.push(orig_end);
+            flags.push(0);
         } else {
             // ===== Clipping mode ========================================
             if only_right {
@@ -51,6 +118,7 @@ pub fn outside_bounds<G: GroupType, T: PositionType>(
                 idx.push(i);
                 out_starts.push(orig_start);
                 out_ends.push(clipped_end);
+                flags.push(if orig_end > size { CLIPPED_RIGHT } else { 0 });
             } else {
                 // clip on both sides
                 if orig_start >= size || orig_end <= T::zero() { continue; }
@@ -61,11 +129,170 @@ pub fn outside_bounds<G: GroupType, T: PositionType>(
                 idx.push(i);
                 out_starts.push(clipped_start);
                 out_ends.push(clipped_end);
+
+                let mut flag = 0;
+                if orig_start < T::zero() { flag |= CLIPPED_LEFT; }
+                if orig_end   > size      { flag |= CLIPPED_RIGHT; }
+                flags.push(flag);
             }
         }
     }
 
     let idx_u32: Vec<u32> = idx.into_iter().map(|x| x as u32).collect();
 
-    Ok((idx_u32, out_starts, out_ends))
+    Ok((idx_u32, out_starts, out_ends, flags))
+}
+
+/// Wraps intervals on circular chromosomes back onto `[0, chrom_len)`.
+///
+/// Coordinates are reduced modulo the chromosome length. An interval that
+/// crosses the origin after wrapping is split into `[start, chrom_len)` and
+/// `[0, end)`, both reported with the same input index; with `clip` set only
+/// the first piece is kept. Intervals at least as long as the chromosome
+/// cover it entirely, and rows on a chromosome of length 0 are passed through
+/// unchanged. As in [`outside_bounds`], a group missing from
+/// `chrom_lens_map` is an error.
+pub fn outside_bounds_circular<G: GroupType, T: PositionType>(
+    groups:         &[G],
+    starts:         &[T],
+    ends:           &[T],
+    chrom_lens_map: &FxHashMap<G, T>,
+    clip:           bool,
+) -> Result<BoundsOutput<T>, String> {
+    let chrom_lens = chrom_lens_per_row(groups, chrom_lens_map)?;
+
+    let n = starts.len();
+    let mut idx        = Vec::with_capacity(n);
+    let mut out_starts = Vec::with_capacity(n);
+    let mut out_ends   = Vec::with_capacity(n);
+
+    for i in 0..n {
+        let (start, end) = (starts[i], ends[i]);
+
+        let size = chrom_lens[i];
+        if size <= T::zero() {
+            idx.push(i as u32);
+            out_starts.push(start);
+            out_ends.push(end);
+            continue;
+        }
+
+        let len = end - start;
+        if len >= size {
+            idx.push(i as u32);
+            out_starts.push(T::zero());
+            out_ends.push(size);
+            continue;
+        }
+
+        // `%` keeps the sign of the dividend, so shift negative starts up.
+        let mut new_start = start % size;
+        if new_start < T::zero() {
+            new_start = new_start + size;
+        }
+        let new_end = new_start + len;
+
+        if new_end <= size {
+            idx.push(i as u32);
+            out_starts.push(new_start);
+            out_ends.push(new_end);
+        } else {
+            idx.push(i as u32);
+            out_starts.push(new_start);
+            out_ends.push(size);
+
+            if !clip {
+                idx.push(i as u32);
+                out_starts.push(T::zero());
+                out_ends.push(new_end - size);
+            }
+        }
+    }
+
+    Ok((idx, out_starts, out_ends))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outside_bounds_circular_splits_wrapped_interval() {
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 100i64);
+
+        let (idx, starts, ends) = outside_bounds_circular(
+            &[0u32, 0, 0],
+            &[10i64, 90, 150],
+            &[20i64, 110, 160],
+            &lens,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(idx, vec![0, 1, 1, 2]);
+        assert_eq!(starts, vec![10, 90, 0, 50]);
+        assert_eq!(ends, vec![20, 100, 10, 60]);
+
+        let err = outside_bounds_circular(&[0u32, 2], &[0i64, 0], &[10i64, 10], &lens, false);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_outside_bounds_uses_length_map() {
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 100i64);
+        lens.insert(1u32, 50i64);
+
+        let (idx, starts, ends) =
+            outside_bounds(&[0u32, 1, 1], &[90i64, 40, 60], &[110i64, 60, 70], &lens, true, false).unwrap();
+        assert_eq!(idx, vec![0, 1]);
+        assert_eq!(starts, vec![90, 40]);
+        assert_eq!(ends, vec![100, 50]);
+
+        let err = outside_bounds(&[0u32, 2], &[0i64, 0], &[10i64, 10], &lens, false, false);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_outside_bounds_parallel_matches_length_map() {
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 100i64);
+        lens.insert(1u32, 50i64);
+        let groups = [0u32, 1, 1, 0, 1];
+        let starts = [90i64, 40, 60, -10, -5];
+        let ends = [110i64, 60, 70, 5, 20];
+        let per_row = chrom_lens_per_row(&groups, &lens).unwrap();
+
+        for (clip, only_right) in [(false, false), (false, true), (true, false), (true, true)] {
+            assert_eq!(
+                outside_bounds_parallel(&groups, &starts, &ends, &per_row, clip, only_right),
+                outside_bounds(&groups, &starts, &ends, &lens, clip, only_right),
+                "clip {clip}, only_right {only_right}"
+            );
+        }
+        let (idx, s, e) = outside_bounds_parallel(&groups, &starts, &ends, &per_row, true, false).unwrap();
+        assert_eq!(idx, vec![0, 1, 3, 4]);
+        assert_eq!(s, vec![90, 40, 0, 0]);
+        assert_eq!(e, vec![100, 50, 5, 20]);
+        assert!(outside_bounds_parallel(&groups, &starts, &ends, &per_row[..4], false, false).is_err());
+    }
+
+    #[test]
+    fn test_outside_bounds_clip_flags() {
+        let groups = [0u32; 4];
+        let starts = [-5i64, 10, 90, -5];
+        let ends = [10i64, 20, 120, 120];
+        let lens = [100i64; 4];
+
+        let (idx, _, _, flags) =
+            outside_bounds_with_flags(&groups, &starts, &ends, &lens, true, false).unwrap();
+        assert_eq!(idx, vec![0, 1, 2, 3]);
+        assert_eq!(flags, vec![CLIPPED_LEFT, 0, CLIPPED_RIGHT, CLIPPED_LEFT | CLIPPED_RIGHT]);
+
+        let (_, starts_out, _, flags) =
+            outside_bounds_with_flags(&groups, &starts, &ends, &lens, true, true).unwrap();
+        assert_eq!(starts_out, vec![-5, 10, 90, -5]);
+        assert_eq!(flags, vec![0, 0, CLIPPED_RIGHT, CLIPPED_RIGHT]);
+    }
 }
@@ -4,10 +4,14 @@ use std::time::{Duration, Instant};
 use radsort::sort_by_key;
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::helpers::{keep_first_by_idx, keep_last_by_idx};
-use crate::ruranges_structs::{GroupType, MaxEvent, MinEvent, OverlapPair, OverlapType, PositionType};
+use crate::helpers::{keep_first_by_idx, keep_first_n_by_idx, keep_last_by_idx, saturating_add_slack};
+use crate::ruranges_structs::{
+    AnnotateType, ContainmentType, CoordinateType, GenericEvent, GroupType, MaxEvent, MinEvent, OverlapPair, OverlapSortBy, OverlapType,
+    PositionType, StrandMode,
+};
 use crate::sorts::{
-    self, build_sorted_events_single_collection_separate_outputs, build_sorted_maxevents_with_starts_ends
+    self, build_sorted_events_single_collection_separate_outputs, build_sorted_maxevents_with_starts_ends,
+    is_point_event,
 };
 
 /// Perform a four-way merge sweep to find cross overlaps.
@@ -30,8 +34,50 @@ impl WhichList {
     }
 }
 
+/// `max_per_query` keeps at most that many matches per set-1 interval,
+/// applied after `overlap_type`. The kept matches are those with the lowest
+/// set-2 indices; `sort_by` (`"idx1"`, `"idx2"`, `"length_desc"`,
+/// `"length_asc"` or `"none"`, see [`OverlapSortBy`]) only reorders the
+/// result and does not change which are kept. The length orders sort by bases shared, ties by `idx`.
+///
+/// `min_overlap_bp` drops pairs sharing fewer bases, before any of the
+/// above: the intersection `min(end, end2) - max(start, start2)` must be at
+/// least that long, so touching pairs (and pairs only joined by slack) fail
+/// any positive threshold. Lengths are always taken from the intervals as
+/// given, never from their slack-widened form.
+///
+/// `strand_mode` (`"any"`, `"same"` or `"opposite"`, see [`StrandMode`])
+/// keeps only pairs whose `strands` entries match or differ, also before
+/// `overlap_type` picks among the hits. `strands` holds one flag per row of
+/// each set and is only read, and then required, when the mode is not
+/// `"any"`.
+#[allow(clippy::too_many_arguments)]
+pub fn overlaps<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+    overlap_type: &str,
+    sort_by: &str,
+    contained: bool,
+    max_per_query: Option<usize>,
+    min_overlap_bp: Option<T>,
+    strand_mode: &str,
+    strands: Option<(&[bool], &[bool])>,
+) -> (Vec<u32>, Vec<u32>) {
+    overlaps_measured_on(
+        chrs, starts, ends, chrs2, starts2, ends2, slack, overlap_type, sort_by, contained, max_per_query,
+        min_overlap_bp, strand_mode, strands, (starts, ends),
+    )
+}
+
+/// [`overlaps`] sweeping `starts`/`ends` but measuring `overlap_len` on
+/// `raw`, the set-1 coordinates before any per-row widening.
 #[allow(clippy::too_many_arguments)]
-pub fn overlaps<C: GroupType, T: PositionType>(
+fn overlaps_measured_on<C: GroupType, T: CoordinateType>(
     chrs: &[C],
     starts: &[T],
     ends: &[T],
@@ -40,11 +86,18 @@ pub fn overlaps<C: GroupType, T: PositionType>(
     ends2: &[T],
     slack: T,
     overlap_type: &str,
-    sort_output: bool,
+    sort_by: &str,
     contained: bool,
+    max_per_query: Option<usize>,
+    min_overlap_bp: Option<T>,
+    strand_mode: &str,
+    strands: Option<(&[bool], &[bool])>,
+    raw: (&[T], &[T]),
 ) -> (Vec<u32>, Vec<u32>) {
     let overlap_type = OverlapType::from_str(overlap_type)
         .expect("invalid overlap_type string");
+    let sort_by = OverlapSortBy::from_str(sort_by).expect("invalid sort_by string");
+    let strand_mode = StrandMode::from_str(strand_mode).expect("invalid strand_mode string");
 
     let mut pairs = if contained {
         let maxevents = compute_sorted_maxevents(
@@ -55,7 +108,28 @@ pub fn overlaps<C: GroupType, T: PositionType>(
         sweep_line_overlaps(chrs, starts, ends, chrs2, starts2, ends2, slack)
     };
 
-    if sort_output || (overlap_type == OverlapType::First || overlap_type == OverlapType::Last) {
+    let (raw_starts, raw_ends) = raw;
+    for p in pairs.iter_mut() {
+        let (i, j) = (p.idx as usize, p.idx2 as usize);
+        p.overlap_len = overlap_len(raw_starts[i], raw_ends[i], starts2[j], ends2[j]);
+    }
+
+    if let Some(min_len) = min_overlap_bp {
+        pairs.retain(|p| p.overlap_len >= min_len);
+    }
+
+    if strand_mode != StrandMode::Any {
+        let (strands, strands2) = strands.expect("strand_mode other than \"any\" needs strands");
+        pairs.retain(|p| strand_mode.keeps(strands[p.idx as usize], strands2[p.idx2 as usize]));
+    }
+
+    // The sweep emits a query's matches in hash order; go by set-2 index
+    // so that the matches `max_per_query` keeps do not depend on it.
+    let trims = max_per_query.is_some() && overlap_type == OverlapType::All;
+    if trims {
+        sort_by_key(&mut pairs, |p| p.idx2);
+    }
+    if trims || sort_by != OverlapSortBy::None || (overlap_type == OverlapType::First || overlap_type == OverlapType::Last) {
         sort_by_key(&mut pairs, |p| p.idx);
     }
 
@@ -65,9 +139,297 @@ pub fn overlaps<C: GroupType, T: PositionType>(
         OverlapType::Last => keep_last_by_idx(&mut pairs),
     }
 
+    if let Some(n) = max_per_query {
+        keep_first_n_by_idx(&mut pairs, n);
+    }
+
+    // Stable, so pairs sharing a set-2 index stay ordered by set-1 index.
+    if sort_by == OverlapSortBy::Idx2 {
+        sort_by_key(&mut pairs, |p| p.idx2);
+    }
+    match sort_by {
+        OverlapSortBy::LengthAsc => sort_by_key(&mut pairs, |p| p.overlap_len),
+        OverlapSortBy::LengthDesc => pairs.sort_by(|a, b| {
+            b.overlap_len.partial_cmp(&a.overlap_len).unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        _ => {}
+    }
+
     pairs.into_iter().map(|pair| (pair.idx, pair.idx2)).unzip()
 }
 
+/// Pairs where one interval lies entirely inside the other, as
+/// `(idx, idx2)` sorted by `idx`, then `idx2`.
+///
+/// `how` picks the direction (see [`ContainmentType`]): `"a_in_b"` is what
+/// `overlaps(.., contained = true)` reports, a set-1 interval inside a set-2
+/// interval; `"b_in_a"` the reverse; `"either"` both, with pairs of equal
+/// intervals listed once. Containment is inclusive at both ends, and set-1
+/// intervals are widened by `slack` on both sides, clamped at zero, before
+/// testing.
+#[allow(clippy::too_many_arguments)]
+pub fn contained_overlaps<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+    how: &str,
+) -> (Vec<u32>, Vec<u32>) {
+    let how = ContainmentType::from_str(how).expect("invalid containment string");
+
+    let wide_starts: Vec<T> = starts.iter().map(|&s| if slack < s { s - slack } else { T::zero() }).collect();
+    let wide_ends: Vec<T> = ends.iter().map(|&e| saturating_add_slack(e, slack)).collect();
+
+    let mut pairs: Vec<(u32, u32)> = Vec::new();
+    if how != ContainmentType::BInA {
+        let events = compute_sorted_maxevents(chrs, &wide_starts, &wide_ends, chrs2, starts2, ends2, T::zero(), false);
+        pairs.extend(sweep_line_overlaps_containment(events).into_iter().map(|p| (p.idx, p.idx2)));
+    }
+    if how != ContainmentType::AInB {
+        // Swap the sets, so set 2 is the contained side, and swap back.
+        let events = compute_sorted_maxevents(chrs2, starts2, ends2, chrs, &wide_starts, &wide_ends, T::zero(), false);
+        pairs.extend(sweep_line_overlaps_containment(events).into_iter().map(|p| (p.idx2, p.idx)));
+    }
+
+    sort_by_key(&mut pairs, |p| p.1);
+    sort_by_key(&mut pairs, |p| p.0);
+    pairs.dedup();
+
+    pairs.into_iter().unzip()
+}
+
+/// For every set-1 interval, the label of one overlapping set-2 interval, or
+/// `None` when nothing overlaps it: a left join, where [`overlaps`] is an
+/// inner join.
+///
+/// `overlap_type` picks among several hits (see [`AnnotateType`]):
+/// `"first"` and `"last"` go by genomic order of the set-2 intervals (start,
+/// then input index), not input order; `"largest"` takes the one sharing
+/// the most bases, ties going to the first in genomic order.
+#[allow(clippy::too_many_arguments)]
+pub fn annotate_overlaps<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    labels2: &[u32],
+    slack: T,
+    overlap_type: &str,
+) -> Vec<Option<u32>> {
+    assert_eq!(labels2.len(), chrs2.len(), "labels2 must have one entry per set-2 interval");
+    let how = AnnotateType::from_str(overlap_type).expect("invalid overlap_type string");
+
+    // Best set-2 index and its overlap length per set-1 interval.
+    let mut best: Vec<Option<(u32, T)>> = vec![None; chrs.len()];
+    let genomic_order = |j: u32| (starts2[j as usize], j);
+    for pair in sweep_line_overlaps(chrs, starts, ends, chrs2, starts2, ends2, slack) {
+        let slot = &mut best[pair.idx as usize];
+        let replace = match *slot {
+            None => true,
+            Some((cur, cur_len)) => match how {
+                AnnotateType::First => genomic_order(pair.idx2) < genomic_order(cur),
+                AnnotateType::Last => genomic_order(pair.idx2) > genomic_order(cur),
+                AnnotateType::Largest => {
+                    pair.overlap_len > cur_len
+                        || (pair.overlap_len == cur_len && genomic_order(pair.idx2) < genomic_order(cur))
+                }
+            },
+        };
+        if replace {
+            *slot = Some((pair.idx2, pair.overlap_len));
+        }
+    }
+
+    best.into_iter().map(|b| b.map(|(j, _)| labels2[j as usize])).collect()
+}
+
+/// [`overlaps`] with one slack value per set-1 interval instead of a single
+/// scalar. Each `slacks[i]` widens interval `i` exactly like a scalar slack
+/// would.
+#[allow(clippy::too_many_arguments)]
+pub fn overlaps_row_slack<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slacks: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    overlap_type: &str,
+    sort_by: &str,
+    contained: bool,
+    max_per_query: Option<usize>,
+    min_overlap_bp: Option<T>,
+    strand_mode: &str,
+    strands: Option<(&[bool], &[bool])>,
+) -> (Vec<u32>, Vec<u32>) {
+    let (wide_starts, wide_ends) = widen_by_row_slack(starts, ends, slacks);
+    overlaps_measured_on(
+        chrs, &wide_starts, &wide_ends, chrs2, starts2, ends2, T::zero(), overlap_type, sort_by, contained,
+        max_per_query, min_overlap_bp, strand_mode, strands, (starts, ends),
+    )
+}
+
+/// [`count_overlaps`] with one slack value per set-1 interval.
+pub fn count_overlaps_row_slack<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slacks: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+) -> Vec<u32> {
+    let (wide_starts, wide_ends) = widen_by_row_slack(starts, ends, slacks);
+    count_overlaps(chrs, &wide_starts, &wide_ends, chrs2, starts2, ends2, T::zero())
+}
+
+/// Applies per-row slack the same way the event builders apply a scalar one:
+/// starts move left (not below zero) and ends move right.
+pub(crate) fn widen_by_row_slack<T: CoordinateType>(starts: &[T], ends: &[T], slacks: &[T]) -> (Vec<T>, Vec<T>) {
+    assert_eq!(slacks.len(), starts.len(), "slacks must have one entry per interval");
+
+    let wide_starts = starts
+        .iter()
+        .zip(slacks)
+        .map(|(&s, &slack)| if slack < s { s - slack } else { T::zero() })
+        .collect();
+    let wide_ends = ends.iter().zip(slacks).map(|(&e, &slack)| saturating_add_slack(e, slack)).collect();
+    (wide_starts, wide_ends)
+}
+
+/// All-vs-all overlaps within a single set as a sparse upper-triangular
+/// matrix in COO format: only pairs with `row < col` are returned, so
+/// self-overlaps and mirrored duplicates are left out.
+pub fn overlap_matrix<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+) -> (Vec<u32>, Vec<u32>) {
+    self_overlaps(chrs, starts, ends, slack)
+}
+
+/// Overlapping pairs `(i, j)` with `i < j` within one set, sorted by `i`
+/// then `j`.
+///
+/// Sweeps a single event stream instead of matching the set against a copy
+/// of itself, so self-pairs never come up. Two intervals overlap when the
+/// gap between them is smaller than `slack`, which is what the two-set sweep
+/// reports for a set against itself.
+pub fn self_overlaps<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+) -> (Vec<u32>, Vec<u32>) {
+    let mut pairs: Vec<(u32, u32)> = Vec::new();
+
+    if chrs.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let events = sorts::build_sorted_events_single_collection_points_last(chrs, starts, ends, slack);
+    let is_point = |i: u32| slack.is_zero() && starts[i as usize] == ends[i as usize];
+
+    let mut active = FxHashSet::default();
+    let mut current_chr = events.first().unwrap().chr;
+
+    for e in events {
+        if e.chr != current_chr {
+            active.clear();
+            current_chr = e.chr;
+        }
+
+        if e.is_start {
+            for &other in active.iter() {
+                pairs.push(if other < e.idx { (other, e.idx) } else { (e.idx, other) });
+            }
+            // Points never become active
+            if !is_point(e.idx) {
+                active.insert(e.idx);
+            }
+        } else {
+            active.remove(&e.idx);
+        }
+    }
+
+    sort_by_key(&mut pairs, |p| p.1);
+    sort_by_key(&mut pairs, |p| p.0);
+
+    pairs.into_iter().unzip()
+}
+
+/// Pairs `(i, j)` with `i < j` within one set that lie at most `max_gap`
+/// apart, with their signed gap, sorted by `i` then `j`.
+///
+/// The gap is `max(start_i, start_j) - min(end_i, end_j)`: positive for
+/// intervals apart, 0 for touching ones and minus the overlap length for
+/// overlapping ones, so every overlapping pair is reported. With
+/// `max_pairs`, the sweep stops once that many pairs are found; the result
+/// then holds the pairs seen first in start order, not the closest ones.
+pub fn pairs_within<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    max_gap: T,
+    max_pairs: Option<usize>,
+) -> (Vec<u32>, Vec<u32>, Vec<T>) {
+    let mut pairs: Vec<(u32, u32, T)> = Vec::new();
+
+    if chrs.is_empty() {
+        return (Vec::new(), Vec::new(), Vec::new());
+    }
+
+    // Ends are pushed out by `max_gap` and starts sort first at ties, so an
+    // interval starting exactly `max_gap` after another one's end still meets it.
+    let events = sorts::build_sorted_events_single_collection_starts_first(chrs, starts, ends, max_gap);
+    let limit = max_pairs.unwrap_or(usize::MAX);
+
+    let mut active = FxHashSet::default();
+    let mut current_chr = events.first().unwrap().chr;
+
+    'sweep: for e in events {
+        if e.chr != current_chr {
+            active.clear();
+            current_chr = e.chr;
+        }
+
+        if e.is_start {
+            let i = e.idx as usize;
+            for &other in active.iter() {
+                if pairs.len() >= limit {
+                    break 'sweep;
+                }
+                let j = other as usize;
+                let gap = starts[i].max(starts[j]) - ends[i].min(ends[j]);
+                pairs.push(if other < e.idx { (other, e.idx, gap) } else { (e.idx, other, gap) });
+            }
+            active.insert(e.idx);
+        } else {
+            active.remove(&e.idx);
+        }
+    }
+
+    sort_by_key(&mut pairs, |p| p.1);
+    sort_by_key(&mut pairs, |p| p.0);
+
+    let mut idx = Vec::with_capacity(pairs.len());
+    let mut idx2 = Vec::with_capacity(pairs.len());
+    let mut gaps = Vec::with_capacity(pairs.len());
+    for (i, j, gap) in pairs {
+        idx.push(i);
+        idx2.push(j);
+        gaps.push(gap);
+    }
+    (idx, idx2, gaps)
+}
+
 pub fn sweep_line_overlaps_set1<C: GroupType, T: PositionType>(
     chrs: &[C],
     starts: &[T],
@@ -137,6 +499,110 @@ pub fn count_overlaps<C: GroupType, T: PositionType>(
     starts2: &[T],
     ends2: &[T],
     slack: T,
+) -> Vec<u32> {
+    count_overlaps_keeping(chrs, starts, ends, chrs2, starts2, ends2, slack, |_, _| true)
+}
+
+/// [`count_overlaps`] counting only pairs allowed by `strand_mode` (see
+/// [`StrandMode`]); `strands` and `strands2` hold one flag per row.
+#[allow(clippy::too_many_arguments)]
+pub fn count_overlaps_stranded<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    strands: &[bool],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    strands2: &[bool],
+    slack: T,
+    strand_mode: &str,
+) -> Vec<u32> {
+    let strand_mode = StrandMode::from_str(strand_mode).expect("invalid strand_mode string");
+    count_overlaps_keeping(chrs, starts, ends, chrs2, starts2, ends2, slack, |idx1, idx2| {
+        strand_mode.keeps(strands[idx1 as usize], strands2[idx2 as usize])
+    })
+}
+
+/// Whether each set-1 interval overlaps at least one set-2 interval.
+///
+/// Same overlap rules as [`count_overlaps`], but a set-1 interval leaves the
+/// active set as soon as its first match is found and set 2 is only tracked
+/// as a count, so dense data costs no more than sparse.
+pub fn any_overlap<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> Vec<bool> {
+    let mut found = vec![false; chrs.len()];
+
+    if chrs.is_empty() | chrs2.is_empty() {
+        return found;
+    };
+
+    let events = sorts::build_sorted_events_points_last(chrs, starts, ends, chrs2, starts2, ends2, slack);
+
+    // Set-1 intervals still without a match, and the number of active set-2
+    // intervals.
+    let mut unmatched1 = FxHashSet::default();
+    let mut active2: i64 = 0;
+
+    let is_point = |e: &GenericEvent<C, T>| is_point_event(e, starts, ends, starts2, ends2, slack);
+
+    let mut current_chr = events.first().unwrap().chr;
+
+    for e in events {
+        if e.chr != current_chr {
+            unmatched1.clear();
+            active2 = 0;
+            current_chr = e.chr;
+        }
+
+        match (e.is_start, e.first_set) {
+            (true, true) => {
+                if active2 > 0 {
+                    found[e.idx as usize] = true;
+                } else if !is_point(&e) {
+                    unmatched1.insert(e.idx);
+                }
+            }
+            (true, false) => {
+                for idx in unmatched1.drain() {
+                    found[idx as usize] = true;
+                }
+                if !is_point(&e) {
+                    active2 += 1;
+                }
+            }
+            (false, true) => {
+                unmatched1.remove(&e.idx);
+            }
+            (false, false) => {
+                // Points never became active, so their end is skipped too.
+                if !is_point(&e) {
+                    active2 -= 1;
+                }
+            }
+        }
+    }
+
+    found
+}
+
+#[allow(clippy::too_many_arguments)]
+fn count_overlaps_keeping<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+    keep: impl Fn(u32, u32) -> bool,
 ) -> Vec<u32> {
     // We'll collect all cross overlaps here
     let mut overlaps = vec![0; chrs.len()];
@@ -145,12 +611,14 @@ pub fn count_overlaps<C: GroupType, T: PositionType>(
         return overlaps;
     };
 
-    let events = sorts::build_sorted_events(chrs, starts, ends, chrs2, starts2, ends2, slack);
+    let events = sorts::build_sorted_events_points_last(chrs, starts, ends, chrs2, starts2, ends2, slack);
 
     // Active sets
     let mut active1 = FxHashSet::default();
     let mut active2 = FxHashSet::default();
 
+    let is_point = |e: &GenericEvent<C, T>| is_point_event(e, starts, ends, starts2, ends2, slack);
+
     let mut current_chr = events.first().unwrap().chr;
 
     // Process events in ascending order of position
@@ -164,18 +632,26 @@ pub fn count_overlaps<C: GroupType, T: PositionType>(
             // Interval is starting
             if e.first_set {
                 // Overlaps with all currently active intervals in set2
-                for &_idx2 in active2.iter() {
-                    overlaps[e.idx as usize] += 1;
+                for &idx2 in active2.iter() {
+                    if keep(e.idx, idx2) {
+                        overlaps[e.idx as usize] += 1;
+                    }
+                }
+                // Now add it to active1 (points never become active)
+                if !is_point(&e) {
+                    active1.insert(e.idx);
                 }
-                // Now add it to active1
-                active1.insert(e.idx);
             } else {
                 // Overlaps with all currently active intervals in set1
                 for &idx1 in active1.iter() {
-                    overlaps[idx1 as usize] += 1;
+                    if keep(idx1, e.idx) {
+                        overlaps[idx1 as usize] += 1;
+                    }
                 }
                 // Now add it to active2
-                active2.insert(e.idx);
+                if !is_point(&e) {
+                    active2.insert(e.idx);
+                }
             }
         } else {
             // Interval is ending
@@ -190,12 +666,180 @@ pub fn count_overlaps<C: GroupType, T: PositionType>(
     overlaps
 }
 
+/// Like [`count_overlaps`], but also counts, for every set-2 interval, how
+/// many set-1 intervals overlap it. Both counts come from the same sweep:
+/// each start event already walks the active intervals of the other set.
+pub fn count_overlaps_both<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> (Vec<u32>, Vec<u32>) {
+    let mut counts = vec![0; chrs.len()];
+    let mut counts2 = vec![0; chrs2.len()];
+
+    if chrs.is_empty() | chrs2.is_empty() {
+        return (counts, counts2);
+    };
+
+    let events = sorts::build_sorted_events_points_last(chrs, starts, ends, chrs2, starts2, ends2, slack);
+
+    // Active sets
+    let mut active1 = FxHashSet::default();
+    let mut active2 = FxHashSet::default();
+
+    let is_point = |e: &GenericEvent<C, T>| is_point_event(e, starts, ends, starts2, ends2, slack);
+
+    let mut current_chr = events.first().unwrap().chr;
+
+    for e in events {
+        if e.chr != current_chr {
+            active1.clear();
+            active2.clear();
+            current_chr = e.chr;
+        }
+
+        if e.is_start {
+            if e.first_set {
+                counts[e.idx as usize] += active2.len() as u32;
+                for &idx2 in active2.iter() {
+                    counts2[idx2 as usize] += 1;
+                }
+                if !is_point(&e) {
+                    active1.insert(e.idx);
+                }
+            } else {
+                counts2[e.idx as usize] += active1.len() as u32;
+                for &idx1 in active1.iter() {
+                    counts[idx1 as usize] += 1;
+                }
+                if !is_point(&e) {
+                    active2.insert(e.idx);
+                }
+            }
+        } else if e.first_set {
+            active1.remove(&e.idx);
+        } else {
+            active2.remove(&e.idx);
+        }
+    }
+
+    (counts, counts2)
+}
+
+/// Total number of base pairs shared between set 1 and set 2.
+///
+/// Set 2 is treated as its union, so a set-1 base covered by several
+/// overlapping set-2 intervals is counted once. Overlapping set-1 intervals
+/// each contribute their own shared bases.
+pub fn intersection_size<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+) -> T {
+    let mut total = T::zero();
+
+    if chrs.is_empty() | chrs2.is_empty() {
+        return total;
+    };
+
+    let events = sorts::build_sorted_events(chrs, starts, ends, chrs2, starts2, ends2, T::zero());
+
+    let mut current_chr = events.first().unwrap().chr;
+    let mut previous_pos = events.first().unwrap().pos;
+    // Signed: a zero-length interval's end event precedes its start.
+    let mut active1: i64 = 0;
+    let mut active2: i64 = 0;
+
+    for e in events {
+        if e.chr != current_chr {
+            current_chr = e.chr;
+            active1 = 0;
+            active2 = 0;
+        } else if e.pos > previous_pos && active1 > 0 && active2 > 0 {
+            total = total + (e.pos - previous_pos) * T::from(active1).unwrap();
+        }
+
+        let delta = if e.is_start { 1 } else { -1 };
+        if e.first_set {
+            active1 += delta;
+        } else {
+            active2 += delta;
+        }
+        previous_pos = e.pos;
+    }
+
+    total
+}
+
+/// Dense `chrs.len() x chrs2.len()` overlap matrix in row-major order:
+/// entry `i * chrs2.len() + j` is 1 when interval `i` of set 1 overlaps
+/// interval `j` of set 2, 0 otherwise.
+///
+/// Meant for small sets; the result takes `4 * n * m` bytes.
+pub fn overlap_count_matrix<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> Vec<u32> {
+    let n_cols = chrs2.len();
+    let mut matrix = vec![0; chrs.len() * n_cols];
+
+    for pair in sweep_line_overlaps(chrs, starts, ends, chrs2, starts2, ends2, slack) {
+        matrix[pair.idx as usize * n_cols + pair.idx2 as usize] = 1;
+    }
+
+    matrix
+}
+
+/// Overlapping pairs from presorted start/end events, with `overlap_len`
+/// left at zero.
+#[deprecated(note = "lengths are not filled in; use `sweep_line_overlaps_overlap_pair_measured`")]
 pub fn sweep_line_overlaps_overlap_pair<C: GroupType, T: PositionType>(
+    sorted_starts: &[MinEvent<C, T>],
+    sorted_ends: &[MinEvent<C, T>],
+    sorted_starts2: &[MinEvent<C, T>],
+    sorted_ends2: &[MinEvent<C, T>],
+) -> Vec<OverlapPair<T>> {
+    sweep_overlap_pair_events(sorted_starts, sorted_ends, sorted_starts2, sorted_ends2, |_, _| T::zero())
+}
+
+/// Overlapping pairs from presorted start/end events. The events may be
+/// shifted by slack, but `overlap_len` is measured on the raw intervals:
+/// `coords` holds set 1's `(starts, ends)` and `coords2` set 2's, indexed by
+/// event `idx`.
+pub fn sweep_line_overlaps_overlap_pair_measured<C: GroupType, T: PositionType>(
+    sorted_starts: &[MinEvent<C, T>],
+    sorted_ends: &[MinEvent<C, T>],
+    sorted_starts2: &[MinEvent<C, T>],
+    sorted_ends2: &[MinEvent<C, T>],
+    coords: (&[T], &[T]),
+    coords2: (&[T], &[T]),
+) -> Vec<OverlapPair<T>> {
+    let ((starts, ends), (starts2, ends2)) = (coords, coords2);
+    sweep_overlap_pair_events(sorted_starts, sorted_ends, sorted_starts2, sorted_ends2, |i, j| {
+        let (i, j) = (i as usize, j as usize);
+        overlap_len(starts[i], ends[i], starts2[j], ends2[j])
+    })
+}
+
+fn sweep_overlap_pair_events<C: GroupType, T: PositionType>(
     sorted_starts: &[MinEvent<C, T>],  // set 1 starts
     sorted_ends: &[MinEvent<C, T>],    // set 1 ends
     sorted_starts2: &[MinEvent<C, T>], // set 2 starts
     sorted_ends2: &[MinEvent<C, T>],   // set 2 ends
-) -> Vec<OverlapPair> {
+    length_of: impl Fn(u32, u32) -> T,
+) -> Vec<OverlapPair<T>> {
     let mut out_idxs = Vec::new();
     // Quick check: if no starts exist in either set, no overlaps.
     if sorted_starts.is_empty() || sorted_starts2.is_empty() {
@@ -248,6 +892,7 @@ pub fn sweep_line_overlaps_overlap_pair<C: GroupType, T: PositionType>(
                     out_idxs.push(OverlapPair {
                         idx: event.idx,
                         idx2: idx2,
+                        overlap_len: length_of(event.idx, idx2),
                     })
                 }
                 // Now add it to active1
@@ -259,6 +904,7 @@ pub fn sweep_line_overlaps_overlap_pair<C: GroupType, T: PositionType>(
                     out_idxs.push(OverlapPair {
                         idx: idx1,
                         idx2: event.idx,
+                        overlap_len: length_of(idx1, event.idx),
                     })
                 }
                 // Now add it to active2
@@ -278,9 +924,9 @@ pub fn sweep_line_overlaps_overlap_pair<C: GroupType, T: PositionType>(
     out_idxs
 }
 
-pub fn sweep_line_overlaps_containment<C: GroupType, T: PositionType>(
+pub fn sweep_line_overlaps_containment<C: GroupType, T: CoordinateType>(
     events: Vec<MaxEvent<C, T>>,
-) -> (Vec<OverlapPair>) {
+) -> (Vec<OverlapPair<T>>) {
     // We'll collect all cross overlaps here
     let mut overlaps = Vec::new();
 
@@ -311,6 +957,7 @@ pub fn sweep_line_overlaps_containment<C: GroupType, T: PositionType>(
                         overlaps.push(OverlapPair {
                             idx: e.idx,
                             idx2: idx2,
+                            overlap_len: overlap_len(e.start, e.end, start2, end2),
                         });
                     };
                 }
@@ -323,6 +970,7 @@ pub fn sweep_line_overlaps_containment<C: GroupType, T: PositionType>(
                         overlaps.push(OverlapPair {
                             idx: idx,
                             idx2: e.idx,
+                            overlap_len: overlap_len(start, end, e.start, e.end),
                         });
                     };
                 }
@@ -342,6 +990,14 @@ pub fn sweep_line_overlaps_containment<C: GroupType, T: PositionType>(
     overlaps
 }
 
+#[inline]
+fn overlap_len<T: CoordinateType>(start: T, end: T, start2: T, end2: T) -> T {
+    // `PartialOrd` only, so that float coordinates work too.
+    let min_end = if end2 < end { end2 } else { end };
+    let max_start = if start2 > start { start2 } else { start };
+    min_end - max_start
+}
+
 fn pick_winner_of_four<'a, C: GroupType, T: PositionType>(
     s1: Option<(WhichList, &'a MinEvent<C, T>)>,
     s2: Option<(WhichList, &'a MinEvent<C, T>)>,
@@ -415,7 +1071,8 @@ pub fn compute_sorted_events<C: GroupType, T: PositionType>(
     }
 }
 
-pub fn compute_sorted_maxevents<C: GroupType, T: PositionType>(
+#[allow(clippy::too_many_arguments)]
+pub fn compute_sorted_maxevents<C: GroupType, T: CoordinateType>(
     chrs: &[C],
     starts: &[T],
     ends: &[T],
@@ -452,7 +1109,150 @@ pub fn compute_sorted_maxevents<C: GroupType, T: PositionType>(
 }
 
 
-pub fn sweep_line_overlaps<C: GroupType, T: PositionType>(
+pub fn sweep_line_overlaps<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> (Vec<OverlapPair<T>>) {
+    // Most queries hit at least one subject, so the larger input is a cheap
+    // lower bound that saves the first rounds of regrowth.
+    let capacity_hint = chrs.len().max(chrs2.len());
+    sweep_line_overlaps_with_capacity(chrs, starts, ends, chrs2, starts2, ends2, slack, capacity_hint)
+}
+
+/// Finds the intervals containing each point.
+///
+/// A point at `p` is treated as the zero-length interval `[p, p)` and
+/// overlaps every interval with `start <= p < end`; an interval ending at
+/// `p` does not contain it. Returns `(point_idx, interval_idx)` sorted by
+/// point index.
+pub fn point_overlaps<C: GroupType, T: PositionType>(
+    chrs_points: &[C],
+    positions: &[T],
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+) -> (Vec<u32>, Vec<u32>) {
+    let mut pairs = sweep_line_overlaps(chrs_points, positions, positions, chrs, starts, ends, T::zero());
+    sort_by_key(&mut pairs, |p| p.idx2);
+    sort_by_key(&mut pairs, |p| p.idx);
+
+    pairs.into_iter().map(|p| (p.idx, p.idx2)).unzip()
+}
+
+/// Overlaps of one query interval with a database already sorted by chr
+/// then start, without a sweep; returns positions in the sorted arrays.
+///
+/// The chromosome's block is found by binary search, as is the first
+/// interval starting at or after `query_end + slack`; everything before it
+/// in the block is checked directly. That is O(log n + m) for the m
+/// intervals of the chromosome that start before the query ends, so it
+/// suits single lookups (e.g. one variant) rather than batches.
+pub fn query_single_overlap<C: GroupType, T: PositionType>(
+    db_chrs_sorted: &[C],
+    db_starts_sorted: &[T],
+    db_ends_sorted: &[T],
+    query_chr: C,
+    query_start: T,
+    query_end: T,
+    slack: T,
+) -> Vec<u32> {
+    let lo = db_chrs_sorted.partition_point(|&c| c < query_chr);
+    let hi = lo + db_chrs_sorted[lo..].partition_point(|&c| c == query_chr);
+
+    let (start, end) = (query_start - slack, query_end + slack);
+    let stop = lo + db_starts_sorted[lo..hi].partition_point(|&s| s < end);
+
+    (lo..stop)
+        .filter(|&i| db_ends_sorted[i] > start)
+        .map(|i| i as u32)
+        .collect()
+}
+
+/// Overlapping pairs `(idx, idx2)` restricted to intervals that share a
+/// metadata group (e.g. gene or sample), i.e. `group_ids[idx] ==
+/// group_ids2[idx2]`. Sorted by `idx` then `idx2`.
+///
+/// The active sets hold `(idx, group_id)` so the group check happens during
+/// the sweep instead of filtering all overlaps afterwards.
+#[allow(clippy::too_many_arguments)]
+pub fn overlaps_within_group<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    group_ids: &[u32],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    group_ids2: &[u32],
+    slack: T,
+) -> (Vec<u32>, Vec<u32>) {
+    let mut pairs: Vec<(u32, u32)> = Vec::new();
+
+    let events = sorts::build_sorted_events_points_last(chrs, starts, ends, chrs2, starts2, ends2, slack);
+    if events.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut active1: FxHashSet<(u32, u32)> = FxHashSet::default();
+    let mut active2: FxHashSet<(u32, u32)> = FxHashSet::default();
+
+    let is_point = |e: &GenericEvent<C, T>| is_point_event(e, starts, ends, starts2, ends2, slack);
+
+    let mut current_chr = events.first().unwrap().chr;
+
+    for e in events {
+        if e.chr != current_chr {
+            active1.clear();
+            active2.clear();
+            current_chr = e.chr;
+        }
+
+        if e.is_start {
+            if e.first_set {
+                let group = group_ids[e.idx as usize];
+                for &(idx2, group2) in active2.iter() {
+                    if group2 == group {
+                        pairs.push((e.idx, idx2));
+                    }
+                }
+                if !is_point(&e) {
+                    active1.insert((e.idx, group));
+                }
+            } else {
+                let group2 = group_ids2[e.idx as usize];
+                for &(idx, group) in active1.iter() {
+                    if group == group2 {
+                        pairs.push((idx, e.idx));
+                    }
+                }
+                if !is_point(&e) {
+                    active2.insert((e.idx, group2));
+                }
+            }
+        } else if e.first_set {
+            active1.remove(&(e.idx, group_ids[e.idx as usize]));
+        } else {
+            active2.remove(&(e.idx, group_ids2[e.idx as usize]));
+        }
+    }
+
+    sort_by_key(&mut pairs, |p| p.1);
+    sort_by_key(&mut pairs, |p| p.0);
+
+    pairs.into_iter().unzip()
+}
+
+/// Same as [`sweep_line_overlaps`], but reserves room for `capacity_hint`
+/// pairs up front. Callers that know the expected number of overlaps (e.g.
+/// from a previous run or from `count_overlaps`) can pass it to avoid
+/// repeatedly reallocating a very large output vector.
+#[allow(clippy::too_many_arguments)]
+pub fn sweep_line_overlaps_with_capacity<C: GroupType, T: CoordinateType>(
     chrs: &[C],
     starts: &[T],
     ends: &[T],
@@ -460,11 +1260,12 @@ pub fn sweep_line_overlaps<C: GroupType, T: PositionType>(
     starts2: &[T],
     ends2: &[T],
     slack: T,
-) -> (Vec<OverlapPair>) {
+    capacity_hint: usize,
+) -> Vec<OverlapPair<T>> {
     // We'll collect all cross overlaps here
-    let mut overlaps = Vec::new();
+    let mut overlaps = Vec::with_capacity(capacity_hint);
 
-    let events = sorts::build_sorted_events(chrs, starts, ends, chrs2, starts2, ends2, slack);
+    let events = sorts::build_sorted_events_points_last(chrs, starts, ends, chrs2, starts2, ends2, slack);
 
     if events.is_empty() {
         return overlaps;
@@ -474,6 +1275,8 @@ pub fn sweep_line_overlaps<C: GroupType, T: PositionType>(
     let mut active1 = FxHashSet::default();
     let mut active2 =FxHashSet::default();
 
+    let is_point = |e: &GenericEvent<C, T>| is_point_event(e, starts, ends, starts2, ends2, slack);
+
     let mut current_chr = events.first().unwrap().chr;
 
     // Process events in ascending order of position
@@ -488,23 +1291,33 @@ pub fn sweep_line_overlaps<C: GroupType, T: PositionType>(
             // Interval is starting
             if e.first_set {
                 // Overlaps with all currently active intervals in set2
+                let (start, end) = (starts[e.idx as usize], ends[e.idx as usize]);
                 for &idx2 in active2.iter() {
+                    let j = idx2 as usize;
                     overlaps.push(OverlapPair {
                         idx: e.idx,
                         idx2: idx2,
+                        overlap_len: overlap_len(start, end, starts2[j], ends2[j]),
                     });
                 }
-                // Now add it to active1
-                active1.insert(e.idx);
+                // Now add it to active1 (points never become active)
+                if !is_point(&e) {
+                    active1.insert(e.idx);
+                }
             } else {
                 // Overlaps with all currently active intervals in set1
+                let (start2, end2) = (starts2[e.idx as usize], ends2[e.idx as usize]);
                 for &idx in active1.iter() {
+                        let i = idx as usize;
                         overlaps.push(OverlapPair {
                             idx: idx,
                             idx2: e.idx,
+                            overlap_len: overlap_len(starts[i], ends[i], start2, end2),
                         });
                     };
-                active2.insert(e.idx);
+                if !is_point(&e) {
+                    active2.insert(e.idx);
+                }
             }
         } else {
             // Interval is ending
@@ -518,3 +1331,600 @@ pub fn sweep_line_overlaps<C: GroupType, T: PositionType>(
 
     overlaps
 }
+
+/// Resumable form of [`sweep_line_overlaps`] that hands out the pairs in
+/// chunks instead of collecting them all, so peak memory is bounded by the
+/// chunk size rather than the number of overlaps.
+///
+/// Each call to `next` continues the sweep until at least `chunk_size`
+/// pairs are found or the events run out. Events are processed whole, so a
+/// chunk can exceed `chunk_size` by the number of intervals active at its
+/// last event. Concatenating the chunks gives the output of
+/// [`sweep_line_overlaps`].
+pub struct OverlapChunks<C: GroupType, T: CoordinateType> {
+    events: Vec<GenericEvent<C, T>>,
+    next_event: usize,
+    starts: Vec<T>,
+    ends: Vec<T>,
+    starts2: Vec<T>,
+    ends2: Vec<T>,
+    slack: T,
+    chunk_size: usize,
+    active1: FxHashSet<u32>,
+    active2: FxHashSet<u32>,
+}
+
+impl<C: GroupType, T: CoordinateType> OverlapChunks<C, T> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        chrs: &[C],
+        starts: &[T],
+        ends: &[T],
+        chrs2: &[C],
+        starts2: &[T],
+        ends2: &[T],
+        slack: T,
+        chunk_size: usize,
+    ) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be positive");
+
+        Self {
+            events: sorts::build_sorted_events_points_last(chrs, starts, ends, chrs2, starts2, ends2, slack),
+            next_event: 0,
+            starts: starts.to_vec(),
+            ends: ends.to_vec(),
+            starts2: starts2.to_vec(),
+            ends2: ends2.to_vec(),
+            slack,
+            chunk_size,
+            active1: FxHashSet::default(),
+            active2: FxHashSet::default(),
+        }
+    }
+}
+
+impl<C: GroupType, T: CoordinateType> Iterator for OverlapChunks<C, T> {
+    type Item = Vec<OverlapPair<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+
+        while chunk.len() < self.chunk_size && self.next_event < self.events.len() {
+            let e = &self.events[self.next_event];
+            if self.next_event > 0 && self.events[self.next_event - 1].chr != e.chr {
+                self.active1.clear();
+                self.active2.clear();
+            }
+            self.next_event += 1;
+
+            let i = e.idx as usize;
+            if !e.is_start {
+                if e.first_set {
+                    self.active1.remove(&e.idx);
+                } else {
+                    self.active2.remove(&e.idx);
+                }
+                continue;
+            }
+
+            let is_point = is_point_event(e, &self.starts, &self.ends, &self.starts2, &self.ends2, self.slack);
+            if e.first_set {
+                for &idx2 in self.active2.iter() {
+                    let j = idx2 as usize;
+                    chunk.push(OverlapPair {
+                        idx: e.idx,
+                        idx2,
+                        overlap_len: overlap_len(self.starts[i], self.ends[i], self.starts2[j], self.ends2[j]),
+                    });
+                }
+                if !is_point {
+                    self.active1.insert(e.idx);
+                }
+            } else {
+                for &idx in self.active1.iter() {
+                    let j = idx as usize;
+                    chunk.push(OverlapPair {
+                        idx,
+                        idx2: e.idx,
+                        overlap_len: overlap_len(self.starts[j], self.ends[j], self.starts2[i], self.ends2[i]),
+                    });
+                }
+                if !is_point {
+                    self.active2.insert(e.idx);
+                }
+            }
+        }
+
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlaps_float_coordinates() {
+        let chrs = [0u32, 0];
+        let starts = [0.0f64, 0.5];
+        let ends = [0.25f64, 0.75];
+        let chrs2 = [0u32, 0];
+        let starts2 = [0.2f64, 0.25];
+        let ends2 = [0.3f64, 0.5];
+
+        let (idx, idx2) = overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0.0, "all", "idx1", false, None, None, "any", None);
+        // [0.25, 0.5) only touches both set-1 intervals.
+        assert_eq!((idx, idx2), (vec![0], vec![0]));
+
+        let (idx, idx2) = overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0.01, "all", "idx1", false, None, None, "any", None);
+        assert_eq!((idx, idx2), (vec![0, 0, 1], vec![0, 1, 1]));
+    }
+
+    #[test]
+    fn test_contained_overlaps_directions() {
+        // 0 lies in subject 0, 1 contains subject 1, 2 equals subject 2.
+        let chrs = [0u32, 0, 0];
+        let starts = [10i64, 100, 200];
+        let ends = [20i64, 200, 300];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [0i64, 120, 200];
+        let ends2 = [50i64, 130, 300];
+
+        let run = |how| contained_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, how);
+        assert_eq!(run("a_in_b"), (vec![0, 2], vec![0, 2]));
+        assert_eq!(run("b_in_a"), (vec![1, 2], vec![1, 2]));
+        assert_eq!(run("either"), (vec![0, 1, 2], vec![0, 1, 2]));
+
+        // a_in_b matches the `contained` flag of `overlaps`.
+        let (idx, idx2) = overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, "all", "idx1", true, None, None, "any", None);
+        assert_eq!((idx, idx2), run("a_in_b"));
+
+        // Widening stops at zero, so [2, 5) with slack 5 still lies in [0, 10).
+        let widened = contained_overlaps(&[0u32], &[2i64], &[5i64], &[0u32], &[0i64], &[10i64], 5, "a_in_b");
+        assert_eq!(widened, (vec![0], vec![0]));
+    }
+
+    #[test]
+    fn test_query_single_overlap_matches_sweep() {
+        let chrs = [0u32, 1, 1, 1, 1, 2];
+        let starts = [0i64, 0, 5, 12, 30, 0];
+        let ends = [100i64, 50, 8, 20, 40, 10];
+
+        for (qs, qe, slack) in [(6i64, 13i64, 0i64), (20, 30, 0), (20, 30, 1), (45, 46, 0)] {
+            let hits = query_single_overlap(&chrs, &starts, &ends, 1, qs, qe, slack);
+            let (_, mut expected) = overlaps(
+                &[1u32], &[qs], &[qe], &chrs, &starts, &ends, slack, "all", "idx1", false, None, None, "any", None,
+            );
+            expected.sort();
+            assert_eq!(hits, expected, "query {qs}-{qe} slack {slack}");
+        }
+        assert!(query_single_overlap(&chrs, &starts, &ends, 3, 0, 10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_overlaps_within_group_matches_same_group_only() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 50];
+        let ends = [20i64, 70];
+        let genes = [7u32, 8];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [5i64, 10, 60];
+        let ends2 = [15i64, 30, 65];
+        let genes2 = [7u32, 8, 8];
+
+        let (idx, idx2) = overlaps_within_group(
+            &chrs, &starts, &ends, &genes, &chrs2, &starts2, &ends2, &genes2, 0,
+        );
+
+        // Query 0 overlaps subjects 0 and 1, but only 0 is from gene 7.
+        assert_eq!(idx, vec![0, 1]);
+        assert_eq!(idx2, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_overlap_matrix_upper_triangle() {
+        // 0 and 1 overlap, 1 and 2 overlap, 0 and 2 do not; 3 is on another chromosome.
+        let chrs = vec![0u32, 0, 0, 1];
+        let starts = vec![0i64, 5, 12, 0];
+        let ends = vec![10i64, 15, 20, 10];
+
+        let (rows, cols) = overlap_matrix(&chrs, &starts, &ends, 0);
+
+        assert_eq!(rows, vec![0, 1]);
+        assert_eq!(cols, vec![1, 2]);
+        // No diagonal entries.
+        assert!(rows.iter().zip(&cols).all(|(r, c)| r < c));
+
+        // Mirroring the upper triangle gives back the full self-overlap matrix.
+        let full = sweep_line_overlaps(&chrs, &starts, &ends, &chrs, &starts, &ends, 0);
+        let mut expected: Vec<(u32, u32)> = full
+            .iter()
+            .filter(|p| p.idx != p.idx2)
+            .map(|p| (p.idx, p.idx2))
+            .collect();
+        expected.sort();
+        let mut mirrored: Vec<(u32, u32)> = rows
+            .iter()
+            .zip(&cols)
+            .flat_map(|(&r, &c)| [(r, c), (c, r)])
+            .collect();
+        mirrored.sort();
+        assert_eq!(mirrored, expected);
+    }
+
+    #[test]
+    fn test_self_overlaps_match_two_set_sweep() {
+        // Touching, nested, duplicated and zero-length intervals.
+        let chrs = [0u32, 0, 0, 0, 0, 0, 1];
+        let starts = [0i64, 10, 2, 2, 15, 12, 0];
+        let ends = [10i64, 20, 5, 5, 15, 30, 10];
+
+        for slack in [0i64, 3] {
+            let (rows, cols) = self_overlaps(&chrs, &starts, &ends, slack);
+
+            let mut expected: Vec<(u32, u32)> = sweep_line_overlaps(&chrs, &starts, &ends, &chrs, &starts, &ends, slack)
+                .iter()
+                .filter(|p| p.idx < p.idx2)
+                .map(|p| (p.idx, p.idx2))
+                .collect();
+            expected.sort();
+            let got: Vec<(u32, u32)> = rows.into_iter().zip(cols).collect();
+            assert_eq!(got, expected, "slack {slack}");
+        }
+    }
+
+    #[test]
+    fn test_pairs_within_reports_signed_gaps() {
+        // 0 overlaps 1 by 5, 1 touches 2, 2 and 3 are 4 apart, 4 is on another chromosome.
+        let chrs = [0u32, 0, 0, 0, 1];
+        let starts = [0i64, 5, 15, 24, 0];
+        let ends = [10i64, 15, 20, 30, 10];
+
+        let (idx, idx2, gaps) = pairs_within(&chrs, &starts, &ends, 4, None);
+        assert_eq!(idx, vec![0, 1, 2]);
+        assert_eq!(idx2, vec![1, 2, 3]);
+        assert_eq!(gaps, vec![-5, 0, 4]);
+
+        let (idx, _, gaps) = pairs_within(&chrs, &starts, &ends, 3, None);
+        assert_eq!(idx, vec![0, 1]);
+        assert_eq!(gaps, vec![-5, 0]);
+
+        let (idx, _, _) = pairs_within(&chrs, &starts, &ends, 4, Some(2));
+        assert_eq!(idx.len(), 2);
+    }
+
+    #[test]
+    fn test_overlap_count_matrix_row_major() {
+        let chrs = [0u32, 0, 1];
+        let starts = [0i64, 20, 0];
+        let ends = [10i64, 30, 10];
+        let chrs2 = [0u32, 0];
+        let starts2 = [5i64, 10];
+        let ends2 = [25i64, 20];
+
+        let matrix = overlap_count_matrix(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0);
+
+        assert_eq!(matrix, vec![1, 0, 1, 0, 0, 0]);
+        assert_eq!(
+            matrix.iter().sum::<u32>(),
+            count_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0).iter().sum::<u32>()
+        );
+    }
+
+    #[test]
+    fn test_overlaps_max_per_query() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 100];
+        let ends = [50i64, 110];
+        let chrs2 = [0u32, 0, 0, 0];
+        let starts2 = [30i64, 10, 20, 105];
+        let ends2 = [35i64, 15, 25, 106];
+
+        for sort_by in ["none", "idx1", "idx2"] {
+            let (idx, idx2) = overlaps(
+                &chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, "all", sort_by, false, Some(2), None, "any", None,
+            );
+            let mut got: Vec<(u32, u32)> = idx.into_iter().zip(idx2).collect();
+            got.sort();
+            // Query 0 overlaps set-2 rows 0, 1 and 2; the two lowest are kept,
+            // not the two that start first.
+            assert_eq!(got, vec![(0, 0), (0, 1), (1, 3)], "sort_by {sort_by}");
+        }
+    }
+
+    #[test]
+    fn test_overlaps_sort_by_idx2() {
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 5, 40];
+        let ends = [50i64, 20, 60];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [45i64, 10, 30];
+        let ends2 = [55i64, 12, 35];
+
+        let (idx, idx2) = overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, "all", "idx2", false, None, None, "any", None);
+        assert_eq!(idx2, vec![0, 0, 1, 1, 2]);
+        // Ties on the set-2 index are ordered by set-1 index.
+        assert_eq!(idx, vec![0, 2, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_overlaps_sort_by_length() {
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 5, 40];
+        let ends = [50i64, 20, 60];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [45i64, 10, 30];
+        let ends2 = [55i64, 12, 35];
+
+        let (idx, idx2) = overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, "all", "length_desc", false, None, None, "any", None);
+        // Shared bases: 10, then 5, 5, then 2, 2; ties ordered by set-1 index.
+        assert_eq!(idx, vec![2, 0, 0, 0, 1]);
+        assert_eq!(idx2[0], 0);
+        assert_eq!(idx2[3..], [1, 1]);
+
+        let (idx, _) = overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, "all", "length_asc", false, None, None, "any", None);
+        assert_eq!(idx, vec![0, 1, 0, 0, 2]);
+    }
+
+    #[test]
+    fn test_count_overlaps_both_matches_swapped_count() {
+        let chrs = [0u32, 0, 1];
+        let starts = [0i64, 20, 0];
+        let ends = [30i64, 25, 10];
+        let chrs2 = [0u32, 0, 0, 1];
+        let starts2 = [5i64, 22, 40, 50];
+        let ends2 = [10i64, 28, 45, 60];
+
+        let (counts, counts2) =
+            count_overlaps_both(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0);
+
+        assert_eq!(counts, count_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0));
+        assert_eq!(counts2, count_overlaps(&chrs2, &starts2, &ends2, &chrs, &starts, &ends, 0));
+        assert_eq!(counts2, vec![1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_overlaps_min_overlap_bp() {
+        let chrs = [0u32];
+        let starts = [0i64];
+        let ends = [100i64];
+        // Shares 10, 1 and 0 bases with the query; the last one only touches.
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [90i64, 95, 100];
+        let ends2 = [120i64, 96, 110];
+
+        let run = |min_bp, slack| {
+            overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, slack, "all", "idx1", false, None, min_bp, "any", None).1
+        };
+        assert_eq!(run(None, 0), vec![0, 1]);
+        assert_eq!(run(Some(1), 0), vec![0, 1]);
+        assert_eq!(run(Some(5), 0), vec![0]);
+        assert_eq!(run(Some(11), 0), Vec::<u32>::new());
+        // Slack brings the touching interval in, but it still shares no base.
+        assert_eq!(run(None, 1), vec![0, 1, 2]);
+        assert_eq!(run(Some(1), 1), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_min_overlap_bp_ignores_slack_widening() {
+        // [10, 20) shares 10 bases with [8, 30), 14 once widened by 2.
+        let contained = |min_bp| {
+            overlaps(&[0u32], &[10i64], &[20], &[0u32], &[8], &[30], 2, "all", "idx1", true, None, min_bp, "any", None).1
+        };
+        assert_eq!(contained(Some(10)), vec![0]);
+        assert_eq!(contained(Some(11)), Vec::<u32>::new());
+
+        // Per-row slack brings the touching interval in, but it shares no base.
+        let row_slack = |min_bp| {
+            overlaps_row_slack(&[0u32], &[0i64], &[100], &[1], &[0u32], &[100], &[110], "all", "idx1", false, None, min_bp, "any", None).1
+        };
+        assert_eq!(row_slack(None), vec![0]);
+        assert_eq!(row_slack(Some(1)), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_overlap_pairs_carry_overlap_length() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 20];
+        let ends = [10i64, 40];
+        let chrs2 = [0u32, 0];
+        let starts2 = [5i64, 25];
+        let ends2 = [30i64, 30];
+
+        let mut pairs = sweep_line_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0);
+        sort_by_key(&mut pairs, |p| (p.idx, p.idx2));
+        let got: Vec<_> = pairs.iter().map(|p| (p.idx, p.idx2, p.overlap_len)).collect();
+        assert_eq!(got, vec![(0, 0, 5), (1, 0, 10), (1, 1, 5)]);
+    }
+
+    #[test]
+    fn test_bookended_intervals_have_overlap_length_zero() {
+        // [0,10) and [10,20) touch but share no base.
+        let chrs = [0u32];
+        let pairs = sweep_line_overlaps(&chrs, &[0i64], &[10i64], &chrs, &[10i64], &[20i64], 0);
+        assert!(pairs.is_empty());
+
+        // Slack makes them a pair, but the reported overlap is still 0 bases.
+        let pairs = sweep_line_overlaps(&chrs, &[0i64], &[10i64], &chrs, &[10i64], &[20i64], 1);
+        let got: Vec<_> = pairs.iter().map(|p| (p.idx, p.idx2, p.overlap_len)).collect();
+        assert_eq!(got, vec![(0, 0, 0)]);
+    }
+
+    #[test]
+    fn test_presorted_overlap_pairs_measure_raw_coordinates() {
+        // [5,10) and [0,5) only meet through slack 1 on set 1.
+        let (starts, ends) = ([5i64], [10i64]);
+        let (starts2, ends2) = ([0i64], [5i64]);
+        let sorted_starts = build_sorted_events_single_collection_separate_outputs(&[0u32], &starts, 1);
+        let sorted_ends = build_sorted_events_single_collection_separate_outputs(&[0u32], &ends, 1);
+        let sorted_starts2 = build_sorted_events_single_collection_separate_outputs(&[0u32], &starts2, 0);
+        let sorted_ends2 = build_sorted_events_single_collection_separate_outputs(&[0u32], &ends2, 0);
+
+        let pairs = sweep_line_overlaps_overlap_pair_measured(
+            &sorted_starts, &sorted_ends, &sorted_starts2, &sorted_ends2, (&starts, &ends), (&starts2, &ends2),
+        );
+        let got: Vec<_> = pairs.iter().map(|p| (p.idx, p.idx2, p.overlap_len)).collect();
+        assert_eq!(got, vec![(0, 0, 0)]);
+
+        let swept = sweep_line_overlaps(&[0u32], &starts, &ends, &[0u32], &starts2, &ends2, 1);
+        assert_eq!(swept[0].overlap_len, 0);
+    }
+
+    #[test]
+    fn test_zero_length_intervals_overlap_half_open() {
+        // Points at 0, 10 and 15 against [0,10) and [10,20).
+        let chrs = [0u32, 0, 0];
+        let starts = [0i64, 10, 15];
+        let ends = [0i64, 10, 15];
+        let chrs2 = [0u32, 0];
+        let starts2 = [0i64, 10];
+        let ends2 = [10i64, 20];
+
+        let (idx, idx2) = point_overlaps(&chrs, &starts, &chrs2, &starts2, &ends2);
+        assert_eq!(idx, vec![0, 1, 2]);
+        assert_eq!(idx2, vec![0, 1, 1]);
+
+        let (counts, counts2) =
+            count_overlaps_both(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0);
+        assert_eq!(counts, vec![1, 1, 1]);
+        assert_eq!(counts2, vec![1, 2]);
+
+        // With the sets swapped the points are still found, and two points at
+        // the same position never overlap each other.
+        let pairs = sweep_line_overlaps(&chrs2, &starts2, &ends2, &chrs, &starts, &ends, 0);
+        assert_eq!(pairs.len(), 3);
+        assert!(sweep_line_overlaps(&chrs, &starts, &ends, &chrs, &starts, &ends, 0).is_empty());
+    }
+
+    #[test]
+    fn test_row_slack_matches_scalar_slack_per_row() {
+        let chrs = [0u32, 0];
+        let starts = [10i64, 50];
+        let ends = [20i64, 60];
+        let chrs2 = [0u32, 0];
+        let starts2 = [23i64, 65];
+        let ends2 = [30i64, 70];
+
+        // Only the second interval gets enough slack to reach its neighbour.
+        let counts = count_overlaps_row_slack(&chrs, &starts, &ends, &[2, 6], &chrs2, &starts2, &ends2);
+        assert_eq!(counts, vec![0, 1]);
+
+        let (idx, idx2) =
+            overlaps_row_slack(&chrs, &starts, &ends, &[4, 6], &chrs2, &starts2, &ends2, "all", "idx1", false, None, None, "any", None);
+        assert_eq!(idx, vec![0, 1]);
+        assert_eq!(idx2, vec![0, 1]);
+
+        // A uniform array behaves like the scalar.
+        assert_eq!(
+            count_overlaps_row_slack(&chrs, &starts, &ends, &[5, 5], &chrs2, &starts2, &ends2),
+            count_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 5),
+        );
+    }
+
+    #[test]
+    fn test_intersection_size_counts_set2_once() {
+        // [0,10) against two overlapping set-2 intervals covering [2,8):
+        // 6 bp, not 4 + 4. [20,30) shares 5 bp with [25,40), [25,35) shares 10.
+        let chrs = [0u32, 0, 0, 1];
+        let starts = [0i64, 20, 25, 0];
+        let ends = [10i64, 30, 35, 5];
+        let chrs2 = [0u32, 0, 0, 2];
+        let starts2 = [2i64, 4, 25, 0];
+        let ends2 = [6i64, 8, 40, 5];
+
+        assert_eq!(intersection_size(&chrs, &starts, &ends, &chrs2, &starts2, &ends2), 6 + 5 + 10);
+        assert_eq!(intersection_size(&chrs, &starts, &ends, &[], &[], &[]), 0);
+    }
+
+    #[test]
+    fn test_strand_mode_filters_overlaps_and_counts() {
+        let chrs = [0u32, 0];
+        let starts = [0i64, 0];
+        let ends = [10i64, 10];
+        let strands = [false, true];
+        let chrs2 = [0u32, 0];
+        let starts2 = [5i64, 8];
+        let ends2 = [15i64, 20];
+        let strands2 = [false, false];
+        let both = Some((&strands[..], &strands2[..]));
+
+        let run = |mode, overlap_type| {
+            overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, overlap_type, "idx1", false, None, None, mode, both)
+        };
+        assert_eq!(run("any", "all"), (vec![0, 0, 1, 1], vec![0, 1, 0, 1]));
+        assert_eq!(run("same", "all"), (vec![0, 0], vec![0, 1]));
+        assert_eq!(run("opposite", "all"), (vec![1, 1], vec![0, 1]));
+        // Strand filtering happens before the first/last pick.
+        assert_eq!(run("same", "last"), (vec![0], vec![1]));
+
+        let count = |mode| {
+            count_overlaps_stranded(&chrs, &starts, &ends, &strands, &chrs2, &starts2, &ends2, &strands2, 0, mode)
+        };
+        assert_eq!(count("any"), vec![2, 2]);
+        assert_eq!(count("same"), vec![2, 0]);
+        assert_eq!(count("opposite"), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_overlap_chunks_concatenate_to_full_sweep() {
+        let chrs = [0u32, 0, 0, 1, 1];
+        let starts = [0i64, 5, 20, 0, 7];
+        let ends = [10i64, 25, 30, 8, 7];
+        let chrs2 = [0u32, 0, 0, 1, 1];
+        let starts2 = [2i64, 8, 22, 1, 0];
+        let ends2 = [6i64, 21, 40, 9, 10];
+
+        let key = |p: &OverlapPair<i64>| (p.idx, p.idx2, p.overlap_len);
+        let mut full: Vec<_> = sweep_line_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0)
+            .iter()
+            .map(key)
+            .collect();
+        full.sort();
+
+        for chunk_size in [1, 2, 3, 100] {
+            let chunks: Vec<_> = OverlapChunks::new(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0, chunk_size)
+                .collect();
+            assert!(chunks.iter().all(|c| !c.is_empty()));
+            let mut flat: Vec<_> = chunks.iter().flatten().map(key).collect();
+            flat.sort();
+            assert_eq!(flat, full);
+        }
+    }
+
+    #[test]
+    fn test_any_overlap_matches_nonzero_counts() {
+        let chrs = [0u32, 0, 0, 0, 1, 1];
+        let starts = [0i64, 5, 30, 12, 0, 50];
+        let ends = [10i64, 25, 40, 12, 8, 60];
+        let chrs2 = [0u32, 0, 0, 1, 1];
+        let starts2 = [2i64, 8, 11, 8, 55];
+        let ends2 = [6i64, 21, 11, 9, 55];
+
+        for slack in [0, 1, 5] {
+            let counts = count_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, slack);
+            let any = any_overlap(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, slack);
+            assert_eq!(any, counts.iter().map(|&c| c > 0).collect::<Vec<_>>(), "slack {slack}");
+        }
+    }
+
+    #[test]
+    fn test_annotate_overlaps_uses_genomic_order() {
+        // Input order of set 2 is the reverse of its genomic order.
+        let chrs = [0u32, 0];
+        let starts = [0i64, 100];
+        let ends = [50i64, 110];
+        let chrs2 = [0u32, 0, 0];
+        let starts2 = [30i64, 10, 0];
+        let ends2 = [45i64, 40, 5];
+        let labels2 = [7u32, 8, 9];
+
+        let run = |how| annotate_overlaps(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, &labels2, 0, how);
+        assert_eq!(run("first"), vec![Some(9), None]);
+        assert_eq!(run("last"), vec![Some(7), None]);
+        assert_eq!(run("largest"), vec![Some(8), None]);
+    }
+}
@@ -1,17 +1,183 @@
-use num_traits::{PrimInt, Signed, ToPrimitive, Zero};
+use num_traits::{Bounded, PrimInt, Signed, ToPrimitive, Zero};
 use numpy::Element; // You'll need the num-traits crate
 use std::{hash::Hash, str::FromStr};
 
-pub trait PositionType: PrimInt + Signed + Hash + Copy + radsort::Key + Element + Copy + PartialOrd + ToPrimitive + Zero + std::fmt::Display + std::fmt::Debug {}
-impl<T> PositionType for T where T: PrimInt + Signed + Hash + Copy + radsort::Key + Element + Copy + PartialOrd + ToPrimitive + Zero + std::fmt::Display + std::fmt::Debug {}
-pub trait GroupType: PrimInt + Hash + Copy + radsort::Key + Zero + std::fmt::Debug {}
-impl<T> GroupType for T where T: PrimInt + Hash + Copy + radsort::Key + Zero + std::fmt::Debug {}
+/// Coordinates accepted by the sweeps that only compare, add and subtract
+/// positions (overlaps, merge and cluster). Unlike [`PositionType`] this
+/// includes `f32` and `f64`, for non-genomic coordinate systems.
+pub trait CoordinateType: Signed + Bounded + Copy + Send + radsort::Key + Element + PartialOrd + ToPrimitive + Zero + std::fmt::Display + std::fmt::Debug {}
+impl<T> CoordinateType for T where T: Signed + Bounded + Copy + Send + radsort::Key + Element + PartialOrd + ToPrimitive + Zero + std::fmt::Display + std::fmt::Debug {}
+pub trait PositionType: CoordinateType + PrimInt + Hash {}
+impl<T> PositionType for T where T: CoordinateType + PrimInt + Hash {}
+pub trait GroupType: PrimInt + Hash + Copy + Send + radsort::Key + Zero + std::fmt::Debug {}
+impl<T> GroupType for T where T: PrimInt + Hash + Copy + Send + radsort::Key + Zero + std::fmt::Debug {}
 
+/// Intervals plus the optional per-row columns that travel with them.
+///
+/// Every `Some` column has one entry per interval. The columns are owned
+/// `Vec`s; there is no Arrow conversion, since the `arrow` crate is not a
+/// dependency.
 pub struct GenomicData<C: GroupType, P: PositionType> {
     pub chroms: Vec<C>,
     pub starts: Vec<P>,
     pub ends: Vec<P>,
     pub strands: Option<Vec<bool>>,
+    pub groups: Option<Vec<u32>>,
+    pub names: Option<Vec<String>>,
+    pub scores: Option<Vec<f64>>,
+    /// Free-form attribute strings, e.g. the GTF attribute column.
+    pub attributes: Option<Vec<String>>,
+}
+
+impl<C: GroupType, P: PositionType> GenomicData<C, P> {
+    pub fn new(chroms: Vec<C>, starts: Vec<P>, ends: Vec<P>) -> Self {
+        GenomicData {
+            chroms,
+            starts,
+            ends,
+            strands: None,
+            groups: None,
+            names: None,
+            scores: None,
+            attributes: None,
+        }
+    }
+
+    pub fn with_names(mut self, names: Vec<String>) -> Self {
+        assert_eq!(names.len(), self.starts.len());
+        self.names = Some(names);
+        self
+    }
+
+    pub fn with_scores(mut self, scores: Vec<f64>) -> Self {
+        assert_eq!(scores.len(), self.starts.len());
+        self.scores = Some(scores);
+        self
+    }
+
+    pub fn with_attributes(mut self, attributes: Vec<String>) -> Self {
+        assert_eq!(attributes.len(), self.starts.len());
+        self.attributes = Some(attributes);
+        self
+    }
+
+    /// Borrowed view of all rows.
+    pub fn view(&self) -> GenomicDataView<'_, C, P> {
+        GenomicDataView {
+            chroms: &self.chroms,
+            starts: &self.starts,
+            ends: &self.ends,
+            strands: self.strands.as_deref(),
+        }
+    }
+
+    /// Yields one view per run of equal chromosomes, in order. Rows are
+    /// expected to be sorted (or at least grouped) by chromosome; otherwise a
+    /// chromosome shows up once per run.
+    pub fn iter_chromosomes(&self) -> ChromosomeChunks<'_, C, P> {
+        ChromosomeChunks { rest: self.view() }
+    }
+}
+
+/// One interval of a [`GenomicData`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenomicInterval<C: GroupType, P: PositionType> {
+    pub chr: C,
+    pub start: P,
+    pub end: P,
+    pub strand: Option<bool>,
+}
+
+/// Owning iterator over the rows of a [`GenomicData`].
+pub struct GenomicDataIntoIter<C: GroupType, P: PositionType> {
+    chroms: std::vec::IntoIter<C>,
+    starts: std::vec::IntoIter<P>,
+    ends: std::vec::IntoIter<P>,
+    strands: Option<std::vec::IntoIter<bool>>,
+}
+
+impl<C: GroupType, P: PositionType> Iterator for GenomicDataIntoIter<C, P> {
+    type Item = GenomicInterval<C, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(GenomicInterval {
+            chr: self.chroms.next()?,
+            start: self.starts.next()?,
+            end: self.ends.next()?,
+            strand: self.strands.as_mut().and_then(|s| s.next()),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chroms.size_hint()
+    }
+}
+
+impl<C: GroupType, P: PositionType> IntoIterator for GenomicData<C, P> {
+    type Item = GenomicInterval<C, P>;
+    type IntoIter = GenomicDataIntoIter<C, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GenomicDataIntoIter {
+            chroms: self.chroms.into_iter(),
+            starts: self.starts.into_iter(),
+            ends: self.ends.into_iter(),
+            strands: self.strands.map(|s| s.into_iter()),
+        }
+    }
+}
+
+/// Borrowed slices of a contiguous block of [`GenomicData`] rows.
+#[derive(Debug, Clone, Copy)]
+pub struct GenomicDataView<'a, C: GroupType, P: PositionType> {
+    pub chroms: &'a [C],
+    pub starts: &'a [P],
+    pub ends: &'a [P],
+    pub strands: Option<&'a [bool]>,
+}
+
+impl<'a, C: GroupType, P: PositionType> GenomicDataView<'a, C, P> {
+    pub fn len(&self) -> usize {
+        self.chroms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chroms.is_empty()
+    }
+
+    fn split_at(&self, mid: usize) -> (Self, Self) {
+        let (chroms, chroms_rest) = self.chroms.split_at(mid);
+        let (starts, starts_rest) = self.starts.split_at(mid);
+        let (ends, ends_rest) = self.ends.split_at(mid);
+        let (strands, strands_rest) = match self.strands {
+            Some(s) => {
+                let (a, b) = s.split_at(mid);
+                (Some(a), Some(b))
+            }
+            None => (None, None),
+        };
+        (
+            GenomicDataView { chroms, starts, ends, strands },
+            GenomicDataView { chroms: chroms_rest, starts: starts_rest, ends: ends_rest, strands: strands_rest },
+        )
+    }
+}
+
+/// Iterator returned by [`GenomicData::iter_chromosomes`].
+pub struct ChromosomeChunks<'a, C: GroupType, P: PositionType> {
+    rest: GenomicDataView<'a, C, P>,
+}
+
+impl<'a, C: GroupType, P: PositionType> Iterator for ChromosomeChunks<'a, C, P> {
+    type Item = GenomicDataView<'a, C, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = *self.rest.chroms.first()?;
+        let run = self.rest.chroms.iter().take_while(|&&c| c == first).count();
+        let (chunk, rest) = self.rest.split_at(run);
+        self.rest = rest;
+        Some(chunk)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -51,7 +217,7 @@ pub struct EventUsize {
 /// - `set_id`: which set does this interval belong to? (1 or 2)
 /// - `idx`: the interval's ID/index
 #[derive(Debug, Clone, Hash)]
-pub struct Event<C: GroupType, T: PositionType> {
+pub struct Event<C: GroupType, T: CoordinateType> {
     pub chr: C,
     pub pos: T,
     pub is_start: bool,
@@ -60,7 +226,7 @@ pub struct Event<C: GroupType, T: PositionType> {
 }
 
 #[derive(Debug, Clone, Hash)]
-pub struct MaxEvent<C: GroupType, T: PositionType> {
+pub struct MaxEvent<C: GroupType, T: CoordinateType> {
     pub chr: C,
     pub pos: T,
     pub start: T,
@@ -71,7 +237,7 @@ pub struct MaxEvent<C: GroupType, T: PositionType> {
 }
 
 #[derive(Debug, Clone, Hash)]
-pub struct MinEvent<C: GroupType, T: PositionType> {
+pub struct MinEvent<C: GroupType, T: CoordinateType> {
     pub chr: C,
     pub pos: T,
     pub idx: u32,
@@ -83,10 +249,15 @@ pub struct GroupStruct<C: GroupType> {
     pub idx: u32,
 }
 
+/// A set-1/set-2 overlap hit. `overlap_len` is
+/// `min(end, end2) - max(start, start2)` on the intervals as given, before
+/// any slack; pairs that only came within slack of each other have a length
+/// of zero or less.
 #[derive(Debug, Clone, Hash)]
-pub struct OverlapPair {
+pub struct OverlapPair<T: CoordinateType> {
     pub idx: u32,
     pub idx2: u32,
+    pub overlap_len: T,
 }
 
 #[derive(Debug, Clone, Hash, Copy)]
@@ -128,7 +299,7 @@ pub struct SubsequenceInterval {
     pub forward_strand: bool, // true => + strand, false => - strand
 }
 
-pub struct GenericEvent<C: GroupType, T: PositionType> {
+pub struct GenericEvent<C: GroupType, T: CoordinateType> {
     pub chr: C,
     pub pos: T,
     pub is_start: bool,
@@ -151,11 +322,121 @@ impl FromStr for OverlapType {
             "all" => Ok(OverlapType::All),
             "first" => Ok(OverlapType::First),
             "last" => Ok(OverlapType::Last),
-            _ => Err("Invalid direction string"),
+            // Containment is its own flag (or `contained_overlaps`), not a
+            // way of picking among the hits.
+            _ => Err("invalid overlap type: expected one of first/all/last"),
         }
     }
 }
 
+/// Which side of a containment query must lie inside the other: set 1 in
+/// set 2 (`"a_in_b"`), set 2 in set 1 (`"b_in_a"`) or either way
+/// (`"either"`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ContainmentType {
+    AInB,
+    BInA,
+    Either,
+}
+
+impl FromStr for ContainmentType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "a_in_b" => Ok(ContainmentType::AInB),
+            "b_in_a" => Ok(ContainmentType::BInA),
+            "either" => Ok(ContainmentType::Either),
+            _ => Err("Invalid containment string"),
+        }
+    }
+}
+
+/// Final order of overlap pairs: by set-1 index (`"idx1"`, or its alias
+/// `"idx"`), by set-2 index (`"idx2"`, ties by set-1 index), by overlap length
+/// longest first (`"length_desc"`) or shortest first (`"length_asc"`), ties by
+/// set-1 index, or the order the sweep found them in (`"none"`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OverlapSortBy {
+    Idx1,
+    Idx2,
+    LengthDesc,
+    LengthAsc,
+    None,
+}
+
+impl FromStr for OverlapSortBy {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "idx" | "idx1" => Ok(OverlapSortBy::Idx1),
+            "idx2" => Ok(OverlapSortBy::Idx2),
+            "length_desc" => Ok(OverlapSortBy::LengthDesc),
+            "length_asc" => Ok(OverlapSortBy::LengthAsc),
+            "none" => Ok(OverlapSortBy::None),
+            _ => Err("Invalid sort_by string"),
+        }
+    }
+}
+
+/// Which overlapping annotation labels a query in `annotate_overlaps`: the
+/// first in genomic order (`"first"`), the last (`"last"`) or the one
+/// sharing the most bases (`"largest"`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AnnotateType {
+    First,
+    Last,
+    Largest,
+}
+
+impl FromStr for AnnotateType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(AnnotateType::First),
+            "last" => Ok(AnnotateType::Last),
+            "largest" => Ok(AnnotateType::Largest),
+            _ => Err("invalid annotate type: expected one of first/last/largest"),
+        }
+    }
+}
+
+/// Which strand combinations count as overlapping: any (`"any"`), only
+/// pairs on the same strand (`"same"`) or only pairs on opposite strands
+/// (`"opposite"`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StrandMode {
+    Any,
+    Same,
+    Opposite,
+}
+
+impl StrandMode {
+    /// Whether a pair with strands `strand` and `strand2` is kept.
+    #[inline]
+    pub fn keeps(self, strand: bool, strand2: bool) -> bool {
+        match self {
+            StrandMode::Any => true,
+            StrandMode::Same => strand == strand2,
+            StrandMode::Opposite => strand != strand2,
+        }
+    }
+}
+
+impl FromStr for StrandMode {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "any" => Ok(StrandMode::Any),
+            "same" | "same_strand" => Ok(StrandMode::Same),
+            "opposite" | "opposite_strand" => Ok(StrandMode::Opposite),
+            _ => Err("invalid strand mode: expected one of any/same/opposite"),
+        }
+    }
+}
 
 pub struct SplicedRecord<T> {
     pub idx: u32,
@@ -163,3 +444,32 @@ pub struct SplicedRecord<T> {
     pub end: T,
     pub strand: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genomic_data_iterators() {
+        let mut data = GenomicData::new(vec![0u32, 0, 2], vec![1i64, 5, 0], vec![4i64, 9, 3]);
+        data.strands = Some(vec![true, false, true]);
+
+        let chunks: Vec<_> = data.iter_chromosomes().map(|c| (c.chroms[0], c.starts.to_vec(), c.strands)).collect();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], (0, vec![1, 5], Some(&[true, false][..])));
+        assert_eq!(chunks[1], (2, vec![0], Some(&[true][..])));
+
+        let rows: Vec<_> = data.into_iter().collect();
+        assert_eq!(rows[1], GenomicInterval { chr: 0, start: 5, end: 9, strand: Some(false) });
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[test]
+    fn test_overlap_type_error_lists_valid_values() {
+        assert_eq!(OverlapType::from_str("LAST"), Ok(OverlapType::Last));
+        assert_eq!(
+            OverlapType::from_str("contained"),
+            Err("invalid overlap type: expected one of first/all/last")
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use rustc_hash::FxHashMap;
+
+use crate::ruranges_structs::{GenomicData, GroupType, PositionType};
+
+/// SplitMix64: small, fast and good enough for sampling test data. The same
+/// seed always gives the same stream, on every platform.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, n)`; `n` must be positive.
+    fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// Uniform in `[lo, hi]`.
+    fn between(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.below(hi - lo + 1)
+    }
+}
+
+fn to_u64<T: PositionType>(x: T) -> u64 {
+    x.to_u64().expect("coordinates must be non-negative")
+}
+
+fn from_u64<T: PositionType>(x: u64) -> T {
+    T::from(x).expect("coordinate out of range for the position type")
+}
+
+/// `n` random intervals on the chromosomes `chrs`, each between `min_len`
+/// and `max_len` bases long (inclusive) and lying within `[0, len]` of its
+/// chromosome.
+///
+/// Chromosomes are picked with probability proportional to their length, so
+/// positions are uniform over the genome; chromosomes shorter than
+/// `min_len` are never picked. Intervals come out in the order drawn.
+pub fn sample_random_intervals<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    chrom_lens: &FxHashMap<G, T>,
+    n: usize,
+    min_len: T,
+    max_len: T,
+    seed: u64,
+) -> Result<GenomicData<G, T>, String> {
+    if min_len < T::zero() || min_len > max_len {
+        return Err(format!("need 0 <= min_len <= max_len, got {} and {}", min_len, max_len));
+    }
+    let (min_len, max_len) = (to_u64(min_len), to_u64(max_len));
+
+    let mut candidates = Vec::with_capacity(chrs.len());
+    let mut cumulative = Vec::with_capacity(chrs.len());
+    let mut total = 0_u64;
+    for &chr in chrs {
+        let len = *chrom_lens
+            .get(&chr)
+            .ok_or_else(|| format!("No chromosome length given for group {:?}", chr))?;
+        if len < T::zero() {
+            return Err(format!("chromosome length for group {:?} is negative: {}", chr, len));
+        }
+        let len = to_u64(len);
+        if len >= min_len && len > 0 {
+            total += len;
+            candidates.push((chr, len));
+            cumulative.push(total);
+        }
+    }
+    if n > 0 && candidates.is_empty() {
+        return Err(format!("no chromosome is at least min_len ({}) long", min_len));
+    }
+
+    let mut rng = SplitMix64(seed);
+    let mut out_chrs = Vec::with_capacity(n);
+    let mut out_starts = Vec::with_capacity(n);
+    let mut out_ends = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        let pick = rng.below(total);
+        let (chr, chrom_len) = candidates[cumulative.partition_point(|&c| c <= pick)];
+        let len = rng.between(min_len, max_len.min(chrom_len));
+        let start = rng.between(0, chrom_len - len);
+
+        out_chrs.push(chr);
+        out_starts.push(from_u64(start));
+        out_ends.push(from_u64(start + len));
+    }
+
+    Ok(GenomicData::new(out_chrs, out_starts, out_ends))
+}
+
+/// Moves every interval to a random position on its own chromosome, keeping
+/// its length, e.g. to build a null model for permutation tests.
+///
+/// New starts are uniform over the positions that keep the interval within
+/// `[0, len]` of its chromosome. Returns `(new_starts, new_ends)` in input
+/// order.
+pub fn permute_starts<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    chrom_lens: &FxHashMap<G, T>,
+    seed: u64,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    if chrs.len() != starts.len() || starts.len() != ends.len() {
+        return Err(format!(
+            "chrs, starts and ends must have the same length, got {}, {} and {}",
+            chrs.len(),
+            starts.len(),
+            ends.len()
+        ));
+    }
+
+    let mut rng = SplitMix64(seed);
+    let mut new_starts = Vec::with_capacity(starts.len());
+    let mut new_ends = Vec::with_capacity(starts.len());
+
+    for i in 0..chrs.len() {
+        let chrom_len = *chrom_lens
+            .get(&chrs[i])
+            .ok_or_else(|| format!("No chromosome length given for group {:?}", chrs[i]))?;
+        let len = ends[i] - starts[i];
+        if len < T::zero() || len > chrom_len {
+            return Err(format!(
+                "interval {} (length {}) does not fit on its chromosome (length {})",
+                i, len, chrom_len
+            ));
+        }
+
+        let start = rng.between(0, to_u64(chrom_len - len));
+        new_starts.push(from_u64(start));
+        new_ends.push(from_u64::<T>(start) + len);
+    }
+
+    Ok((new_starts, new_ends))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_and_permute_stay_within_chromosomes() {
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 100i64);
+        lens.insert(1u32, 15i64);
+        lens.insert(2u32, 5i64);
+
+        let data = sample_random_intervals(&[0, 1, 2], &lens, 500, 10, 20, 42).unwrap();
+        assert_eq!(data.chroms.len(), 500);
+        for ((&c, &s), &e) in data.chroms.iter().zip(&data.starts).zip(&data.ends) {
+            // Chromosome 2 is shorter than min_len.
+            assert_ne!(c, 2);
+            assert!(s >= 0 && e <= lens[&c]);
+            assert!((10..=20).contains(&(e - s)));
+        }
+        // Same seed, same intervals.
+        let again = sample_random_intervals(&[0, 1, 2], &lens, 500, 10, 20, 42).unwrap();
+        assert_eq!(again.starts, data.starts);
+
+        let (s, e) = permute_starts(&data.chroms, &data.starts, &data.ends, &lens, 7).unwrap();
+        for i in 0..s.len() {
+            assert_eq!(e[i] - s[i], data.ends[i] - data.starts[i]);
+            assert!(s[i] >= 0 && e[i] <= lens[&data.chroms[i]]);
+        }
+
+        assert!(sample_random_intervals(&[2u32], &lens, 1, 10, 20, 0).is_err());
+        assert!(permute_starts(&[2u32], &[0i64], &[10i64], &lens, 0).is_err());
+    }
+
+    #[test]
+    fn test_sample_and_permute_reject_bad_input() {
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, -100i64);
+        assert!(sample_random_intervals(&[0u32], &lens, 1, 10, 20, 0).is_err());
+        assert!(permute_starts(&[0u32], &[0i64], &[10i64], &lens, 0).is_err());
+
+        lens.insert(0u32, 100i64);
+        assert!(permute_starts(&[0u32, 0], &[0i64], &[10i64], &lens, 0).is_err());
+        assert!(permute_starts(&[0u32], &[0i64], &[10i64, 20], &lens, 0).is_err());
+    }
+}
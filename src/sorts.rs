@@ -1,5 +1,11 @@
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
 use radsort::sort_by_key;
+use rustc_hash::FxHashMap;
 
+use crate::helpers::saturating_add_slack;
+use crate::ruranges_structs::CoordinateType;
 use crate::ruranges_structs::Event;
 use crate::ruranges_structs::GenericEvent;
 use crate::ruranges_structs::GroupStruct;
@@ -173,6 +179,84 @@ pub fn sort_order_idx<G: GroupType, T: PositionType>(
         .collect()
 }
 
+/// Tie-break applied after the start coordinate in [`sort_order_idx_by`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SecondarySortKey {
+    End,
+    Length,
+}
+
+impl FromStr for SecondarySortKey {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "end" => Ok(SecondarySortKey::End),
+            "length" => Ok(SecondarySortKey::Length),
+            _ => Err("Invalid secondary sort key"),
+        }
+    }
+}
+
+/// Like [`sort_order_idx`], but with the order within each group chosen by
+/// the caller: by start, then by `secondary` (`"end"` or `"length"`),
+/// ascending or `descending`. Groups themselves stay in ascending order.
+///
+/// Rows flagged in `sort_reverse_direction` are flipped relative to the
+/// requested direction, so with `descending` they come out ascending.
+pub fn sort_order_idx_by<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    sort_reverse_direction: Option<&[bool]>,
+    descending: bool,
+    secondary: &str,
+) -> Vec<u32> {
+    let secondary = SecondarySortKey::from_str(secondary).expect("invalid secondary sort key");
+
+    let mut intervals = Vec::with_capacity(chrs.len());
+    for i in 0..chrs.len() {
+        let flip = descending ^ sort_reverse_direction.is_some_and(|r| r[i]);
+        let second = match secondary {
+            SecondarySortKey::End => ends[i],
+            SecondarySortKey::Length => ends[i] - starts[i],
+        };
+        let (start, end) = if flip { (-starts[i], -second) } else { (starts[i], second) };
+        intervals.push(Interval {
+            group: chrs[i],
+            start,
+            end,
+            idx: i as u32,
+        });
+    }
+
+    sort_by_key(&mut intervals, |i| i.end);
+    sort_by_key(&mut intervals, |i| i.start);
+    sort_by_key(&mut intervals, |i| i.group);
+
+    intervals.iter().map(|i| i.idx).collect()
+}
+
+/// Sorts the intervals like [`sort_order_idx_by`] and returns the permuted
+/// coordinates together with the permutation, so callers don't have to
+/// gather the arrays themselves.
+pub fn sort_intervals_apply<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    sort_reverse_direction: Option<&[bool]>,
+    descending: bool,
+    secondary: &str,
+) -> (Vec<G>, Vec<T>, Vec<T>, Vec<u32>) {
+    let order = sort_order_idx_by(chrs, starts, ends, sort_reverse_direction, descending, secondary);
+
+    let sorted_chrs = order.iter().map(|&i| chrs[i as usize]).collect();
+    let sorted_starts = order.iter().map(|&i| starts[i as usize]).collect();
+    let sorted_ends = order.iter().map(|&i| ends[i as usize]).collect();
+
+    (sorted_chrs, sorted_starts, sorted_ends, order)
+}
+
 pub fn build_sorted_events_single_position<C: GroupType, T: PositionType>(
     chrs: &[C],
     pos: &[T],
@@ -204,7 +288,90 @@ pub fn build_sorted_events_single_position<C: GroupType, T: PositionType>(
     events
 }
 
-pub fn build_sorted_events_single_collection<C: GroupType, T: PositionType>(
+pub fn build_sorted_events_single_collection<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+) -> Vec<Event<C, T>> {
+    let mut events = build_events_single_collection(chrs, starts, ends, slack);
+
+    // Sort events by:
+    // 1. pos (ascending)
+    // 2. is_start before is_end (if pos ties)
+    // (We don't strictly need to tie-break by set_id or idx, but we can.)
+
+    sort_by_key(&mut events, |e| e.is_start);
+    sort_by_key(&mut events, |e| e.pos);
+    sort_by_key(&mut events, |e| e.chr);
+
+    events
+}
+
+/// Like [`build_sorted_events_single_collection`], but at equal positions
+/// start events come before end events, so an interval starting exactly
+/// where another one's (slack-extended) end lies is still seen as active.
+pub fn build_sorted_events_single_collection_starts_first<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+) -> Vec<Event<C, T>> {
+    let mut events = build_events_single_collection(chrs, starts, ends, slack);
+
+    sort_by_key(&mut events, |e| !e.is_start);
+    sort_by_key(&mut events, |e| e.pos);
+    sort_by_key(&mut events, |e| e.chr);
+
+    events
+}
+
+/// Whether the merge gap rule joins two intervals whose gap is exactly
+/// `slack`: always when `slack` is positive, and for touching intervals at
+/// `slack == 0` only with `merge_touching`.
+pub fn joins_gap_of_slack<T: CoordinateType>(slack: T, merge_touching: bool) -> bool {
+    merge_touching || !slack.is_zero()
+}
+
+/// Events for the merge gap rule (see [`joins_gap_of_slack`]): starts sort
+/// before ends at equal positions when a gap of exactly `slack` joins, and
+/// after them otherwise.
+pub fn build_sorted_events_gap_rule<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    merge_touching: bool,
+) -> Vec<Event<C, T>> {
+    if joins_gap_of_slack(slack, merge_touching) {
+        build_sorted_events_single_collection_starts_first(chrs, starts, ends, slack)
+    } else {
+        build_sorted_events_single_collection(chrs, starts, ends, slack)
+    }
+}
+
+/// Like [`build_sorted_events_single_collection`] (ends before starts at
+/// equal positions), with the start events of zero-length intervals placed
+/// after all other starts at the same position, as in
+/// [`build_sorted_events_points_last`].
+pub fn build_sorted_events_single_collection_points_last<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+) -> Vec<Event<C, T>> {
+    let mut events = build_events_single_collection(chrs, starts, ends, slack);
+
+    let is_point = |i: u32| slack.is_zero() && starts[i as usize] == ends[i as usize];
+    sort_by_key(&mut events, |e| is_point(e.idx));
+    sort_by_key(&mut events, |e| e.is_start);
+    sort_by_key(&mut events, |e| e.pos);
+    sort_by_key(&mut events, |e| e.chr);
+
+    events
+}
+
+fn build_events_single_collection<C: GroupType, T: CoordinateType>(
     chrs: &[C],
     starts: &[T],
     ends: &[T],
@@ -230,15 +397,6 @@ pub fn build_sorted_events_single_collection<C: GroupType, T: PositionType>(
         });
     }
 
-    // Sort events by:
-    // 1. pos (ascending)
-    // 2. is_start before is_end (if pos ties)
-    // (We don't strictly need to tie-break by set_id or idx, but we can.)
-
-    sort_by_key(&mut events, |e| e.is_start);
-    sort_by_key(&mut events, |e| e.pos);
-    sort_by_key(&mut events, |e| e.chr);
-
     events
 }
 
@@ -299,6 +457,11 @@ pub fn build_sorted_events_with_starts_ends<C: GroupType, T: PositionType>(
     out_pos
 }
 
+/// Inputs with at least this many intervals across both sets are sorted
+/// per chromosome on several threads; below it the threads cost more than
+/// they save.
+const PER_CHROMOSOME_SORT_MIN_INTERVALS: usize = 1 << 20;
+
 pub fn build_sorted_events<C: GroupType, T: PositionType>(
     chrs: &[C],
     starts: &[T],
@@ -307,6 +470,133 @@ pub fn build_sorted_events<C: GroupType, T: PositionType>(
     starts2: &[T],
     ends2: &[T],
     slack: T,
+) -> Vec<GenericEvent<C, T>> {
+    if chrs.len() + chrs2.len() >= PER_CHROMOSOME_SORT_MIN_INTERVALS {
+        return build_sorted_events_per_chromosome(chrs, starts, ends, chrs2, starts2, ends2, slack);
+    }
+
+    let mut events = build_generic_events(chrs, starts, ends, chrs2, starts2, ends2, slack);
+
+    sort_by_key(&mut events, |e| e.is_start);
+    sort_by_key(&mut events, |e| e.pos);
+    sort_by_key(&mut events, |e| e.chr);
+
+    events
+}
+
+/// Produces the same ordering as [`build_sorted_events`], but first buckets
+/// the events by chromosome in a single pass and then radix-sorts every
+/// bucket on its own thread. The buckets are concatenated in chromosome
+/// order, so the final `chr` pass over the whole vector is not needed.
+///
+/// Within a chromosome the sort is still stable on `(pos, is_start)`: end
+/// events come before start events at equal positions. [`build_sorted_events`]
+/// switches to it for large inputs.
+pub fn build_sorted_events_per_chromosome<C: GroupType, T: PositionType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> Vec<GenericEvent<C, T>> {
+    let events = build_generic_events(chrs, starts, ends, chrs2, starts2, ends2, slack);
+    let n_events = events.len();
+
+    let mut buckets: FxHashMap<C, Vec<GenericEvent<C, T>>> = FxHashMap::default();
+    for e in events {
+        buckets.entry(e.chr).or_default().push(e);
+    }
+
+    let mut keys: Vec<C> = buckets.keys().copied().collect();
+    radsort::sort(&mut keys);
+    let mut partitions: Vec<Vec<GenericEvent<C, T>>> = keys
+        .iter()
+        .map(|k| buckets.remove(k).unwrap())
+        .collect();
+
+    if !partitions.is_empty() {
+        let n_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(partitions.len());
+        let chunk_size = partitions.len().div_ceil(n_threads);
+
+        std::thread::scope(|scope| {
+            for chunk in partitions.chunks_mut(chunk_size) {
+                scope.spawn(move || {
+                    for partition in chunk {
+                        sort_by_key(partition, |e| e.is_start);
+                        sort_by_key(partition, |e| e.pos);
+                    }
+                });
+            }
+        });
+    }
+
+    let mut sorted = Vec::with_capacity(n_events);
+    for partition in partitions {
+        sorted.extend(partition);
+    }
+
+    sorted
+}
+
+/// Same ordering as [`build_sorted_events`], except that at equal
+/// `(chr, pos, is_start)` the start events of zero-length intervals come
+/// after those of regular intervals. A point at `p` therefore sees every
+/// interval that starts at `p` as active, while intervals ending at `p`
+/// are already gone (half-open coordinates).
+///
+/// A set-1 interval only counts as a point when `slack` is zero, since
+/// slack widens it.
+pub fn build_sorted_events_points_last<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> Vec<GenericEvent<C, T>> {
+    let mut events = build_generic_events(chrs, starts, ends, chrs2, starts2, ends2, slack);
+
+    sort_by_key(&mut events, |e| is_point_event(e, starts, ends, starts2, ends2, slack));
+    sort_by_key(&mut events, |e| e.is_start);
+    sort_by_key(&mut events, |e| e.pos);
+    sort_by_key(&mut events, |e| e.chr);
+
+    events
+}
+
+/// Whether `e` belongs to a zero-length interval (see
+/// [`build_sorted_events_points_last`]).
+#[inline]
+pub fn is_point_event<C: GroupType, T: CoordinateType>(
+    e: &GenericEvent<C, T>,
+    starts: &[T],
+    ends: &[T],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
+) -> bool {
+    let i = e.idx as usize;
+    if e.first_set {
+        slack.is_zero() && starts[i] == ends[i]
+    } else {
+        starts2[i] == ends2[i]
+    }
+}
+
+fn build_generic_events<C: GroupType, T: CoordinateType>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    chrs2: &[C],
+    starts2: &[T],
+    ends2: &[T],
+    slack: T,
 ) -> Vec<GenericEvent<C, T>> {
     let mut events = Vec::with_capacity(2 * (chrs.len() + chrs2.len()));
 
@@ -325,7 +615,7 @@ pub fn build_sorted_events<C: GroupType, T: PositionType>(
         });
         events.push(GenericEvent {
             chr: chrs[i],
-            pos: ends[i].saturating_add(slack),
+            pos: saturating_add_slack(ends[i], slack),
             is_start: false,
             first_set: true,
             idx: i as u32,
@@ -349,14 +639,10 @@ pub fn build_sorted_events<C: GroupType, T: PositionType>(
         });
     }
 
-    sort_by_key(&mut events, |e| e.is_start);
-    sort_by_key(&mut events, |e| e.pos);
-    sort_by_key(&mut events, |e| e.chr);
-
     events
 }
 
-pub fn build_sorted_maxevents_with_starts_ends<C: GroupType, T: PositionType>(
+pub fn build_sorted_maxevents_with_starts_ends<C: GroupType, T: CoordinateType>(
     chrs: &[C],
     starts: &[T],
     ends: &[T],
@@ -520,3 +806,146 @@ pub fn build_sorted_events_from_intervals<C: GroupType, T: PositionType>(
 
     events
 }
+
+/// Leading bytes of a serialised event array.
+const SORTED_EVENTS_MAGIC: &[u8; 4] = b"RREV";
+
+/// Writes events (e.g. from
+/// [`build_sorted_events_single_collection_separate_outputs`]) so another
+/// process can reuse them without sorting again.
+///
+/// Format, little-endian: the magic `RREV`, the event count as `u64`, then
+/// per event `chr` as `u32`, `pos` as `i64` and `idx` as `u32`. Fails with
+/// `InvalidInput` if a chromosome code does not fit in a `u32`.
+pub fn serialize_sorted_events<C: GroupType, T: PositionType>(
+    events: &[MinEvent<C, T>],
+    writer: &mut dyn Write,
+) -> io::Result<()> {
+    writer.write_all(SORTED_EVENTS_MAGIC)?;
+    writer.write_all(&(events.len() as u64).to_le_bytes())?;
+    for e in events {
+        let chr = e.chr.to_u32().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("chromosome code {:?} does not fit in u32", e.chr))
+        })?;
+        writer.write_all(&chr.to_le_bytes())?;
+        writer.write_all(&e.pos.to_i64().unwrap().to_le_bytes())?;
+        writer.write_all(&e.idx.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads events written by [`serialize_sorted_events`], in the order they
+/// were written. Fails with `InvalidData` on a bad header or on values that
+/// do not fit in `C`/`T`.
+pub fn deserialize_sorted_events<C: GroupType, T: PositionType>(
+    reader: &mut dyn Read,
+) -> io::Result<Vec<MinEvent<C, T>>> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != SORTED_EVENTS_MAGIC {
+        return Err(invalid("not a sorted event file"));
+    }
+    let mut count = [0u8; 8];
+    reader.read_exact(&mut count)?;
+    let count = u64::from_le_bytes(count) as usize;
+
+    // `count` comes from the file, so a corrupt header must not decide the
+    // allocation; beyond the cap the vector grows as records actually arrive.
+    let mut events = Vec::with_capacity(count.min(1 << 16));
+    let mut record = [0u8; 16];
+    for _ in 0..count {
+        reader.read_exact(&mut record)?;
+        let chr = u32::from_le_bytes(record[0..4].try_into().unwrap());
+        let pos = i64::from_le_bytes(record[4..12].try_into().unwrap());
+        let idx = u32::from_le_bytes(record[12..16].try_into().unwrap());
+        events.push(MinEvent {
+            chr: C::from(chr).ok_or_else(|| invalid("chromosome code out of range"))?,
+            pos: T::from(pos).ok_or_else(|| invalid("position out of range"))?,
+            idx,
+        });
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_events_roundtrip() {
+        let chrs = [1u16, 0, 1, 0];
+        let pos = [50i32, -5, 10, 7];
+        let events = build_sorted_events_single_collection_separate_outputs(&chrs, &pos, 0);
+
+        let mut buf = Vec::new();
+        serialize_sorted_events(&events, &mut buf).unwrap();
+        assert_eq!(buf.len(), 4 + 8 + 16 * events.len());
+
+        let loaded: Vec<MinEvent<u16, i32>> = deserialize_sorted_events(&mut buf.as_slice()).unwrap();
+        let key = |e: &MinEvent<u16, i32>| (e.chr, e.pos, e.idx);
+        assert_eq!(loaded.iter().map(key).collect::<Vec<_>>(), events.iter().map(key).collect::<Vec<_>>());
+
+        // A position that does not fit the requested type is rejected.
+        let big = build_sorted_events_single_collection_separate_outputs(&[0u32], &[1i64 << 40], 0);
+        let mut buf = Vec::new();
+        serialize_sorted_events(&big, &mut buf).unwrap();
+        assert!(deserialize_sorted_events::<u32, i32>(&mut buf.as_slice()).is_err());
+        assert!(deserialize_sorted_events::<u32, i64>(&mut &b"nope"[..]).is_err());
+
+        // A truncated file claiming `u64::MAX` events fails instead of
+        // trying to allocate them up front.
+        let mut buf = Vec::new();
+        serialize_sorted_events(&events, &mut buf).unwrap();
+        buf[4..12].copy_from_slice(&u64::MAX.to_le_bytes());
+        let err = deserialize_sorted_events::<u16, i32>(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_per_chromosome_sort_matches_global_sort() {
+        let chrs = vec![2u32, 0, 1, 0, 2, 1, 0];
+        let starts = vec![5i64, 10, 0, 10, 3, 7, 20];
+        let ends = vec![9i64, 20, 7, 15, 5, 9, 30];
+        let chrs2 = vec![0u32, 1, 2, 0];
+        let starts2 = vec![15i64, 7, 9, 30];
+        let ends2 = vec![20i64, 8, 10, 35];
+
+        let key = |e: &GenericEvent<u32, i64>| (e.chr, e.pos, e.is_start, e.first_set, e.idx);
+
+        let global: Vec<_> = build_sorted_events(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0)
+            .iter()
+            .map(key)
+            .collect();
+        let per_chr: Vec<_> =
+            build_sorted_events_per_chromosome(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0)
+                .iter()
+                .map(key)
+                .collect();
+
+        assert_eq!(global, per_chr);
+
+        // Ends sort before starts at equal positions within a chromosome.
+        for w in per_chr.windows(2) {
+            if w[0].0 == w[1].0 && w[0].1 == w[1].1 {
+                assert!(w[0].2 <= w[1].2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_order_idx_by_descending_and_length() {
+        let chrs = [0u32, 0, 0, 1];
+        let starts = [5i64, 5, 1, 0];
+        let ends = [20i64, 8, 30, 3];
+
+        assert_eq!(
+            sort_order_idx_by(&chrs, &starts, &ends, None, false, "end"),
+            sort_order_idx(&chrs, &starts, &ends, None),
+        );
+        assert_eq!(sort_order_idx_by(&chrs, &starts, &ends, None, false, "length"), vec![2, 1, 0, 3]);
+        assert_eq!(sort_order_idx_by(&chrs, &starts, &ends, None, true, "end"), vec![0, 1, 2, 3]);
+        assert_eq!(sort_order_idx_by(&chrs, &starts, &ends, None, true, "length"), vec![0, 1, 2, 3]);
+    }
+}
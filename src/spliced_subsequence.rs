@@ -1,6 +1,8 @@
 use radsort::sort_by_key;
+use rustc_hash::FxHashMap;
 
 use crate::{
+    group_cumsum::sweep_line_cumsum_with_totals,
     ruranges_structs::{GroupType, PositionType, SplicedSubsequenceInterval},
     sorts::build_sorted_subsequence_intervals,
 };
@@ -13,7 +15,98 @@ fn global_shift<T: PositionType>(starts: &[T], ends: &[T]) -> T {
     if min_coord < T::zero() { -min_coord } else { T::zero() }
 }
 
+/// Sorts the exons of each group in the order their spliced length is
+/// counted: 5'->3' along their strand, or left to right for every strand with
+/// `force_plus_strand`. `forward_strand` keeps the real strand either way.
+fn sorted_exons<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    strand_flags: &[bool],
+    force_plus_strand: bool,
+) -> Vec<SplicedSubsequenceInterval<G, T>> {
+    if !force_plus_strand {
+        return build_sorted_subsequence_intervals(chrs, starts, ends, strand_flags);
+    }
+    let plus = vec![true; strand_flags.len()];
+    let mut intervals = build_sorted_subsequence_intervals(chrs, starts, ends, &plus);
+    for iv in &mut intervals {
+        iv.forward_strand = strand_flags[iv.idx as usize];
+    }
+    intervals
+}
+
+/// Checks that the exons of every transcript (`chrs` group) can be chained:
+/// all on one strand and, in input order, sorted by coordinate with none
+/// overlapping the next.
+///
+/// The spliced-length bookkeeping assumes this; exons given out of order, or
+/// of several transcripts sharing a group code, silently give wrong
+/// coordinates. The error names the offending transcript and rows.
+pub fn validate_exon_order<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    strand_flags: &[bool],
+) -> Result<(), String> {
+    // Previous row of every transcript seen so far.
+    let mut last_row: FxHashMap<G, usize> = FxHashMap::default();
+
+    for cur in 0..chrs.len() {
+        let Some(prev) = last_row.insert(chrs[cur], cur) else {
+            continue;
+        };
+        if strand_flags[prev] != strand_flags[cur] {
+            return Err(format!(
+                "transcript {:?} mixes strands (rows {} and {})",
+                chrs[cur], prev, cur
+            ));
+        }
+        if starts[cur] < starts[prev] {
+            return Err(format!(
+                "transcript {:?} has exons out of order (rows {} and {}: {}-{} before {}-{})",
+                chrs[cur], prev, cur, starts[prev], ends[prev], starts[cur], ends[cur]
+            ));
+        }
+        if starts[cur] < ends[prev] {
+            return Err(format!(
+                "transcript {:?} has overlapping exons (rows {} and {}: {}-{} and {}-{})",
+                chrs[cur], prev, cur, starts[prev], ends[prev], starts[cur], ends[cur]
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Total spliced length (sum of exon lengths) of every transcript, one value
+/// per distinct `chrs` group in ascending group order.
+///
+/// This is the final running total of [`sweep_line_cumsum`] for the group,
+/// e.g. to turn negative `spliced_subseq` offsets into absolute ones.
+///
+/// [`sweep_line_cumsum`]: crate::group_cumsum::sweep_line_cumsum
+pub fn spliced_length<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    strand_flags: &[bool],
+) -> Vec<T> {
+    // Unsorted output comes grouped by chromosome, in ascending order.
+    let (idxs, _, _, totals) = sweep_line_cumsum_with_totals(chrs, starts, ends, strand_flags, false);
+
+    let mut lengths = Vec::new();
+    for i in 0..idxs.len() {
+        if i == 0 || chrs[idxs[i] as usize] != chrs[idxs[i - 1] as usize] {
+            lengths.push(totals[i]);
+        }
+    }
+    lengths
+}
+
 /// (idxs, starts, ends, strands) for **one** (start,end) slice
+///
+/// The output strand is `true` unless a minus-strand exon was read left to
+/// right because of `force_plus_strand`.
 pub fn spliced_subseq<G: GroupType, T: PositionType>(
     chrs:           &[G],
     starts:         &[T],
@@ -59,11 +152,12 @@ pub fn spliced_subseq<G: GroupType, T: PositionType>(
     }
 
     // Build sorted interval vector (caller guarantees same grouping rules).
-    let mut intervals = build_sorted_subsequence_intervals(
+    let intervals = sorted_exons(
         chrs,
         starts_slice,
         ends_slice,
         strand_flags,
+        force_plus_strand,
     );
 
     // Early-exit when nothing to do
@@ -182,6 +276,9 @@ pub fn spliced_subseq<G: GroupType, T: PositionType>(
     (out_idxs, out_starts, out_ends, out_strands)
 }
 
+/// Same as [`spliced_subseq`] with one `(start, end)` per row; each
+/// transcript uses the slice of its first exon in sorted order. Negative
+/// offsets count from the 3' end exactly as in the single-window path.
 pub fn spliced_subseq_multi<G: GroupType, T: PositionType>(
     chrs: &[G],
     starts: &[T],
@@ -225,8 +322,7 @@ pub fn spliced_subseq_multi<G: GroupType, T: PositionType>(
         strand: bool,
     }
 
-    let mut intervals =
-        build_sorted_subsequence_intervals(chrs, starts_slice, ends_slice, strand_flags);
+    let intervals = sorted_exons(chrs, starts_slice, ends_slice, strand_flags, force_plus_strand);
 
     if intervals.is_empty() {
         return (Vec::new(), Vec::new(), Vec::new(), Vec::new());
@@ -358,3 +454,148 @@ pub fn spliced_subseq_multi<G: GroupType, T: PositionType>(
 
     (out_idxs, out_starts, out_ends, out_strands)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base-by-base reference for one transcript: walk its bases 5'->3'
+    /// (left to right when forced to plus), keep positions `start..end` of
+    /// that walk and return the kept pieces per exon as
+    /// `(idx, start, end, strand)` in row order.
+    fn reference_slice(
+        exons: &[(u32, i64, i64)],
+        forward: bool,
+        force_plus_strand: bool,
+        start: i64,
+        end: Option<i64>,
+    ) -> Vec<(u32, i64, i64, bool)> {
+        let left_to_right = forward || force_plus_strand;
+        let mut bases: Vec<(u32, i64)> = exons
+            .iter()
+            .flat_map(|&(idx, s, e)| (s..e).map(move |p| (idx, p)))
+            .collect();
+        bases.sort_by_key(|&(_, p)| p);
+        if !left_to_right {
+            bases.reverse();
+        }
+
+        let total = bases.len() as i64;
+        let resolve = |v: i64| if v < 0 { total + v } else { v };
+        let (from, to) = (resolve(start), resolve(end.unwrap_or(total)));
+
+        let mut out: Vec<(u32, i64, i64, bool)> = Vec::new();
+        for (pos, &(idx, p)) in bases.iter().enumerate() {
+            if (pos as i64) < from || (pos as i64) >= to {
+                continue;
+            }
+            match out.iter_mut().find(|o| o.0 == idx) {
+                Some(o) => {
+                    o.1 = o.1.min(p);
+                    o.2 = o.2.max(p + 1);
+                }
+                None => out.push((idx, p, p + 1, forward == left_to_right)),
+            }
+        }
+        out.sort_by_key(|o| o.0);
+        out
+    }
+
+    #[test]
+    fn test_spliced_length_matches_cumsum_total() {
+        let chrs = [2u32, 0, 2, 0, 1];
+        let starts = [100i64, 0, 10, 20, 5];
+        let ends = [150i64, 10, 40, 25, 6];
+        let strands = [false, true, false, true, true];
+
+        let lengths = spliced_length(&chrs, &starts, &ends, &strands);
+        assert_eq!(lengths, vec![15, 1, 80]);
+
+        let (idxs, _, cum_ends) = crate::group_cumsum::sweep_line_cumsum(&chrs, &starts, &ends, &strands, false);
+        for (group, &len) in [0u32, 1, 2].iter().zip(&lengths) {
+            let last = (0..idxs.len()).rfind(|&i| chrs[idxs[i] as usize] == *group).unwrap();
+            assert_eq!(cum_ends[last], len);
+        }
+    }
+
+    #[test]
+    fn test_spliced_subseq_multi_last_bases_per_row() {
+        // Transcript 0 (+) and 1 (-), both with exons [0, 10) and [20, 30).
+        let chrs = [0u32, 0, 1, 1];
+        let starts = [0i64, 20, 0, 20];
+        let ends = [10i64, 30, 10, 30];
+        let strands = [true, true, false, false];
+
+        // Last 5 nt of transcript 0 and last 12 nt of transcript 1, i.e. the
+        // 3' end: the right end on +, the left end (spanning the intron) on -.
+        let (idx, st, en, _) = spliced_subseq_multi(
+            &chrs, &starts, &ends, &strands,
+            &[-5, -5, -12, -12], &[None; 4], false,
+        );
+        assert_eq!(idx, vec![1, 2, 3]);
+        assert_eq!(st, vec![25, 0, 20]);
+        assert_eq!(en, vec![30, 10, 22]);
+
+        // Same as the single-window path for one shared offset.
+        for k in [1i64, 5, 15, 25] {
+            assert_eq!(
+                spliced_subseq_multi(&chrs, &starts, &ends, &strands, &[-k; 4], &[None; 4], false),
+                spliced_subseq(&chrs, &starts, &ends, &strands, -k, None, false),
+            );
+        }
+    }
+
+    #[test]
+    fn test_spliced_subseq_strand_matrix() {
+        // Three exons of 10, 5 and 10 bases, out of coordinate order in the input.
+        let exons = [(0u32, 40i64, 50i64), (1, 0, 10), (2, 20, 25)];
+        let chrs = [0u32; 3];
+        let starts: Vec<i64> = exons.iter().map(|e| e.1).collect();
+        let ends: Vec<i64> = exons.iter().map(|e| e.2).collect();
+        let slices = [(3i64, Some(12i64)), (0, None), (-12, Some(-3)), (-7, None), (2, Some(-2))];
+
+        for forward in [true, false] {
+            for force_plus_strand in [false, true] {
+                for &(start, end) in &slices {
+                    let (idx, st, en, strand) = spliced_subseq(
+                        &chrs, &starts, &ends, &[forward; 3], start, end, force_plus_strand,
+                    );
+                    let got: Vec<(u32, i64, i64, bool)> = (0..idx.len())
+                        .map(|i| (idx[i], st[i], en[i], strand[i]))
+                        .collect();
+                    let expected = reference_slice(&exons, forward, force_plus_strand, start, end);
+                    assert_eq!(
+                        got, expected,
+                        "forward={forward} force_plus_strand={force_plus_strand} slice={start}..{end:?}"
+                    );
+
+                    let (idx_m, st_m, en_m, strand_m) = spliced_subseq_multi(
+                        &chrs, &starts, &ends, &[forward; 3],
+                        &[start; 3], &[end; 3], force_plus_strand,
+                    );
+                    assert_eq!((idx_m, st_m, en_m, strand_m), (idx, st, en, strand));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_exon_order() {
+        let starts = [0i64, 20, 15];
+        let ends = [10i64, 30, 18];
+        let strands = [true, true, true];
+
+        assert!(validate_exon_order(&[0u32, 0, 1], &starts, &ends, &strands).is_ok());
+
+        // Two transcripts sharing group 0: [5, 25) overlaps both other exons.
+        let err = validate_exon_order(&[0u32, 0, 0], &[0i64, 5, 20], &[10i64, 25, 30], &strands).unwrap_err();
+        assert!(err.contains("transcript 0") && err.contains("overlapping"), "{err}");
+
+        // Disjoint exons, but listed right to left.
+        let err = validate_exon_order(&[0u32, 0], &[20i64, 0], &[30i64, 10], &[true, true]).unwrap_err();
+        assert!(err.contains("transcript 0") && err.contains("out of order"), "{err}");
+
+        let err = validate_exon_order(&[0u32, 0, 1], &starts, &ends, &[true, false, true]).unwrap_err();
+        assert!(err.contains("mixes strands"), "{err}");
+    }
+}
@@ -76,3 +76,114 @@ pub fn sweep_line_split<G: GroupType, T: PositionType>(
 
     (idxs_out, starts_out, ends_out)
 }
+
+/// Same as [`sweep_line_split`], but no output segment is longer than
+/// `max_len`: longer segments are cut every `max_len` bases from their start,
+/// with the last piece holding the remainder.
+pub fn sweep_line_split_max_length<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    slack: T,
+    max_len: T,
+    between: bool,
+) -> (Vec<u32>, Vec<T>, Vec<T>) {
+    assert!(max_len > T::zero(), "max_len must be positive");
+
+    let (idxs, seg_starts, seg_ends) = sweep_line_split(chrs, starts, ends, slack, between);
+
+    let mut idxs_out = Vec::with_capacity(idxs.len());
+    let mut starts_out = Vec::with_capacity(idxs.len());
+    let mut ends_out = Vec::with_capacity(idxs.len());
+
+    for ((idx, start), end) in idxs.into_iter().zip(seg_starts).zip(seg_ends) {
+        let mut cur = start;
+        while end - cur > max_len {
+            idxs_out.push(idx);
+            starts_out.push(cur);
+            ends_out.push(cur + max_len);
+            cur = cur + max_len;
+        }
+        idxs_out.push(idx);
+        starts_out.push(cur);
+        ends_out.push(end);
+    }
+
+    (idxs_out, starts_out, ends_out)
+}
+
+/// Assigns every interval a layer (0, 1, 2, ...) such that intervals in the
+/// same layer never overlap, like colouring the interval-overlap graph.
+///
+/// Greedy: intervals are visited in (chr, start) order and each goes to the
+/// first layer whose last interval ends at or before its start; a new layer
+/// is opened when none fits. Layers are restarted on every chromosome.
+pub fn partition_into_layers<G: GroupType, T: PositionType>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+) -> Vec<u32> {
+    let intervals = sorts::build_sorted_intervals(chrs, starts, ends, None, T::zero(), true);
+
+    let mut layers = vec![0u32; chrs.len()];
+    // End of the last interval placed in each layer on the current chromosome.
+    let mut layer_ends: Vec<T> = Vec::new();
+    let mut current_chr = None;
+
+    for iv in &intervals {
+        if current_chr != Some(iv.group) {
+            current_chr = Some(iv.group);
+            layer_ends.clear();
+        }
+
+        let layer = match layer_ends.iter().position(|&end| end <= iv.start) {
+            Some(layer) => {
+                layer_ends[layer] = iv.end;
+                layer
+            }
+            None => {
+                layer_ends.push(iv.end);
+                layer_ends.len() - 1
+            }
+        };
+        layers[iv.idx as usize] = layer as u32;
+    }
+
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_into_layers_separates_overlaps() {
+        let chrs = [0u32, 0, 0, 0, 1];
+        let starts = [0i64, 5, 10, 12, 0];
+        let ends = [10i64, 15, 20, 14, 10];
+
+        let layers = partition_into_layers(&chrs, &starts, &ends);
+
+        // [0,10) and [10,20) touch but don't overlap, so they share layer 0;
+        // [12,14) overlaps both other layers' last intervals.
+        assert_eq!(layers, vec![0, 1, 0, 2, 0]);
+    }
+
+    #[test]
+    fn test_split_max_length_caps_segments() {
+        let (idx, s, e) = sweep_line_split_max_length(&[0u32, 0], &[0i64, 5], &[12i64, 8], 0, 4, false);
+        assert_eq!(s, vec![0, 4, 5, 8]);
+        assert_eq!(e, vec![4, 5, 8, 12]);
+        assert_eq!(idx.len(), 4);
+
+        // [3, 13) on its own: two full pieces and a last partial one.
+        let (idx, s, e) = sweep_line_split_max_length(&[0u32], &[3i64], &[13i64], 0, 4, false);
+        assert_eq!(idx, vec![0, 0, 0]);
+        assert_eq!(s, vec![3, 7, 11]);
+        assert_eq!(e, vec![7, 11, 13]);
+
+        // A segment of exactly max_len is not cut.
+        let (_, s, e) = sweep_line_split_max_length(&[0u32], &[0i64], &[4i64], 0, 4, false);
+        assert_eq!((s, e), (vec![0], vec![4]));
+    }
+}
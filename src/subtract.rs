@@ -153,3 +153,161 @@ pub fn sweep_line_subtract<G: GroupType, T: PositionType>(
 
     (out_idxs, out_starts, out_ends)
 }
+
+/// [`sweep_line_subtract`] without the slivers: output fragments shorter
+/// than `min_fraction` of their original interval's length are dropped.
+pub fn sweep_line_subtract_min_fraction<G: GroupType, T: PositionType>(
+    chrs1: &[G],
+    starts1: &[T],
+    ends1: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+    min_fraction: f64,
+) -> (Vec<u32>, Vec<T>, Vec<T>) {
+    let (idxs, starts, ends) = sweep_line_subtract(chrs1, starts1, ends1, chrs2, starts2, ends2);
+
+    let mut out_idxs = Vec::with_capacity(idxs.len());
+    let mut out_starts = Vec::with_capacity(idxs.len());
+    let mut out_ends = Vec::with_capacity(idxs.len());
+
+    for ((idx, start), end) in idxs.into_iter().zip(starts).zip(ends) {
+        let original_length = (ends1[idx as usize] - starts1[idx as usize]).to_f64().unwrap();
+        let length = (end - start).to_f64().unwrap();
+        if length < min_fraction * original_length {
+            continue;
+        }
+        out_idxs.push(idx);
+        out_starts.push(start);
+        out_ends.push(end);
+    }
+
+    (out_idxs, out_starts, out_ends)
+}
+
+/// The opposite view of [`sweep_line_subtract`]: for each set1 interval, the
+/// parts covered by set2, merged and clipped to the set1 interval.
+///
+/// Returns `(idx1, start, end)` sorted by `idx1` then `start`. Set2
+/// intervals that overlap or touch are merged into one piece.
+pub fn sweep_line_clip_to<G: GroupType, T: PositionType>(
+    chrs1: &[G],
+    starts1: &[T],
+    ends1: &[T],
+    chrs2: &[G],
+    starts2: &[T],
+    ends2: &[T],
+) -> (Vec<u32>, Vec<T>, Vec<T>) {
+    if chrs1.is_empty() || chrs2.is_empty() {
+        return (Vec::new(), Vec::new(), Vec::new());
+    }
+
+    let events =
+        sorts::build_sorted_events_idxs(chrs1, starts1, ends1, chrs2, starts2, ends2, T::zero());
+
+    let mut pieces = Vec::new();
+    let mut active2_count: i64 = 0;
+    // active1[idx] = Some(position) while set2 covers `idx` since `position`.
+    let mut active1: FxHashMap<u32, Option<T>> = FxHashMap::default();
+    let mut current_chr = events.first().unwrap().chr;
+
+    for e in events.iter() {
+        if e.chr != current_chr {
+            active1.clear();
+            active2_count = 0;
+            current_chr = e.chr;
+        }
+        let pos = e.pos;
+
+        if e.first_set {
+            if e.is_start {
+                active1.insert(e.idx, if active2_count > 0 { Some(pos) } else { None });
+            } else {
+                if let Some(Some(start_pos)) = active1.remove(&e.idx) {
+                    if start_pos < pos {
+                        pieces.push(MinInterval { start: start_pos, end: pos, idx: e.idx });
+                    }
+                }
+            }
+        } else if e.is_start {
+            active2_count += 1;
+            // Coverage begins: start a piece in every open set1 interval.
+            if active2_count == 1 {
+                for v in active1.values_mut() {
+                    *v = Some(pos);
+                }
+            }
+        } else {
+            active2_count -= 1;
+            // Coverage ends: close the pieces.
+            if active2_count == 0 {
+                for (&idx1, v) in active1.iter_mut() {
+                    if let Some(start_pos) = v.take() {
+                        if start_pos < pos {
+                            pieces.push(MinInterval { start: start_pos, end: pos, idx: idx1 });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    sort_by_key(&mut pieces, |p| p.start);
+    sort_by_key(&mut pieces, |p| p.idx);
+
+    // Ends sort before starts at equal positions, so touching set2 intervals
+    // leave two bookended pieces; join them.
+    let mut out_idxs: Vec<u32> = Vec::with_capacity(pieces.len());
+    let mut out_starts: Vec<T> = Vec::with_capacity(pieces.len());
+    let mut out_ends: Vec<T> = Vec::with_capacity(pieces.len());
+    for p in pieces {
+        if out_idxs.last() == Some(&p.idx) && out_ends.last() == Some(&p.start) {
+            *out_ends.last_mut().unwrap() = p.end;
+            continue;
+        }
+        out_idxs.push(p.idx);
+        out_starts.push(p.start);
+        out_ends.push(p.end);
+    }
+
+    (out_idxs, out_starts, out_ends)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_to_merges_set2_within_each_set1_interval() {
+        let chrs = [0u32, 0, 1];
+        let starts = [0i64, 50, 0];
+        let ends = [100i64, 60, 10];
+        // Two touching and one overlapping interval inside [0, 100), one
+        // spanning both 0-intervals, and nothing on chr 1.
+        let chrs2 = [0u32, 0, 0, 0];
+        let starts2 = [10i64, 20, 25, 55];
+        let ends2 = [20i64, 30, 40, 120];
+
+        let (idx, s, e) = sweep_line_clip_to(&chrs, &starts, &ends, &chrs2, &starts2, &ends2);
+        assert_eq!(idx, vec![0, 0, 1]);
+        assert_eq!(s, vec![10, 55, 55]);
+        assert_eq!(e, vec![40, 100, 60]);
+    }
+
+    #[test]
+    fn test_subtract_min_fraction_drops_slivers() {
+        // 60 of the 100 bp are covered, leaving a 40 bp fragment.
+        let chrs = [0u32];
+        let starts = [0i64];
+        let ends = [100i64];
+        let chrs2 = [0u32];
+        let starts2 = [40i64];
+        let ends2 = [100i64];
+
+        let (idx, _, _) = sweep_line_subtract_min_fraction(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0.5);
+        assert!(idx.is_empty());
+
+        let (idx, s, e) = sweep_line_subtract_min_fraction(&chrs, &starts, &ends, &chrs2, &starts2, &ends2, 0.4);
+        assert_eq!((idx, s, e), (vec![0], vec![0], vec![40]));
+    }
+}
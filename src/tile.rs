@@ -1,12 +1,67 @@
+use std::str::FromStr;
+
+use radsort::sort_by_key;
+use rustc_hash::FxHashMap;
+
+use crate::coverage::coverage_rle;
+use crate::overlaps::count_overlaps;
 use crate::ruranges_structs::{GroupType, PositionType};
 
+/// `(tile_starts, tile_ends, indices, tile_fractions)`
+pub type TileOutput<T> = (Vec<T>, Vec<T>, Vec<usize>, Vec<f64>);
+
+/// [`TileOutput`] followed by the interval fractions.
+pub type TileFractionOutput<T> = (Vec<T>, Vec<T>, Vec<usize>, Vec<f64>, Vec<f64>);
+
+/// [`TileFractionOutput`] followed by the genome-wide tile ids
+/// (`tile_start / tile_size`).
+pub type TileGroupedOutput<T> = (Vec<T>, Vec<T>, Vec<usize>, Vec<f64>, Vec<f64>, Vec<T>);
+
+/// Largest multiple of `tile_size` that is <= `pos` (rounds toward -inf).
+#[inline]
+fn floor_to_tile<T: PositionType>(pos: T, tile_size: T) -> T {
+    let mut multiple = pos / tile_size;
+    if pos < T::zero() && pos % tile_size != T::zero() {
+        multiple = multiple - T::one();
+    }
+    multiple * tile_size
+}
+
+/// [`tile`] walking one group of equal `chrs` at a time.
+///
+/// With `chrom_lens`, tiles are clamped to `[0, len]` of their group and
+/// dropped when they lie entirely outside it; every group must then have a
+/// length. Overlap fractions keep the full `tile_size` as denominator.
 pub fn tile_grouped<T, C>(
     chrs: &[C],
     starts: &[T],
     ends: &[T],
     negative_strand: &[bool],
     tile_size: T,
-) -> (Vec<T>, Vec<T>, Vec<usize>, Vec<f64>)
+    chrom_lens: Option<&FxHashMap<C, T>>,
+) -> Result<TileOutput<T>, String>
+where
+    T: PositionType,
+    C: GroupType + PartialEq,
+{
+    let (out_starts, out_ends, out_indices, out_overlaps, _, _) =
+        tile_grouped_with_ids(chrs, starts, ends, negative_strand, tile_size, chrom_lens)?;
+    Ok((out_starts, out_ends, out_indices, out_overlaps))
+}
+
+/// [`tile_grouped`] with two extra output columns: the interval fractions of
+/// [`tile_with_interval_fraction`] and the tile id `tile_start / tile_size`.
+/// Tile starts are multiples of `tile_size`, so the id is exact and identifies
+/// the same genomic tile across feature sets. Tile ids refer to the unclamped
+/// tile.
+pub fn tile_grouped_with_ids<T, C>(
+    chrs: &[C],
+    starts: &[T],
+    ends: &[T],
+    negative_strand: &[bool],
+    tile_size: T,
+    chrom_lens: Option<&FxHashMap<C, T>>,
+) -> Result<TileGroupedOutput<T>, String>
 where
     T: PositionType,          // signed integer-like
     C: GroupType + PartialEq, // unsigned integer-like; equality for boundaries
@@ -20,9 +75,10 @@ where
     let mut out_ends = Vec::new();
     let mut out_indices = Vec::new();
     let mut out_overlaps = Vec::new();
+    let mut out_feature_fractions = Vec::new();
 
     if n == 0 {
-        return (out_starts, out_ends, out_indices, out_overlaps);
+        return Ok((out_starts, out_ends, out_indices, out_overlaps, out_feature_fractions, Vec::new()));
     }
 
     let denom = tile_size.to_f64().unwrap();
@@ -40,10 +96,21 @@ where
             let s = starts[i];
             let e = ends[i];
 
-            // Skip invalid intervals.
-            if e <= s {
+            // Skip invalid intervals; a zero-length interval lands in the
+            // tile containing its position.
+            if e < s {
+                continue;
+            }
+            if e == s {
+                let tile_start = floor_to_tile(s, tile_size);
+                out_starts.push(tile_start);
+                out_ends.push(tile_start + tile_size);
+                out_indices.push(i);
+                out_overlaps.push(0.0);
+                out_feature_fractions.push(1.0);
                 continue;
             }
+            let feature_len = (e - s).to_f64().unwrap();
 
             if !negative_strand[i] {
                 // === Forward direction (same as original) ===
@@ -70,6 +137,7 @@ where
                         out_ends.push(tile_end);
                         out_indices.push(i);
                         out_overlaps.push(overlap_fraction);
+                        out_feature_fractions.push(num / feature_len);
                     }
                     tile_start = tile_start + tile_size;
                 }
@@ -101,6 +169,7 @@ where
                         out_ends.push(tile_end);
                         out_indices.push(i);
                         out_overlaps.push(overlap_fraction);
+                        out_feature_fractions.push(num / feature_len);
                     }
                     tile_end = tile_end - tile_size;
                 }
@@ -110,7 +179,37 @@ where
         g_start = g_end;
     }
 
-    (out_starts, out_ends, out_indices, out_overlaps)
+    let mut tile_ids: Vec<T> = out_starts.iter().map(|&tile_start| tile_start / tile_size).collect();
+
+    if let Some(lens) = chrom_lens {
+        let mut kept = 0;
+        for k in 0..out_starts.len() {
+            let chr = chrs[out_indices[k]];
+            let len = *lens
+                .get(&chr)
+                .ok_or_else(|| format!("No chromosome length given for group {:?}", chr))?;
+            let tile_start = out_starts[k].max(T::zero());
+            let tile_end = out_ends[k].min(len);
+            if tile_start >= tile_end {
+                continue;
+            }
+            out_starts[kept] = tile_start;
+            out_ends[kept] = tile_end;
+            out_indices[kept] = out_indices[k];
+            out_overlaps[kept] = out_overlaps[k];
+            out_feature_fractions[kept] = out_feature_fractions[k];
+            tile_ids[kept] = tile_ids[k];
+            kept += 1;
+        }
+        out_starts.truncate(kept);
+        out_ends.truncate(kept);
+        out_indices.truncate(kept);
+        out_overlaps.truncate(kept);
+        out_feature_fractions.truncate(kept);
+        tile_ids.truncate(kept);
+    }
+
+    Ok((out_starts, out_ends, out_indices, out_overlaps, out_feature_fractions, tile_ids))
 }
 
 
@@ -126,12 +225,32 @@ where
 /// - For an interval 100–250 with tile size 100:
 ///     - The tile [100,200) gets an overlap fraction of 1.0,
 ///     - The tile [200,300) gets an overlap fraction of 0.5.
+///
+/// Tiles may reach below 0 or past the chromosome end; [`tile_grouped`] can
+/// clamp them to chromosome bounds.
 pub fn tile<T>(
     starts: &[T],
     ends: &[T],
     negative_strand: &[bool],
     tile_size: T,
-) -> (Vec<T>, Vec<T>, Vec<usize>, Vec<f64>) where T: PositionType {
+) -> TileOutput<T> where T: PositionType {
+    let (out_starts, out_ends, out_indices, out_overlaps, _) =
+        tile_with_interval_fraction(starts, ends, negative_strand, tile_size);
+    (out_starts, out_ends, out_indices, out_overlaps)
+}
+
+/// [`tile`] with an extra output column: the tile overlap as a fraction of the
+/// original interval's length, so it sums to 1 per interval.
+///
+/// For the interval 100–250 with tile size 100 the tiles hold 100/150 and
+/// 50/150 of its bases. A zero-length interval at 150 yields the tile
+/// [100,200) with an overlap fraction of 0.0 and an interval fraction of 1.0.
+pub fn tile_with_interval_fraction<T>(
+    starts: &[T],
+    ends: &[T],
+    negative_strand: &[bool],
+    tile_size: T,
+) -> TileFractionOutput<T> where T: PositionType {
     assert_eq!(starts.len(), ends.len());
     assert_eq!(starts.len(), negative_strand.len());
 
@@ -139,6 +258,7 @@ pub fn tile<T>(
     let mut out_ends = Vec::new();
     let mut out_indices = Vec::new();
     let mut out_overlaps = Vec::new();
+    let mut out_feature_fractions = Vec::new();
     let denom = tile_size.to_f64().unwrap();
 
     for (i, ((&s, &e), &is_neg)) in starts
@@ -147,10 +267,21 @@ pub fn tile<T>(
         .zip(negative_strand.iter())
         .enumerate()
     {
-        // Skip invalid intervals.
-        if e <= s {
+        // Skip invalid intervals; a zero-length interval lands in the tile
+        // containing its position.
+        if e < s {
             continue;
         }
+        if e == s {
+            let tile_start = floor_to_tile(s, tile_size);
+            out_starts.push(tile_start);
+            out_ends.push(tile_start + tile_size);
+            out_indices.push(i);
+            out_overlaps.push(0.0);
+            out_feature_fractions.push(1.0);
+            continue;
+        }
+        let feature_len = (e - s).to_f64().unwrap();
 
         if !is_neg {
             // === Forward direction (same as original) === //
@@ -178,6 +309,7 @@ pub fn tile<T>(
                     out_ends.push(tile_end);
                     out_indices.push(i);
                     out_overlaps.push(overlap_fraction);
+                    out_feature_fractions.push(num / feature_len);
                 }
                 tile_start = tile_start + tile_size;
             }
@@ -218,16 +350,244 @@ pub fn tile<T>(
                     out_ends.push(tile_end);
                     out_indices.push(i);
                     out_overlaps.push(overlap_fraction);
+                    out_feature_fractions.push(num / feature_len);
                 }
                 tile_end = tile_end - tile_size;
             }
         }
     }
 
-    (out_starts, out_ends, out_indices, out_overlaps)
+    (out_starts, out_ends, out_indices, out_overlaps, out_feature_fractions)
 }
 
 
+/// Splits every interval into `n` bins of (almost) equal length.
+///
+/// When the interval length is not divisible by `n`, the first `len % n` bins
+/// counted from the 5' end get one extra base. Bins are emitted 5'→3', so for
+/// negative-strand rows the rightmost bin comes first. Intervals shorter than
+/// `n` only yield their non-empty bins; the ordinal still tells which of the
+/// `n` bins each one is.
+///
+/// Returns `(bin_starts, bin_ends, indices, ordinals)`.
+pub fn tile_n<T>(
+    starts: &[T],
+    ends: &[T],
+    negative_strand: &[bool],
+    n: u32,
+) -> (Vec<T>, Vec<T>, Vec<usize>, Vec<u32>) where T: PositionType {
+    assert_eq!(starts.len(), ends.len());
+    assert_eq!(starts.len(), negative_strand.len());
+    assert!(n > 0);
+
+    let mut out_starts = Vec::new();
+    let mut out_ends = Vec::new();
+    let mut out_indices = Vec::new();
+    let mut out_ordinals = Vec::new();
+
+    let bins = T::from(n).expect("number of bins does not fit in the position type");
+
+    for (i, ((&s, &e), &is_neg)) in starts
+        .iter()
+        .zip(ends.iter())
+        .zip(negative_strand.iter())
+        .enumerate()
+    {
+        // Skip invalid intervals.
+        if e <= s {
+            continue;
+        }
+
+        let len = e - s;
+        let base = len / bins;
+        let rem = (len % bins).to_u32().unwrap();
+
+        let mut cursor = if is_neg { e } else { s };
+        for k in 0..n {
+            let size = if k < rem { base + T::one() } else { base };
+            if size.is_zero() {
+                continue;
+            }
+
+            let (bin_start, bin_end) = if is_neg {
+                (cursor - size, cursor)
+            } else {
+                (cursor, cursor + size)
+            };
+            cursor = if is_neg { bin_start } else { bin_end };
+
+            out_starts.push(bin_start);
+            out_ends.push(bin_end);
+            out_indices.push(i);
+            out_ordinals.push(k);
+        }
+    }
+
+    (out_starts, out_ends, out_indices, out_ordinals)
+}
+
+/// Counts, for every fixed-size tile, how many input intervals overlap it.
+///
+/// Unlike [`tile`], the output has one row per tile rather than one row per
+/// (tile, interval) pair. Only tiles covered by at least one interval are
+/// returned, sorted by chromosome and tile start. The covered tiles are taken
+/// from the [`coverage_rle`] runs and then counted with [`count_overlaps`].
+pub fn tile_aggregate_count<G, T>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    tile_size: T,
+) -> (Vec<G>, Vec<T>, Vec<T>, Vec<u32>)
+where
+    G: GroupType,
+    T: PositionType,
+{
+    assert_eq!(starts.len(), ends.len());
+    assert_eq!(starts.len(), chrs.len());
+    assert!(tile_size > T::zero());
+
+    // Skip invalid intervals; count_overlaps would count a point in its tile.
+    let kept: Vec<usize> = (0..chrs.len()).filter(|&i| starts[i] < ends[i]).collect();
+    let k_chrs: Vec<G> = kept.iter().map(|&i| chrs[i]).collect();
+    let k_starts: Vec<T> = kept.iter().map(|&i| starts[i]).collect();
+    let k_ends: Vec<T> = kept.iter().map(|&i| ends[i]).collect();
+
+    let (run_chrs, run_starts, run_ends, _) = coverage_rle(&k_chrs, &k_starts, &k_ends);
+
+    let mut out_chrs: Vec<G> = Vec::new();
+    let mut out_starts: Vec<T> = Vec::new();
+    let mut out_ends: Vec<T> = Vec::new();
+    for ((&chr, &s), &e) in run_chrs.iter().zip(&run_starts).zip(&run_ends) {
+        let mut tile_start = floor_to_tile(s, tile_size);
+        while tile_start < e {
+            // Adjacent runs can share a tile.
+            if out_chrs.last() != Some(&chr) || out_starts.last() != Some(&tile_start) {
+                out_chrs.push(chr);
+                out_starts.push(tile_start);
+                out_ends.push(tile_start + tile_size);
+            }
+            tile_start = tile_start + tile_size;
+        }
+    }
+
+    // The overlap sweep clamps negative starts to 0, so shift both sets onto
+    // non-negative coordinates first; the lowest tile start is a lower bound.
+    let shift = out_starts.iter().fold(T::zero(), |lo, &s| if s < lo { s } else { lo });
+    let shifted = |v: &[T]| v.iter().map(|&x| x - shift).collect::<Vec<T>>();
+    let out_counts = count_overlaps(
+        &out_chrs,
+        &shifted(&out_starts),
+        &shifted(&out_ends),
+        &k_chrs,
+        &shifted(&k_starts),
+        &shifted(&k_ends),
+        T::zero(),
+    );
+
+    (out_chrs, out_starts, out_ends, out_counts)
+}
+
+/// How [`tile_aggregate_scores`] combines the weighted scores in a tile.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TileAggregation {
+    Sum,
+    Mean,
+    Max,
+    Min,
+}
+
+impl FromStr for TileAggregation {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sum" => Ok(TileAggregation::Sum),
+            "mean" => Ok(TileAggregation::Mean),
+            "max" => Ok(TileAggregation::Max),
+            "min" => Ok(TileAggregation::Min),
+            _ => Err("Invalid aggregation string"),
+        }
+    }
+}
+
+/// Aggregates per-interval scores over fixed-size tiles.
+///
+/// Every interval overlapping a tile contributes its score weighted by the
+/// fraction of the tile it covers, as reported by [`tile`]. The contributions
+/// are combined with `aggregation` (`"sum"`, `"mean"`, `"max"` or `"min"`).
+/// As in [`tile_aggregate_count`], only covered tiles are returned, sorted by
+/// chromosome and tile start.
+pub fn tile_aggregate_scores<G, T>(
+    chrs: &[G],
+    starts: &[T],
+    ends: &[T],
+    scores: &[f64],
+    tile_size: T,
+    aggregation: &str,
+) -> (Vec<G>, Vec<T>, Vec<T>, Vec<f64>)
+where
+    G: GroupType,
+    T: PositionType,
+{
+    assert_eq!(starts.len(), ends.len());
+    assert_eq!(starts.len(), chrs.len());
+    assert_eq!(starts.len(), scores.len());
+    assert!(tile_size > T::zero());
+    let aggregation = TileAggregation::from_str(aggregation).expect("invalid aggregation string");
+
+    let denom = tile_size.to_f64().unwrap();
+    // (sum, count, max, min) of the weighted scores per tile
+    let mut tiles: FxHashMap<(G, T), (f64, u32, f64, f64)> = FxHashMap::default();
+
+    for i in 0..chrs.len() {
+        let (s, e) = (starts[i], ends[i]);
+
+        // Skip invalid intervals.
+        if e <= s {
+            continue;
+        }
+
+        let mut tile_start = floor_to_tile(s, tile_size);
+        while tile_start < e {
+            let tile_end = tile_start + tile_size;
+            let fraction = (tile_end.min(e) - tile_start.max(s)).to_f64().unwrap() / denom;
+            let weighted = scores[i] * fraction;
+
+            let acc = tiles
+                .entry((chrs[i], tile_start))
+                .or_insert((0.0, 0, f64::NEG_INFINITY, f64::INFINITY));
+            acc.0 += weighted;
+            acc.1 += 1;
+            acc.2 = acc.2.max(weighted);
+            acc.3 = acc.3.min(weighted);
+
+            tile_start = tile_end;
+        }
+    }
+
+    let mut tiles: Vec<_> = tiles.into_iter().collect();
+    sort_by_key(&mut tiles, |t| t.0 .1);
+    sort_by_key(&mut tiles, |t| t.0 .0);
+
+    let mut out_chrs = Vec::with_capacity(tiles.len());
+    let mut out_starts = Vec::with_capacity(tiles.len());
+    let mut out_ends = Vec::with_capacity(tiles.len());
+    let mut out_scores = Vec::with_capacity(tiles.len());
+    for ((chr, tile_start), (sum, count, max, min)) in tiles {
+        out_chrs.push(chr);
+        out_starts.push(tile_start);
+        out_ends.push(tile_start + tile_size);
+        out_scores.push(match aggregation {
+            TileAggregation::Sum => sum,
+            TileAggregation::Mean => sum / count as f64,
+            TileAggregation::Max => max,
+            TileAggregation::Min => min,
+        });
+    }
+
+    (out_chrs, out_starts, out_ends, out_scores)
+}
+
 use std::cmp::min;
 
 pub fn window_grouped<T, C>(
@@ -236,6 +596,7 @@ pub fn window_grouped<T, C>(
     ends: &[T],
     negative_strand: &[bool],
     window_size: T,
+    drop_partial: bool,
 ) -> (Vec<T>, Vec<T>, Vec<usize>)
 where
     T: PositionType,          // PrimInt + Signed + Zero + etc.
@@ -270,11 +631,14 @@ where
         // MINUS: how many bases we need at the LEFT of the next minus interval
         // to complete the current RIGHT-anchored window across this group
         let mut total_minus_len = T::zero();
+        let mut total_plus_len = T::zero();
         for i in g_start..g_end {
-            if negative_strand[i] {
-                let len = ends[i] - starts[i];
-                if len > T::zero() {
+            let len = ends[i] - starts[i];
+            if len > T::zero() {
+                if negative_strand[i] {
                     total_minus_len = total_minus_len + len;
+                } else {
+                    total_plus_len = total_plus_len + len;
                 }
             }
         }
@@ -284,6 +648,15 @@ where
             total_minus_len % window_size
         };
 
+        // With `drop_partial`, the last incomplete window of the group is not
+        // emitted. PLUS windows are phased from the left, so it is whatever
+        // lies past `plus_full_len`; MINUS windows are phased from the right,
+        // so it is the initial `minus_needed` bases. Positions still advance
+        // over the dropped bases, keeping the carry logic unchanged.
+        let plus_full_len = total_plus_len - total_plus_len % window_size;
+        let mut plus_offset = T::zero();
+        let mut minus_in_partial = drop_partial && minus_needed > T::zero();
+
         // ----- process intervals in the group -----
         for i in g_start..g_end {
             let s = starts[i];
@@ -303,10 +676,13 @@ where
                     let take = min(need, remaining);
                     if take > T::zero() {
                         let seg_end = cur + take;
-                        out_starts.push(cur);
-                        out_ends.push(seg_end);
-                        out_indices.push(i);
+                        if !drop_partial || plus_offset < plus_full_len {
+                            out_starts.push(cur);
+                            out_ends.push(seg_end);
+                            out_indices.push(i);
+                        }
 
+                        plus_offset = plus_offset + take;
                         cur = seg_end;
                         remaining = remaining - take;
                         carry_plus = carry_plus + take;
@@ -327,6 +703,7 @@ where
                     out_ends.push(seg_end);
                     out_indices.push(i);
 
+                    plus_offset = plus_offset + window_size;
                     cur = seg_end;
                     remaining = remaining - window_size;
                 }
@@ -334,9 +711,12 @@ where
                 // 3) Tail becomes next carry
                 if remaining > T::zero() {
                     let seg_end = e; // cur + remaining
-                    out_starts.push(cur);
-                    out_ends.push(seg_end);
-                    out_indices.push(i);
+                    if !drop_partial || plus_offset < plus_full_len {
+                        out_starts.push(cur);
+                        out_ends.push(seg_end);
+                        out_indices.push(i);
+                    }
+                    plus_offset = plus_offset + remaining;
                     carry_plus = remaining; // read at start of next interval in this group
                 } else {
                     carry_plus = T::zero();
@@ -353,11 +733,16 @@ where
                 if minus_needed > T::zero() {
                     let take = min(minus_needed, remaining);
                     let seg_end = cur + take;
-                    segs.push((cur, seg_end));
+                    if !minus_in_partial {
+                        segs.push((cur, seg_end));
+                    }
 
                     cur = seg_end;
                     remaining = remaining - take;
                     minus_needed = minus_needed - take;
+                    if minus_needed.is_zero() {
+                        minus_in_partial = false;
+                    }
 
                     if remaining.is_zero() {
                         // emit collected (just the leftmost piece) in reverse (trivial here)
@@ -404,4 +789,161 @@ where
     }
 
     (out_starts, out_ends, out_indices)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_n_spreads_remainder_over_first_bins() {
+        // Length 11 in 3 bins: 11 = 3 * 3 + 2, so the first two bins get 4 bases.
+        let (starts, ends, idx, ordinals) = tile_n(&[100i64], &[111i64], &[false], 3);
+        assert_eq!(starts, vec![100, 104, 108]);
+        assert_eq!(ends, vec![104, 108, 111]);
+        assert_eq!(idx, vec![0, 0, 0]);
+        assert_eq!(ordinals, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_tile_n_minus_strand_ordinals() {
+        // On the minus strand bin 0 is the rightmost one and takes the extra base.
+        let (starts, ends, _, ordinals) = tile_n(&[100i64], &[111i64], &[true], 3);
+        assert_eq!(starts, vec![107, 103, 100]);
+        assert_eq!(ends, vec![111, 107, 103]);
+        assert_eq!(ordinals, vec![0, 1, 2]);
+
+        // Shorter than n: only the non-empty bins are emitted, ordinals keep their slot.
+        let (starts, ends, _, ordinals) = tile_n(&[0i64], &[2i64], &[true], 4);
+        assert_eq!(starts, vec![1, 0]);
+        assert_eq!(ends, vec![2, 1]);
+        assert_eq!(ordinals, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_tile_aggregate_count_empty() {
+        let (chrs, starts, ends, counts) =
+            tile_aggregate_count::<u32, i64>(&[], &[], &[], 100);
+        assert!(chrs.is_empty());
+        assert!(starts.is_empty());
+        assert!(ends.is_empty());
+        assert!(counts.is_empty());
+    }
+
+    #[test]
+    fn test_tile_aggregate_count_single_interval() {
+        let (chrs, starts, ends, counts) =
+            tile_aggregate_count(&[3u32], &[150i64], &[420], 100);
+        assert_eq!(chrs, vec![3, 3, 3, 3]);
+        assert_eq!(starts, vec![100, 200, 300, 400]);
+        assert_eq!(ends, vec![200, 300, 400, 500]);
+        assert_eq!(counts, vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_window_grouped_drop_partial() {
+        // Two exons of 7 + 8 = 15 bases, windows of 4: three full windows
+        // (one spanning the exon junction) and a 3-base partial.
+        let chrs = [0u32, 0];
+        let starts = [0i64, 100];
+        let ends = [7i64, 108];
+
+        let (s, e, _) = window_grouped(&chrs, &starts, &ends, &[false, false], 4, true);
+        assert_eq!(s, vec![0, 4, 100, 101]);
+        assert_eq!(e, vec![4, 7, 101, 105]);
+
+        // On the minus strand the partial window is the leftmost 3 bases.
+        let (s, e, _) = window_grouped(&chrs, &starts, &ends, &[true, true], 4, false);
+        assert_eq!(s, vec![3, 0, 104, 100]);
+        assert_eq!(e, vec![7, 3, 108, 104]);
+
+        let (s, e, _) = window_grouped(&chrs, &starts, &ends, &[true, true], 4, true);
+        assert_eq!(s, vec![3, 104, 100]);
+        assert_eq!(e, vec![7, 108, 104]);
+    }
+
+    #[test]
+    fn test_tile_aggregate_count_overlapping_intervals() {
+        let (chrs, starts, _, counts) = tile_aggregate_count(
+            &[1u32, 0, 0],
+            &[0i64, 50, 120],
+            &[10, 150, 130],
+            100,
+        );
+        assert_eq!(chrs, vec![0, 0, 1]);
+        assert_eq!(starts, vec![0, 100, 0]);
+        assert_eq!(counts, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_tile_aggregate_count_skips_points_and_handles_negatives() {
+        // A point inside a covered tile is not counted; [-150,-20) spans two tiles.
+        let (_, starts, ends, counts) = tile_aggregate_count(
+            &[0u32, 0, 0],
+            &[-150i64, -50, -60],
+            &[-20, -50, -40],
+            100,
+        );
+        assert_eq!(starts, vec![-200, -100]);
+        assert_eq!(ends, vec![-100, 0]);
+        assert_eq!(counts, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_tile_grouped_tile_ids() {
+        let (starts, _, idx, _, _, tile_ids) =
+            tile_grouped_with_ids(&[0u32, 1], &[150i64, -50], &[320i64, 10], &[false, true], 100, None).unwrap();
+        assert_eq!(starts, vec![100, 200, 300, 0, -100]);
+        assert_eq!(idx, vec![0, 0, 0, 1, 1]);
+        assert_eq!(tile_ids, vec![1, 2, 3, 0, -1]);
+    }
+
+    #[test]
+    fn test_tile_grouped_clamps_to_chrom_lens() {
+        // Chromosome 0 is 250 bases long; an interval at 0 and one near the end.
+        let mut lens = FxHashMap::default();
+        lens.insert(0u32, 250i64);
+        let (starts, ends, idx, overlaps, _, tile_ids) = tile_grouped_with_ids(
+            &[0u32, 0], &[0i64, 180], &[30i64, 260], &[false, false], 100, Some(&lens),
+        )
+        .unwrap();
+        // The tile [200,300) is cut at 250 and [300,400) past the end is dropped;
+        // fractions still count against the full tile size.
+        assert_eq!(starts, vec![0, 100, 200]);
+        assert_eq!(ends, vec![100, 200, 250]);
+        assert_eq!(idx, vec![0, 1, 1]);
+        assert_eq!(overlaps, vec![0.3, 0.2, 0.6]);
+        assert_eq!(tile_ids, vec![0, 1, 2]);
+
+        lens.clear();
+        assert!(tile_grouped(&[0u32], &[0i64], &[30i64], &[false], 100, Some(&lens)).is_err());
+    }
+
+    #[test]
+    fn test_tile_aggregate_scores_weighted_by_tile_fraction() {
+        // Interval 0 covers all of [0,100) and half of [100,200);
+        // interval 1 covers a quarter of [100,200).
+        let chrs = [0u32, 0];
+        let starts = [0i64, 150];
+        let ends = [150i64, 175];
+        let scores = [2.0, 8.0];
+
+        let expected = [("sum", [2.0, 3.0]), ("mean", [2.0, 1.5]), ("max", [2.0, 2.0]), ("min", [2.0, 1.0])];
+        for (aggregation, values) in expected {
+            let (_, t_starts, _, t_scores) =
+                tile_aggregate_scores(&chrs, &starts, &ends, &scores, 100, aggregation);
+            assert_eq!(t_starts, vec![0, 100]);
+            assert_eq!(t_scores, values.to_vec(), "aggregation {aggregation}");
+        }
+    }
+
+    #[test]
+    fn test_tile_zero_length_interval() {
+        let (starts, ends, idx, overlaps, fractions) =
+            tile_with_interval_fraction(&[150i64, -5], &[150i64, -5], &[false, true], 100);
+        assert_eq!(starts, vec![100, -100]);
+        assert_eq!(ends, vec![200, 0]);
+        assert_eq!(idx, vec![0, 1]);
+        assert_eq!(overlaps, vec![0.0, 0.0]);
+        assert_eq!(fractions, vec![1.0, 1.0]);
+    }
+}
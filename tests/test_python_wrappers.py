@@ -0,0 +1,176 @@
+"""Tests for the pure-Python wrappers in ``ruranges/__init__.py``.
+
+Run with ``pytest`` after ``maturin develop``.
+"""
+
+import numpy as np
+import pytest
+
+import ruranges
+
+
+def test_overlaps_polars_matches_chromosomes_by_name():
+    pl = pytest.importorskip("polars")
+
+    df = pl.DataFrame(
+        {"Chromosome": ["chr1", "chr2"], "Start": [0, 0], "End": [10, 10]}
+    )
+    # Categories in the opposite order, so the physical codes differ from df.
+    df2 = pl.DataFrame(
+        {"Chromosome": ["chr2", "chr1"], "Start": [5, 20], "End": [15, 30]},
+        schema_overrides={"Chromosome": pl.Categorical},
+    )
+
+    out = ruranges.overlaps_polars(df, df2)
+
+    assert out.columns == ["idx", "idx2"]
+    assert out.schema["idx"] == pl.UInt32
+    assert out["idx"].to_list() == [1]
+    assert out["idx2"].to_list() == [0]
+
+
+def test_overlaps_polars_custom_columns_and_kwargs():
+    pl = pytest.importorskip("polars")
+
+    df = pl.DataFrame({"chrom": ["a"], "s": [0], "e": [10]})
+    df2 = pl.DataFrame({"chrom": ["a"], "s": [12], "e": [20]})
+
+    out = ruranges.overlaps_polars(df, df2, chrom_col="chrom", start_col="s", end_col="e")
+    assert out.height == 0
+
+    out = ruranges.overlaps_polars(
+        df, df2, chrom_col="chrom", start_col="s", end_col="e", slack=3
+    )
+    assert out["idx"].to_list() == [0]
+    assert out["idx2"].to_list() == [0]
+
+
+def test_remap_codes_into_target_code_space():
+    codes = np.array([0, 1, 2, 0, 3], dtype=np.uint8)
+    categories = ["chr2", "chr1", "chrM", "chrY"]
+    target = ["chr1", "chr2", "chrX"]
+
+    remapped = ruranges.remap_codes(codes, categories, target)
+
+    assert remapped.dtype == np.uint32
+    # Shared names take the target's codes; missing ones get fresh codes
+    # from len(target) on, so they match nothing in the target frame.
+    np.testing.assert_array_equal(remapped, [1, 0, 3, 1, 4])
+
+
+def test_remap_codes_identity_and_empty():
+    categories = np.array(["a", "b"], dtype=object)
+    codes = np.array([1, 0, 1])
+    np.testing.assert_array_equal(ruranges.remap_codes(codes, categories, categories), codes)
+
+    empty = ruranges.remap_codes(np.array([], dtype=np.int64), categories, ["b"])
+    assert empty.dtype == np.uint32 and empty.size == 0
+
+
+def test_extend_clips_to_each_rows_chromosome():
+    # Two transcripts (7 and 9) on chromosomes 0 and 1.
+    groups = np.array([7, 7, 9], dtype=np.uint32)
+    starts = np.array([5, 50, 990], dtype=np.int64)
+    ends = np.array([20, 80, 995], dtype=np.int64)
+    negative_strand = np.zeros(3, dtype=bool)
+
+    new_starts, new_ends = ruranges.extend(
+        starts=starts,
+        ends=ends,
+        negative_strand=negative_strand,
+        groups=groups,
+        ext=10,
+        chrs=np.array([0, 0, 1], dtype=np.uint32),
+        chrom_ids=np.array([0, 1], dtype=np.uint32),
+        chrom_length=np.array([85, 1000], dtype=np.int64),
+    )
+
+    np.testing.assert_array_equal(new_starts, [0, 50, 980])
+    np.testing.assert_array_equal(new_ends, [20, 85, 1000])
+
+    with pytest.raises(ValueError):
+        ruranges.extend(
+            starts=starts,
+            ends=ends,
+            negative_strand=negative_strand,
+            groups=groups,
+            ext=10,
+            chrom_ids=np.array([0, 1], dtype=np.uint32),
+            chrom_length=np.array([85, 1000], dtype=np.int64),
+        )
+
+
+def test_outside_bounds_circular_casts_length_table():
+    # The length table's dtypes differ from the interval set's.
+    idx, new_starts, new_ends = ruranges.outside_bounds_circular(
+        groups=np.array([0, 0], dtype=np.uint32),
+        starts=np.array([90, 150], dtype=np.int64),
+        ends=np.array([110, 160], dtype=np.int64),
+        chrom_len_ids=np.array([0], dtype=np.int64),
+        chrom_lens=np.array([100], dtype=np.int32),
+    )
+
+    np.testing.assert_array_equal(idx, [0, 0, 1])
+    np.testing.assert_array_equal(new_starts, [90, 0, 50])
+    np.testing.assert_array_equal(new_ends, [100, 10, 60])
+
+
+def test_overlaps_and_nearest_return_named_tuples():
+    starts = np.array([0, 20], dtype=np.int64)
+    ends = np.array([10, 30], dtype=np.int64)
+    starts2 = np.array([5, 40], dtype=np.int64)
+    ends2 = np.array([8, 50], dtype=np.int64)
+
+    res = ruranges.overlaps(starts=starts, ends=ends, starts2=starts2, ends2=ends2)
+    assert isinstance(res, tuple) and len(res) == 2
+    idx1, idx2 = res
+    np.testing.assert_array_equal(res.query_idx, idx1)
+    assert list(res.to_dict()) == ["query_idx", "subject_idx"]
+
+    res = ruranges.nearest(starts=starts, ends=ends, starts2=starts2, ends2=ends2)
+    assert isinstance(res, tuple) and len(res) == 3
+    np.testing.assert_array_equal(res.distance, res[2])
+
+    res = ruranges.nearest(
+        starts=starts, ends=ends, starts2=starts2, ends2=ends2, return_ties=True
+    )
+    assert len(res) == 4
+    assert res.n_ties is res[3]
+
+
+def test_tile_grouped_resolves_on_wider_ends():
+    # int64 ends past the int32 range must not be narrowed to the starts dtype.
+    end = 2**31 + 100
+    out = ruranges.tile_grouped(
+        starts=np.array([2**31 - 50], dtype=np.int32),
+        ends=np.array([end], dtype=np.int64),
+        negative_strand=np.array([False]),
+        tile_size=100,
+        groups=np.array([0], dtype=np.uint32),
+    )
+    assert out[2].dtype == np.int64
+    assert out[2].max() >= end
+
+    with pytest.raises(ValueError):
+        ruranges.tile_grouped(
+            starts=np.array([0], dtype=np.int64),
+            ends=np.array([10], dtype=np.int64),
+            negative_strand=np.array([False]),
+            tile_size=0,
+            groups=np.array([0], dtype=np.uint32),
+        )
+
+
+def test_write_gtf_rejects_coordinates_past_int32(tmp_path):
+    with pytest.raises(ValueError):
+        ruranges.write_gtf(
+            tmp_path / "out.gtf",
+            chr_codes=np.array([0], dtype=np.uint32),
+            starts=np.array([2**31], dtype=np.int64),
+            ends=np.array([2**31 + 10], dtype=np.int64),
+            strands=np.array([True]),
+            features=["exon"],
+            attributes=['gene_id "g";'],
+            chrom_names=["chr1"],
+        )
+    assert not (tmp_path / "out.gtf").exists()